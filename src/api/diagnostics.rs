@@ -0,0 +1,194 @@
+use std::fmt;
+
+use super::module_directory::{ModuleDirectory, SourceLocation};
+use super::parsing::RustFile;
+
+/// A problem found in the source files of the crate (e.g. a syntax error), which didn't prevent
+/// the extraction of its public API.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Diagnostic {
+    /// The description of the problem
+    pub message: String,
+    /// The location of the problem
+    pub location: SourceLocation,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.location.path.display(),
+            self.location.span.start_line,
+            self.location.span.start_column,
+            self.message
+        )
+    }
+}
+
+/// Collect the diagnostics of the module directories (e.g. the syntax errors of their files and
+/// the modules whose files couldn't be found).
+pub fn collect_diagnostics(module_directories: &[ModuleDirectory]) -> Vec<Diagnostic> {
+    module_directories
+        .iter()
+        .flat_map(|directory| {
+            let file_diagnostics = std::iter::once(&directory.entry_point)
+                .chain(directory.internal_files.values())
+                .flat_map(get_file_diagnostics);
+            let unresolved_module_diagnostics =
+                directory
+                    .unresolved_modules
+                    .iter()
+                    .map(|unresolved_module| Diagnostic {
+                        message: unresolved_module.message.clone(),
+                        location: unresolved_module.location.clone(),
+                    });
+            file_diagnostics.chain(unresolved_module_diagnostics)
+        })
+        .collect()
+}
+
+fn get_file_diagnostics(file: &RustFile) -> impl Iterator<Item = Diagnostic> + '_ {
+    file.syntax_errors.iter().map(|syntax_error| Diagnostic {
+        message: syntax_error.message.clone(),
+        location: SourceLocation {
+            path: file.path.clone(),
+            span: syntax_error.span,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::module_directory::UnresolvedModule;
+    use crate::api::parsing::{SourceSpan, SyntaxError};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn make_span(line: usize) -> SourceSpan {
+        SourceSpan {
+            start_line: line,
+            start_column: 1,
+            end_line: line,
+            end_column: 2,
+        }
+    }
+
+    fn make_file(path: &str, syntax_errors: Vec<SyntaxError>) -> RustFile {
+        RustFile {
+            path: PathBuf::from(path),
+            span: SourceSpan::default(),
+            doc_comment: None,
+            symbols: Vec::new(),
+            syntax_errors,
+        }
+    }
+
+    fn make_syntax_error(line: usize) -> SyntaxError {
+        SyntaxError {
+            message: "Missing `;`".to_string(),
+            span: make_span(line),
+        }
+    }
+
+    #[test]
+    fn valid_files() {
+        let directories = vec![ModuleDirectory {
+            name: String::new(),
+            is_public: true,
+            entry_point: make_file("src/lib.rs", Vec::new()),
+            internal_files: HashMap::new(),
+            cfg: None,
+            is_doc_hidden: false,
+            unresolved_modules: Vec::new(),
+        }];
+
+        let diagnostics = collect_diagnostics(&directories);
+
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let directories = vec![ModuleDirectory {
+            name: String::new(),
+            is_public: true,
+            entry_point: make_file("src/lib.rs", vec![make_syntax_error(2)]),
+            internal_files: HashMap::from([(
+                "submodule".to_string(),
+                make_file("src/submodule.rs", vec![make_syntax_error(3)]),
+            )]),
+            cfg: None,
+            is_doc_hidden: false,
+            unresolved_modules: Vec::new(),
+        }];
+
+        let diagnostics = collect_diagnostics(&directories);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    message: "Missing `;`".to_string(),
+                    location: SourceLocation {
+                        path: PathBuf::from("src/lib.rs"),
+                        span: make_span(2),
+                    },
+                },
+                Diagnostic {
+                    message: "Missing `;`".to_string(),
+                    location: SourceLocation {
+                        path: PathBuf::from("src/submodule.rs"),
+                        span: make_span(3),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unresolved_modules() {
+        let location = SourceLocation {
+            path: PathBuf::from("src/lib.rs"),
+            span: make_span(1),
+        };
+        let directories = vec![ModuleDirectory {
+            name: String::new(),
+            is_public: true,
+            entry_point: make_file("src/lib.rs", Vec::new()),
+            internal_files: HashMap::new(),
+            cfg: None,
+            is_doc_hidden: false,
+            unresolved_modules: vec![UnresolvedModule {
+                name: "generated".to_string(),
+                message: "Could not find module generated".to_string(),
+                location: location.clone(),
+            }],
+        }];
+
+        let diagnostics = collect_diagnostics(&directories);
+
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic {
+                message: "Could not find module generated".to_string(),
+                location,
+            }]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let diagnostic = Diagnostic {
+            message: "Missing `;`".to_string(),
+            location: SourceLocation {
+                path: PathBuf::from("src/lib.rs"),
+                span: make_span(2),
+            },
+        };
+
+        assert_eq!(diagnostic.to_string(), "src/lib.rs:2:1: Missing `;`");
+    }
+}
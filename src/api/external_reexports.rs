@@ -0,0 +1,265 @@
+use daipendency_extractor::Symbol;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::namespace_construction::{RustNamespace, SymbolDetails};
+use super::parsing::ImportType;
+use super::symbol_resolution::{
+    assign_public_paths, rename_symbol_in_source_code, ExternalReexport, SymbolDeclaration,
+    SymbolResolution,
+};
+
+/// Provider of the public API of a dependency, given the name of its crate (e.g. `serde_json`).
+pub type DependencyApiProvider<'a> = dyn FnMut(&str) -> Option<Rc<Vec<RustNamespace>>> + 'a;
+
+/// Replace the reexports of items from external crates with the items themselves, as rustdoc does
+/// when it inlines them.
+///
+/// Reexports whose items can't be found (e.g. because the dependency is unavailable) are preserved
+/// as `pub use` declarations.
+pub fn inline_external_reexports(
+    resolution: &mut SymbolResolution,
+    get_dependency_api: &mut DependencyApiProvider,
+) {
+    let mut dependency_apis: HashMap<String, Option<Rc<Vec<RustNamespace>>>> = HashMap::new();
+    let declarations = std::mem::take(&mut resolution.symbols);
+    for declaration in declarations {
+        let Some(reexport) = &declaration.external_reexport else {
+            resolution.symbols.push(declaration);
+            continue;
+        };
+        let crate_name = reexport.source_path.split("::").next().unwrap();
+        let dependency_api = dependency_apis
+            .entry(crate_name.to_string())
+            .or_insert_with(|| get_dependency_api(crate_name));
+        let symbols = match dependency_api {
+            Some(namespaces) => find_reexported_symbols(reexport, namespaces),
+            None => Vec::new(),
+        };
+        if symbols.is_empty() {
+            resolution.symbols.push(declaration);
+            continue;
+        }
+        resolution
+            .symbols
+            .extend(symbols.into_iter().map(|details| SymbolDeclaration {
+                symbol: details.symbol,
+                modules: declaration.modules.clone(),
+                is_doc_hidden: declaration.is_doc_hidden,
+                kind: details.kind,
+                generics: details.generics,
+                module_path: format!("::{}", details.module_path),
+                canonical_path: format!("::{}", details.canonical_path),
+                public_paths: Vec::new(),
+                location: details.location,
+                external_reexport: None,
+            }));
+    }
+    assign_public_paths(&mut resolution.symbols);
+}
+
+fn find_reexported_symbols(
+    reexport: &ExternalReexport,
+    namespaces: &[RustNamespace],
+) -> Vec<SymbolDetails> {
+    let find_namespace = |name: &str| namespaces.iter().find(|namespace| namespace.name == name);
+    if let ImportType::Wildcard = reexport.import_type {
+        return find_namespace(&reexport.source_path)
+            .map(|namespace| namespace.symbols.clone())
+            .unwrap_or_default();
+    }
+
+    let Some((namespace_name, symbol_name)) = reexport.source_path.rsplit_once("::") else {
+        return Vec::new();
+    };
+    let Some(details) = find_namespace(namespace_name)
+        .and_then(|namespace| namespace.get_symbol(symbol_name))
+        .cloned()
+    else {
+        return Vec::new();
+    };
+    match &reexport.import_type {
+        ImportType::Aliased(alias) => vec![SymbolDetails {
+            symbol: Symbol {
+                name: alias.clone(),
+                source_code: rename_symbol_in_source_code(&details.symbol, alias),
+            },
+            ..details
+        }],
+        _ => vec![details],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::module_directory::SourceLocation;
+    use crate::api::namespace_construction::VisibilityOrigin;
+    use crate::api::parsing::SymbolKind;
+    use crate::test_helpers::stub_symbol_with_name;
+    use std::collections::HashSet;
+
+    const DEPENDENCY_NAME: &str = "dependency";
+
+    fn make_resolution(source_path: &str, import_type: ImportType) -> SymbolResolution {
+        SymbolResolution {
+            symbols: vec![SymbolDeclaration {
+                symbol: stub_symbol_with_name("reexport"),
+                modules: vec![String::new()],
+                is_doc_hidden: false,
+                kind: SymbolKind::Reexport,
+                generics: None,
+                module_path: String::new(),
+                canonical_path: String::new(),
+                public_paths: Vec::new(),
+                external_reexport: Some(ExternalReexport {
+                    source_path: source_path.to_string(),
+                    import_type,
+                }),
+                location: SourceLocation::default(),
+            }],
+            doc_comments: HashMap::new(),
+            module_locations: HashMap::new(),
+            unresolved_modules: HashSet::new(),
+            unresolved_includes: HashMap::new(),
+        }
+    }
+
+    fn make_symbol_details(symbol: Symbol, kind: SymbolKind, module_path: &str) -> SymbolDetails {
+        SymbolDetails {
+            canonical_path: format!("{}::{}", module_path, symbol.name),
+            symbol,
+            kind,
+            generics: None,
+            visibility_origin: VisibilityOrigin::Declaration,
+            module_path: module_path.to_string(),
+            public_paths: Vec::new(),
+            location: SourceLocation::default(),
+        }
+    }
+
+    fn make_dependency_api() -> Rc<Vec<RustNamespace>> {
+        let prelude_name = format!("{}::prelude", DEPENDENCY_NAME);
+        Rc::new(vec![
+            RustNamespace {
+                name: DEPENDENCY_NAME.to_string(),
+                symbols: vec![make_symbol_details(
+                    Symbol {
+                        name: "Foo".to_string(),
+                        source_code: "pub struct Foo;".to_string(),
+                    },
+                    SymbolKind::Struct,
+                    DEPENDENCY_NAME,
+                )],
+                doc_comment: None,
+                location: None,
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            },
+            RustNamespace {
+                name: prelude_name.clone(),
+                symbols: vec![
+                    make_symbol_details(
+                        stub_symbol_with_name("Bar"),
+                        SymbolKind::Function,
+                        &prelude_name,
+                    ),
+                    make_symbol_details(
+                        stub_symbol_with_name("Baz"),
+                        SymbolKind::Function,
+                        &prelude_name,
+                    ),
+                ],
+                doc_comment: None,
+                location: None,
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            },
+        ])
+    }
+
+    fn get_dependency_api(crate_name: &str) -> Option<Rc<Vec<RustNamespace>>> {
+        (crate_name == DEPENDENCY_NAME).then(make_dependency_api)
+    }
+
+    #[test]
+    fn simple_reexport() {
+        let mut resolution = make_resolution("dependency::Foo", ImportType::Simple);
+
+        inline_external_reexports(&mut resolution, &mut get_dependency_api);
+
+        assert_eq!(resolution.symbols.len(), 1);
+        let declaration = &resolution.symbols[0];
+        assert_eq!(declaration.symbol.name, "Foo");
+        assert_eq!(declaration.symbol.source_code, "pub struct Foo;");
+        assert_eq!(declaration.modules, vec![String::new()]);
+        assert_eq!(declaration.kind, SymbolKind::Struct);
+        assert_eq!(declaration.module_path, "::dependency");
+        assert_eq!(declaration.canonical_path, "::dependency::Foo");
+        assert_eq!(declaration.public_paths, vec!["crate::Foo"]);
+        assert!(declaration.external_reexport.is_none());
+    }
+
+    #[test]
+    fn aliased_reexport() {
+        let mut resolution =
+            make_resolution("dependency::Foo", ImportType::Aliased("Qux".to_string()));
+
+        inline_external_reexports(&mut resolution, &mut get_dependency_api);
+
+        assert_eq!(resolution.symbols.len(), 1);
+        let symbol = &resolution.symbols[0].symbol;
+        assert_eq!(symbol.name, "Qux");
+        assert_eq!(symbol.source_code, "pub struct Qux;");
+    }
+
+    #[test]
+    fn wildcard_reexport() {
+        let mut resolution = make_resolution("dependency::prelude", ImportType::Wildcard);
+
+        inline_external_reexports(&mut resolution, &mut get_dependency_api);
+
+        let names: Vec<_> = resolution
+            .symbols
+            .iter()
+            .map(|declaration| declaration.symbol.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Bar", "Baz"]);
+    }
+
+    #[test]
+    fn missing_symbol() {
+        let mut resolution = make_resolution("dependency::Missing", ImportType::Simple);
+
+        inline_external_reexports(&mut resolution, &mut get_dependency_api);
+
+        assert_eq!(resolution.symbols.len(), 1);
+        assert_eq!(resolution.symbols[0].symbol.name, "reexport");
+    }
+
+    #[test]
+    fn unavailable_dependency() {
+        let mut resolution = make_resolution("other::Foo", ImportType::Simple);
+
+        inline_external_reexports(&mut resolution, &mut get_dependency_api);
+
+        assert_eq!(resolution.symbols.len(), 1);
+        assert_eq!(resolution.symbols[0].symbol.name, "reexport");
+    }
+
+    #[test]
+    fn dependency_api_requested_once() {
+        let mut resolution = make_resolution("dependency::Foo", ImportType::Simple);
+        resolution
+            .symbols
+            .extend(make_resolution("dependency::prelude", ImportType::Wildcard).symbols);
+        let mut request_count = 0;
+
+        inline_external_reexports(&mut resolution, &mut |crate_name: &str| {
+            request_count += 1;
+            get_dependency_api(crate_name)
+        });
+
+        assert_eq!(request_count, 1);
+    }
+}
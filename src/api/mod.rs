@@ -1,6 +1,24 @@
+//! The pipeline that builds the public API of a crate, exposed stage by stage so that its
+//! intermediate results can be inspected or post-processed:
+//!
+//! 1. [`collect_module_directories`] parses the source files of the crate into
+//!    [`ModuleDirectory`]s.
+//! 2. [`extract_modules`] splits the module directories into [`Module`]s.
+//! 3. [`resolve_symbols`] matches the reexports in the modules with their definitions, yielding a
+//!    [`SymbolResolution`]. [`inline_external_reexports`] can optionally replace the reexports of
+//!    items from dependencies with the items themselves.
+//! 4. [`construct_namespaces`] (or [`construct_rust_namespaces`]) groups the resolved symbols into
+//!    namespaces.
+//!
+//! [`build_public_api`] and [`build_rust_api`] run the whole pipeline. The problems found in the
+//! source files along the way (e.g. syntax errors) can be gathered with [`collect_diagnostics`].
+
+mod diagnostics;
+mod external_reexports;
 mod module_directory;
 mod module_extraction;
 mod namespace_construction;
+mod options;
 mod parsing;
 mod symbol_collection;
 mod symbol_resolution;
@@ -8,30 +26,139 @@ mod test_helpers;
 
 use daipendency_extractor::ExtractionError;
 use daipendency_extractor::Namespace;
-use module_extraction::extract_modules;
+use std::collections::HashMap;
 use std::path::Path;
 use tree_sitter::Parser;
 
-use namespace_construction::construct_namespaces;
-use symbol_collection::collect_module_directories;
-use symbol_resolution::resolve_symbols;
+pub use diagnostics::{collect_diagnostics, Diagnostic};
+pub use external_reexports::{inline_external_reexports, DependencyApiProvider};
+pub use module_directory::{
+    Module, ModuleDirectory, ModuleItem, SourceLocation, UnresolvedInclude, UnresolvedModule,
+};
+pub use module_extraction::{extract_modules, DEFAULT_MAX_MACRO_RULES};
+pub use namespace_construction::{
+    construct_namespaces, construct_rust_namespaces, RustNamespace, SymbolDetails,
+    VisibilityOrigin, INCLUDE_PLACEHOLDER_NAME, UNRESOLVED_MODULE_DOC_COMMENT,
+};
+pub use options::ExtractionOptions;
+pub use parsing::{
+    parse_rust_file, CfgOptions, CfgPredicate, ImportType, IncludePath, RustFile, RustSymbol,
+    SourceSpan, SymbolKind, SyntaxError, Visibility,
+};
+pub use symbol_collection::collect_module_directories;
+pub use symbol_resolution::{
+    resolve_symbols, ExternalReexport, SymbolDeclaration, SymbolResolution,
+};
+
+/// The public API of a crate, along with the problems found in its source files.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RustApi {
+    /// The namespaces of the public API, starting with the crate root
+    pub namespaces: Vec<RustNamespace>,
+    /// The problems found in the source files of the crate (e.g. syntax errors)
+    pub diagnostics: Vec<Diagnostic>,
+}
 
+/// Build the public API of the crate at `entry_point`, as configured by `options`.
+///
+/// Items disabled by the features and target configuration in `options` are pruned, unless they're
+/// annotated instead. Items hidden from the documentation (i.e. `#[doc(hidden)]`) are excluded
+/// unless `options` include them.
+///
+/// Modules whose files can't be found abort the extraction unless `options` tolerate them, in which
+/// case they're reported as diagnostics and their namespaces are marked as unresolved, with the
+/// doc comment [`UNRESOLVED_MODULE_DOC_COMMENT`] once converted to [`Namespace`]s.
+///
+/// Files included with `include!` are spliced into the modules including them, where they can be
+/// found. Paths appended to `OUT_DIR` are looked up in the output directories in `options`, in
+/// order. The invocations whose files can't be found are recorded in the namespaces of their
+/// modules, and left as placeholder symbols named [`INCLUDE_PLACEHOLDER_NAME`] once converted to
+/// [`Namespace`]s.
+///
+/// `dependencies` maps the names the crate's dependencies are referred to in the code to the names
+/// of their crates, so that reexports from renamed dependencies can be resolved. The items reexported
+/// from dependencies are inlined if `options` say so and `get_dependency_api` is set, and preserved
+/// as `pub use` declarations otherwise.
 pub fn build_public_api(
     entry_point: &Path,
     crate_name: &str,
+    options: &ExtractionOptions,
+    dependencies: &HashMap<String, String>,
+    get_dependency_api: Option<&mut DependencyApiProvider>,
     parser: &mut Parser,
 ) -> Result<Vec<Namespace>, ExtractionError> {
-    let module_directories = collect_module_directories(entry_point, parser)?;
-    let modules = extract_modules(&module_directories)?;
-    let resolution = resolve_symbols(&modules)?;
-    let namespaces = construct_namespaces(resolution, crate_name);
-    Ok(namespaces)
+    let (resolution, _) = resolve_public_api(
+        entry_point,
+        options,
+        dependencies,
+        get_dependency_api,
+        parser,
+    )?;
+    Ok(construct_namespaces(resolution, crate_name))
+}
+
+/// Build the public API of the crate at `entry_point` like [`build_public_api`], retaining the
+/// details of each symbol (e.g. its kind) and the diagnostics of the source files.
+pub fn build_rust_api(
+    entry_point: &Path,
+    crate_name: &str,
+    options: &ExtractionOptions,
+    dependencies: &HashMap<String, String>,
+    get_dependency_api: Option<&mut DependencyApiProvider>,
+    parser: &mut Parser,
+) -> Result<RustApi, ExtractionError> {
+    let (resolution, diagnostics) = resolve_public_api(
+        entry_point,
+        options,
+        dependencies,
+        get_dependency_api,
+        parser,
+    )?;
+    Ok(RustApi {
+        namespaces: construct_rust_namespaces(resolution, crate_name),
+        diagnostics,
+    })
+}
+
+fn resolve_public_api(
+    entry_point: &Path,
+    options: &ExtractionOptions,
+    dependencies: &HashMap<String, String>,
+    get_dependency_api: Option<&mut DependencyApiProvider>,
+    parser: &mut Parser,
+) -> Result<(SymbolResolution, Vec<Diagnostic>), ExtractionError> {
+    let module_directories = collect_module_directories(entry_point, options, parser)?;
+    let diagnostics = collect_diagnostics(&module_directories);
+    if options.strict_parsing && !diagnostics.is_empty() {
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.to_string()).collect();
+        return Err(ExtractionError::Malformed(messages.join("\n")));
+    }
+    let modules = extract_modules(&module_directories, options)?;
+    let mut resolution = resolve_symbols(&modules, options, dependencies)?;
+    if let Some(get_dependency_api) = get_dependency_api.filter(|_| options.inline_reexports) {
+        inline_external_reexports(&mut resolution, get_dependency_api);
+    }
+    Ok((resolution, diagnostics))
+}
+
+/// Extract the documentation of the crate from the inner doc comments of its entry point, without
+/// their markers (e.g. `My crate` for `//! My crate`).
+pub fn extract_crate_documentation(
+    entry_point: &Path,
+    parser: &mut Parser,
+) -> Result<Option<String>, ExtractionError> {
+    let content = std::fs::read_to_string(entry_point)?;
+    let file = parsing::parse_rust_file(&content, entry_point, parser)?;
+    Ok(file
+        .doc_comment
+        .map(|doc_comment| parsing::strip_inner_doc_comment_markers(&doc_comment)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_helpers::setup_parser;
+    use crate::test_helpers::{get_namespace, setup_parser};
     use assertables::assert_matches;
     use daipendency_testing::tempdir::TempDir;
 
@@ -42,11 +169,258 @@ mod tests {
         let mut parser = setup_parser();
         let path = std::path::PathBuf::from("nonexistent.rs");
 
-        let result = build_public_api(&path, STUB_CRATE_NAME, &mut parser);
+        let result = build_public_api(
+            &path,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        );
 
         assert_matches!(result, Err(ExtractionError::Io(_)));
     }
 
+    #[test]
+    fn pipeline_stages() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"
+pub mod module;
+pub use module::Format;
+"#,
+            )
+            .unwrap();
+        temp_dir
+            .create_file("src/module.rs", "pub struct Format;")
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let module_directories =
+            collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                .unwrap();
+        let modules = extract_modules(&module_directories, &ExtractionOptions::default()).unwrap();
+        let resolution =
+            resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+        let namespaces = construct_namespaces(resolution, STUB_CRATE_NAME);
+
+        assert_eq!(module_directories.len(), 1);
+        let module_names: Vec<_> = modules.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(module_names, vec!["", "module"]);
+        assert_eq!(namespaces.len(), 2);
+        let root = get_namespace(STUB_CRATE_NAME, &namespaces).unwrap();
+        assert!(root.get_symbol("Format").is_some());
+    }
+
+    #[test]
+    fn source_locations() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("README.md", "# Test crate\n\nDoes things.\n")
+            .unwrap();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"#![doc = include_str!("../README.md")]
+
+pub mod module;
+pub use module::Format;
+
+pub fn process() {}
+"#,
+            )
+            .unwrap();
+        let module_rs = temp_dir
+            .create_file("src/module.rs", "\npub struct Format;\n")
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let api = build_rust_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        let namespaces = &api.namespaces;
+        let root = namespaces
+            .iter()
+            .find(|n| n.name == STUB_CRATE_NAME)
+            .unwrap();
+        assert_eq!(root.location.as_ref().unwrap().path, lib_rs);
+        let process = &root.get_symbol("process").unwrap().location;
+        assert_eq!(process.path, lib_rs);
+        assert_eq!((process.span.start_line, process.span.start_column), (6, 1));
+        let format = &root.get_symbol("Format").unwrap().location;
+        assert_eq!(format.path, module_rs);
+        assert_eq!(format.span.start_line, 2);
+        let module = namespaces
+            .iter()
+            .find(|n| n.name == format!("{STUB_CRATE_NAME}::module"))
+            .unwrap();
+        assert_eq!(module.location.as_ref().unwrap().path, module_rs);
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file("src/lib.rs", "pub mod module;\npub fn process() {}\n")
+            .unwrap();
+        let module_rs = temp_dir
+            .create_file("src/module.rs", "pub struct Format\n")
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let api = build_rust_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        assert_eq!(api.diagnostics.len(), 1);
+        let diagnostic = &api.diagnostics[0];
+        assert_eq!(diagnostic.message, "Missing `;`");
+        assert_eq!(diagnostic.location.path, module_rs);
+        let root = api
+            .namespaces
+            .iter()
+            .find(|n| n.name == STUB_CRATE_NAME)
+            .unwrap();
+        assert!(root.get_symbol("process").is_some());
+    }
+
+    #[test]
+    fn enum_variants_glob() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"
+mod format;
+pub use format::Format::*;
+"#,
+            )
+            .unwrap();
+        temp_dir
+            .create_file("src/format.rs", "pub enum Format { Text, Binary }")
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        let root = get_namespace(STUB_CRATE_NAME, &namespaces).unwrap();
+        let mut symbol_names: Vec<_> = root.symbols.iter().map(|s| s.name.as_str()).collect();
+        symbol_names.sort();
+        assert_eq!(symbol_names, vec!["Binary", "Text"]);
+        assert_eq!(
+            root.get_symbol("Text").unwrap().source_code,
+            "pub use format::Format::Text;"
+        );
+    }
+
+    #[test]
+    fn unresolved_reexport_paths() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file("src/lib.rs", "pub use self::missing::Foo;\n")
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let api = build_rust_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        let root = api
+            .namespaces
+            .iter()
+            .find(|n| n.name == STUB_CRATE_NAME)
+            .unwrap();
+        let foo = root.get_symbol("Foo").unwrap();
+        assert_eq!(
+            foo.canonical_path,
+            format!("{}::missing::Foo", STUB_CRATE_NAME)
+        );
+        assert_eq!(foo.public_paths, vec![format!("{}::Foo", STUB_CRATE_NAME)]);
+    }
+
+    #[test]
+    fn aliased_enum_variants() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"
+pub mod format;
+pub use format::Format::{Binary, Text as Plain};
+"#,
+            )
+            .unwrap();
+        temp_dir
+            .create_file("src/format.rs", "pub enum Format { Text, Binary }")
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let api = build_rust_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        let root = api
+            .namespaces
+            .iter()
+            .find(|n| n.name == STUB_CRATE_NAME)
+            .unwrap();
+        let mut symbol_names: Vec<_> = root
+            .symbols
+            .iter()
+            .map(|details| details.symbol.name.as_str())
+            .collect();
+        symbol_names.sort();
+        assert_eq!(symbol_names, vec!["Binary", "Plain"]);
+        let plain = root.get_symbol("Plain").unwrap();
+        assert_eq!(
+            plain.symbol.source_code,
+            "pub use format::Format::Text as Plain;"
+        );
+        assert_eq!(
+            plain.canonical_path,
+            format!("{}::format::Format::Text", STUB_CRATE_NAME)
+        );
+        assert_eq!(
+            plain.public_paths,
+            vec![format!("{}::Plain", STUB_CRATE_NAME)]
+        );
+    }
+
     #[test]
     fn integration() {
         let temp_dir = TempDir::new();
@@ -76,7 +450,15 @@ pub enum Format {
             .unwrap();
         let mut parser = setup_parser();
 
-        let namespaces = build_public_api(&lib_rs, STUB_CRATE_NAME, &mut parser).unwrap();
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
 
         assert_eq!(namespaces.len(), 2);
         let root = namespaces
@@ -95,6 +477,95 @@ pub enum Format {
         assert!(module.symbols.iter().any(|s| s.name == "Format"));
     }
 
+    #[test]
+    fn impl_block_of_reexported_type() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"
+mod format;
+pub use format::Format;
+"#,
+            )
+            .unwrap();
+        temp_dir
+            .create_file(
+                "src/format.rs",
+                r#"
+pub struct Format;
+
+impl Format {
+    pub fn new() -> Self {
+        Format
+    }
+}
+"#,
+            )
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        assert_eq!(namespaces.len(), 1);
+        let format = namespaces[0].get_symbol("Format").unwrap();
+        assert_eq!(
+            format.source_code,
+            "pub struct Format;\n\nimpl Format {\n    pub fn new() -> Self;\n}"
+        );
+    }
+
+    #[test]
+    fn restricted_visibility() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"
+pub(crate) mod internal;
+pub use internal::Format;
+pub(crate) use internal::Helper;
+
+pub(crate) fn crate_function() {}
+"#,
+            )
+            .unwrap();
+        temp_dir
+            .create_file(
+                "src/internal.rs",
+                r#"
+pub struct Format;
+pub struct Helper;
+"#,
+            )
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        assert_eq!(namespaces.len(), 1);
+        let root = &namespaces[0];
+        assert_eq!(root.name, STUB_CRATE_NAME);
+        assert_eq!(root.symbols.len(), 1);
+        assert!(root.get_symbol("Format").is_some());
+    }
+
     #[test]
     fn wildcard_reexport() {
         let temp_dir = TempDir::new();
@@ -118,7 +589,15 @@ pub struct Two;
             .unwrap();
         let mut parser = setup_parser();
 
-        let namespaces = build_public_api(&lib_rs, STUB_CRATE_NAME, &mut parser).unwrap();
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
 
         assert_eq!(namespaces.len(), 1);
         let root = &namespaces[0];
@@ -141,7 +620,15 @@ pub struct Two;
             .unwrap();
         let mut parser = setup_parser();
 
-        let namespaces = build_public_api(&lib_rs, STUB_CRATE_NAME, &mut parser).unwrap();
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
 
         assert_eq!(namespaces.len(), 1);
         let namespace = &namespaces[0];
@@ -153,6 +640,39 @@ pub struct Two;
         assert!(namespace.get_symbol("Foo").is_some());
     }
 
+    #[test]
+    fn module_with_path_attribute() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"
+#[path = "platform/unix.rs"]
+pub mod sys;
+"#,
+            )
+            .unwrap();
+        temp_dir
+            .create_file("src/platform/unix.rs", r#"pub struct Handle;"#)
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        assert_eq!(namespaces.len(), 1);
+        let sys = &namespaces[0];
+        assert_eq!(sys.name, format!("{STUB_CRATE_NAME}::sys"));
+        assert!(sys.get_symbol("Handle").is_some());
+    }
+
     #[test]
     fn external_dependency_reexport() {
         let temp_dir = TempDir::new();
@@ -166,7 +686,15 @@ pub use serde_json;
             .unwrap();
         let mut parser = setup_parser();
 
-        let namespaces = build_public_api(&lib_rs, STUB_CRATE_NAME, &mut parser).unwrap();
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
 
         assert_eq!(namespaces.len(), 1);
         let root = &namespaces[0];
@@ -175,4 +703,349 @@ pub use serde_json;
         let symbol = root.get_symbol("serde_json").unwrap();
         assert_eq!(symbol.source_code, "pub use serde_json;");
     }
+
+    #[test]
+    fn unresolved_glob_reexports() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                "pub use serde::de::*;\npub use serde_json::*;\n",
+            )
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        let root = &namespaces[0];
+        assert_eq!(root.symbols.len(), 2);
+        let serde_glob = root.get_symbol("serde::de::*").unwrap();
+        assert_eq!(serde_glob.source_code, "pub use serde::de::*;");
+        let serde_json_glob = root.get_symbol("serde_json::*").unwrap();
+        assert_eq!(serde_json_glob.source_code, "pub use serde_json::*;");
+    }
+
+    #[test]
+    fn unresolved_module() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file("src/lib.rs", "pub mod generated;\npub fn process() {}\n")
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::new().with_missing_modules_tolerated(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        let generated =
+            get_namespace(&format!("{}::generated", STUB_CRATE_NAME), &namespaces).unwrap();
+        assert!(generated.symbols.is_empty());
+        assert_eq!(
+            generated.doc_comment.as_deref(),
+            Some(UNRESOLVED_MODULE_DOC_COMMENT)
+        );
+        let root = get_namespace(STUB_CRATE_NAME, &namespaces).unwrap();
+        assert_eq!(root.doc_comment, None);
+    }
+
+    #[test]
+    fn unresolved_include() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                "pub fn process() {}\ninclude!(concat!(env!(\"OUT_DIR\"), \"/bindings.rs\"));\n",
+            )
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        let root = get_namespace(STUB_CRATE_NAME, &namespaces).unwrap();
+        assert!(root.get_symbol("process").is_some());
+        let placeholder = root.get_symbol(INCLUDE_PLACEHOLDER_NAME).unwrap();
+        assert_eq!(
+            placeholder.source_code,
+            "// The file included here couldn't be found.\n\
+             include!(concat!(env!(\"OUT_DIR\"), \"/bindings.rs\"));"
+        );
+    }
+
+    #[test]
+    fn cfg_annotations() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"
+#[cfg(feature = "serde")]
+pub mod serde {
+    pub fn serialize() {}
+}
+"#,
+            )
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::new().with_cfg_annotations(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        let serde = get_namespace(&format!("{}::serde", STUB_CRATE_NAME), &namespaces).unwrap();
+        let symbol = serde.get_symbol("serialize").unwrap();
+        assert_eq!(
+            symbol.source_code,
+            "// Available on crate feature `serde` only.\npub fn serialize();"
+        );
+    }
+
+    #[test]
+    fn included_documentation() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("README.md", "# Test crate\n\nDoes things.\n")
+            .unwrap();
+        temp_dir
+            .create_file("docs/format.md", "The output format.")
+            .unwrap();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"
+#![doc = include_str!("../README.md")]
+
+#[doc = include_str!("../docs/format.md")]
+pub struct Format;
+"#,
+            )
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        assert_eq!(namespaces.len(), 1);
+        let root = &namespaces[0];
+        assert_eq!(
+            root.doc_comment,
+            Some("//!# Test crate\n//!\n//!Does things.\n".to_string())
+        );
+        let format = root.get_symbol("Format").unwrap();
+        assert_eq!(
+            format.source_code,
+            "///The output format.\npub struct Format;"
+        );
+    }
+
+    #[test]
+    fn included_documentation_locations() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("docs/format.md", "The output format.\n\nIt's \"quoted\".\n")
+            .unwrap();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"#[doc = include_str!("../docs/format.md")] pub struct Format;
+
+/// Documented with `#[doc = include_str!("../docs/format.md")]`.
+pub fn process() {}
+"#,
+            )
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let api = build_rust_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        let root = api
+            .namespaces
+            .iter()
+            .find(|n| n.name == STUB_CRATE_NAME)
+            .unwrap();
+        let format = root.get_symbol("Format").unwrap();
+        assert_eq!(
+            (
+                format.location.span.start_line,
+                format.location.span.start_column
+            ),
+            (1, 44)
+        );
+        let process = root.get_symbol("process").unwrap();
+        assert_eq!(
+            process.symbol.source_code,
+            "/// Documented with `#[doc = include_str!(\"../docs/format.md\")]`.\npub fn process();"
+        );
+        assert_eq!(process.location.span.start_line, 4);
+    }
+
+    #[test]
+    fn doc_hidden_module() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"
+#[doc(hidden)]
+pub mod __private;
+pub use __private::Visible;
+"#,
+            )
+            .unwrap();
+        temp_dir
+            .create_file(
+                "src/__private/mod.rs",
+                r#"
+pub mod nested;
+pub struct Visible;
+"#,
+            )
+            .unwrap();
+        temp_dir
+            .create_file("src/__private/nested.rs", "pub fn helper() {}")
+            .unwrap();
+        let mut parser = setup_parser();
+
+        let namespaces = build_public_api(
+            &lib_rs,
+            STUB_CRATE_NAME,
+            &ExtractionOptions::default(),
+            &HashMap::new(),
+            None,
+            &mut parser,
+        )
+        .unwrap();
+
+        assert_eq!(namespaces.len(), 1);
+        let root = &namespaces[0];
+        assert_eq!(root.name, STUB_CRATE_NAME);
+        assert_eq!(root.symbols.len(), 1);
+        assert!(root.get_symbol("Visible").is_some());
+    }
+
+    mod macros {
+        use super::*;
+
+        fn build_namespaces(lib_rs_content: &str, module_content: &str) -> Vec<Namespace> {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir.create_file("src/lib.rs", lib_rs_content).unwrap();
+            temp_dir
+                .create_file("src/module.rs", module_content)
+                .unwrap();
+            let mut parser = setup_parser();
+            build_public_api(
+                &lib_rs,
+                STUB_CRATE_NAME,
+                &ExtractionOptions::default(),
+                &HashMap::new(),
+                None,
+                &mut parser,
+            )
+            .unwrap()
+        }
+
+        #[test]
+        fn exported_macro_in_private_module() {
+            let namespaces = build_namespaces(
+                "mod module;",
+                "#[macro_export]\nmacro_rules! my_macro {\n    () => {};\n}",
+            );
+
+            assert_eq!(namespaces.len(), 1);
+            let root = &namespaces[0];
+            let my_macro = root.get_symbol("my_macro").unwrap();
+            assert_eq!(
+                my_macro.source_code,
+                "#[macro_export]\nmacro_rules! my_macro {\n    () => { ... };\n}"
+            );
+        }
+
+        #[test]
+        fn exported_macro_reexported_by_path() {
+            let namespaces = build_namespaces(
+                "pub mod module;",
+                r#"
+#[macro_export]
+macro_rules! my_macro_impl {
+    () => {};
+}
+
+pub use crate::my_macro_impl as my_macro;
+"#,
+            );
+
+            let module =
+                get_namespace(&format!("{}::module", STUB_CRATE_NAME), &namespaces).unwrap();
+            assert_eq!(module.symbols.len(), 1);
+            assert!(module.get_symbol("my_macro").is_some());
+        }
+
+        #[test]
+        fn private_macro_reexported_within_crate() {
+            let namespaces = build_namespaces(
+                "pub mod module;",
+                "macro_rules! my_macro {\n    () => {};\n}\npub(crate) use my_macro;",
+            );
+
+            assert!(namespaces
+                .iter()
+                .all(|namespace| namespace.symbols.is_empty()));
+        }
+
+        #[test]
+        fn decl_macro() {
+            let namespaces = build_namespaces(
+                "pub mod module;",
+                "pub macro my_macro($x:expr) {\n    $x\n}",
+            );
+
+            assert_eq!(namespaces.len(), 1);
+            let module =
+                get_namespace(&format!("{}::module", STUB_CRATE_NAME), &namespaces).unwrap();
+            let my_macro = module.get_symbol("my_macro").unwrap();
+            assert_eq!(my_macro.source_code, "pub macro my_macro($x:expr);");
+        }
+    }
 }
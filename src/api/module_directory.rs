@@ -1,30 +1,149 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use daipendency_extractor::{ExtractionError, Symbol};
 
-use super::parsing::{ImportType, RustFile, RustSymbol};
+use super::parsing::{
+    CfgPredicate, ImportType, IncludePath, RustFile, RustSymbol, SourceSpan, SymbolKind, Visibility,
+};
 
+/// The location of an item in the source files of the crate.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[non_exhaustive]
+pub struct SourceLocation {
+    /// The path to the file defining the item (e.g. `/path/to/crate/src/lib.rs`).
+    pub path: PathBuf,
+    /// The span of the item in its file.
+    pub span: SourceSpan,
+}
+
+/// A module whose file couldn't be found (e.g. `mod generated;` for a file generated by a build
+/// script).
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct UnresolvedModule {
+    /// The path to the module, relative to the module directory like its internal files.
+    pub name: String,
+    /// Why the file couldn't be found.
+    pub message: String,
+    /// The location of the declaration of the module.
+    pub location: SourceLocation,
+}
+
+/// An `include!` invocation whose file couldn't be found (e.g.
+/// `include!(concat!(env!("OUT_DIR"), "/bindings.rs"));`).
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct UnresolvedInclude {
+    /// The path to the included file, or `None` if it isn't computed in a supported way.
+    pub path: Option<IncludePath>,
+    /// The invocation itself, preceded by the annotation of its predicate if any.
+    pub source_code: String,
+    /// The location of the invocation.
+    pub location: SourceLocation,
+}
+
+/// An item of a module that's relevant to its public API.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum ModuleItem {
     /// A public symbol (e.g. `pub struct Foo { ... }`)
-    Symbol { symbol: Symbol },
+    Symbol {
+        /// The symbol, with its source code
+        symbol: Symbol,
+        /// The kind of item the symbol stands for
+        kind: SymbolKind,
+        /// The generic parameters of the symbol, if any (e.g. `<T: Clone>`)
+        generics: Option<String>,
+        /// Whether the symbol is hidden from the documentation (i.e. `#[doc(hidden)]`)
+        is_doc_hidden: bool,
+        /// The location of the symbol's declaration
+        location: SourceLocation,
+    },
+    /// A `macro_rules!` macro exported at the crate root (i.e. `#[macro_export]`), which is
+    /// moved to the root module once all the modules are extracted
+    ExportedMacro {
+        /// The macro, whose source code stops before its rules (e.g. `macro_rules! foo`)
+        symbol: Symbol,
+        /// The matchers of the macro's rules (e.g. `($x:expr)`)
+        matchers: Vec<String>,
+        /// Whether the macro is hidden from the documentation (i.e. `#[doc(hidden)]`)
+        is_doc_hidden: bool,
+        /// The location of the macro's definition
+        location: SourceLocation,
+    },
     /// A symbol reexport (e.g. `pub use foo::Bar;`)
     SymbolReexport {
+        /// The path to the reexported item (e.g. `foo::Bar`)
         source_path: String,
+        /// How the item is reexported (e.g. with an alias)
         import_type: ImportType,
+        /// Whether the reexport is hidden from the documentation (i.e. `#[doc(hidden)]`)
+        is_doc_hidden: bool,
+        /// The location of the reexport
+        location: SourceLocation,
+    },
+    /// A private import (e.g. `use crate::foo::Bar;`), used to resolve the types of implementation
+    /// blocks
+    Import {
+        /// The path to the imported item (e.g. `crate::foo::Bar`)
+        source_path: String,
+        /// How the item is imported (e.g. with an alias)
+        import_type: ImportType,
+    },
+    /// An external crate declaration (e.g. `extern crate foo as bar;`)
+    ExternCrate {
+        /// The name of the crate (e.g. `foo`)
+        name: String,
+        /// The name the crate is bound to instead, if any (e.g. `bar`)
+        alias: Option<String>,
+    },
+    /// The variants of an enum (e.g. `Red` and `Green` in `pub enum Colour { Red, Green }`)
+    EnumVariants {
+        /// The name of the enum (e.g. `Colour`)
+        enum_name: String,
+        /// The names of the variants (e.g. `["Red", "Green"]`)
+        variants: Vec<String>,
+    },
+    /// An implementation block for a type (e.g. `impl Foo { ... }`)
+    ImplBlock {
+        /// The path to the type as written in the block (e.g. `Foo`, `crate::foo::Foo`)
+        type_path: String,
+        /// The block itself
+        source_code: String,
     },
 }
 
+/// A module of the crate, with the items relevant to its public API.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Module {
+    /// The path to the module, relative to the crate root (e.g. "" for the crate root and
+    /// "submodule::grandchild" for `src/submodule/grandchild.rs`).
     pub name: String,
+    /// Whether the declaration of the module is public (e.g. `pub mod foo;`), regardless of its
+    /// ancestors.
     pub is_public: bool,
+    /// Whether the declaration of the module is hidden from the documentation (i.e.
+    /// `#[doc(hidden)]`), regardless of its ancestors.
+    pub is_doc_hidden: bool,
+    /// The inner doc comments of the module (e.g. `//! My module`), if any.
     pub doc_comment: Option<String>,
+    /// The items of the module that are relevant to its public API.
     pub symbols: Vec<ModuleItem>,
+    /// The location of the module's file, or of its block if it's inline (e.g. `mod foo { ... }`).
+    ///
+    /// If the module is unresolved, this is the location of its declaration instead.
+    pub location: SourceLocation,
+    /// Whether the file of the module couldn't be found, leaving it empty.
+    pub is_unresolved: bool,
+    /// The `include!` invocations of the module whose files couldn't be found.
+    pub unresolved_includes: Vec<UnresolvedInclude>,
 }
 
 /// A module directory like `src` (with `src/lib.rs`) or `src/submodule` (with `src/submodule/mod.rs`).
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ModuleDirectory {
     /// The name of the module directory.
     ///
@@ -36,37 +155,56 @@ pub struct ModuleDirectory {
     ///
     /// For example, `src/lib.rs` or `src/submodule/mod.rs`.
     pub entry_point: RustFile,
-    /// The internal files of the module directory.
+    /// The internal files of the module directory, keyed by their module path relative to the directory.
     ///
-    /// For example, `src/submodule.rs` or `src/submodule/another_submodule.rs`.
+    /// For example, "submodule" for `src/submodule.rs` and "inline::submodule" for `src/inline/submodule.rs`
+    /// (declared in `mod inline { mod submodule; }`).
     pub internal_files: HashMap<String, RustFile>,
+    /// The predicate required by the declaration of the module directory and its ancestors, if any.
+    ///
+    /// For example, `feature = "serde"` for `src/serde/mod.rs` (declared in `#[cfg(feature = "serde")] mod serde;`).
+    pub cfg: Option<CfgPredicate>,
+    /// Whether the declaration of the module directory is hidden from the documentation (i.e.
+    /// `#[doc(hidden)] pub mod foo;`).
+    pub is_doc_hidden: bool,
+    /// The modules declared in the module directory whose files couldn't be found.
+    pub unresolved_modules: Vec<UnresolvedModule>,
 }
 
 impl ModuleDirectory {
+    /// Split the module directory into its modules, starting with the module of its entry point.
     pub fn extract_modules(&self) -> Result<Vec<Module>, ExtractionError> {
         extract_modules_from_symbols(
-            &self.name,
-            self.is_public,
-            self.entry_point.doc_comment.clone(),
+            Module {
+                name: self.name.clone(),
+                is_public: self.is_public,
+                is_doc_hidden: self.is_doc_hidden,
+                doc_comment: self.entry_point.doc_comment.clone(),
+                symbols: Vec::new(),
+                location: get_file_location(&self.entry_point),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            },
             &self.entry_point.symbols,
+            &self.entry_point.path,
             &self.internal_files,
+            &self.unresolved_modules,
+            "",
+            self.cfg.as_ref(),
         )
     }
 }
 
+/// Extract `root_module` and its submodules from `symbols`.
 fn extract_modules_from_symbols(
-    root_module_name: &str,
-    root_module_is_public: bool,
-    root_module_doc_comment: Option<String>,
+    mut root_module: Module,
     symbols: &Vec<RustSymbol>,
+    file_path: &Path,
     internal_files: &HashMap<String, RustFile>,
+    unresolved_modules: &[UnresolvedModule],
+    internal_files_prefix: &str,
+    inherited_cfg: Option<&CfgPredicate>,
 ) -> Result<Vec<Module>, ExtractionError> {
-    let mut root_module = Module {
-        name: root_module_name.to_string(),
-        symbols: Vec::new(),
-        doc_comment: root_module_doc_comment,
-        is_public: root_module_is_public,
-    };
     let mut root_symbols: Vec<ModuleItem> = Vec::new();
     let mut submodules = vec![];
     for symbol in symbols {
@@ -75,46 +213,217 @@ fn extract_modules_from_symbols(
                 name,
                 content,
                 doc_comment,
-                is_public,
+                visibility,
+                cfg,
+                is_doc_hidden,
+                span,
             } => {
-                let nested_module_name = get_symbol_path(name, &root_module);
                 let nested_modules = extract_modules_from_symbols(
-                    &nested_module_name,
-                    *is_public,
-                    doc_comment.clone(),
+                    Module {
+                        name: get_symbol_path(name, &root_module),
+                        is_public: *visibility == Visibility::Public,
+                        is_doc_hidden: *is_doc_hidden,
+                        doc_comment: doc_comment.clone(),
+                        symbols: Vec::new(),
+                        location: get_location(file_path, span),
+                        is_unresolved: false,
+                        unresolved_includes: Vec::new(),
+                    },
                     content,
-                    &HashMap::new(),
+                    file_path,
+                    internal_files,
+                    unresolved_modules,
+                    &prefix_module_path(name, internal_files_prefix),
+                    CfgPredicate::conjoin(inherited_cfg, cfg.as_ref()).as_ref(),
                 )?;
                 submodules.extend(nested_modules);
             }
             RustSymbol::ModuleImport {
                 name,
-                is_reexported,
+                visibility,
+                cfg,
+                is_doc_hidden,
+                ..
             } => {
-                if let Some(file) = internal_files.get(name) {
+                let internal_file_key = prefix_module_path(name, internal_files_prefix);
+                if let Some(file) = internal_files.get(&internal_file_key) {
                     let internal_file_modules = extract_modules_from_symbols(
-                        &get_symbol_path(name, &root_module),
-                        *is_reexported,
-                        file.doc_comment.clone(),
+                        Module {
+                            name: get_symbol_path(name, &root_module),
+                            is_public: *visibility == Visibility::Public,
+                            is_doc_hidden: *is_doc_hidden,
+                            doc_comment: file.doc_comment.clone(),
+                            symbols: Vec::new(),
+                            location: get_file_location(file),
+                            is_unresolved: false,
+                            unresolved_includes: Vec::new(),
+                        },
                         &file.symbols,
+                        &file.path,
                         &HashMap::new(),
+                        &[],
+                        "",
+                        CfgPredicate::conjoin(inherited_cfg, cfg.as_ref()).as_ref(),
                     )?;
                     submodules.extend(internal_file_modules);
+                } else if let Some(unresolved_module) = unresolved_modules
+                    .iter()
+                    .find(|module| module.name == internal_file_key)
+                {
+                    submodules.push(Module {
+                        name: get_symbol_path(name, &root_module),
+                        is_public: *visibility == Visibility::Public,
+                        is_doc_hidden: *is_doc_hidden,
+                        doc_comment: None,
+                        symbols: Vec::new(),
+                        location: unresolved_module.location.clone(),
+                        is_unresolved: true,
+                        unresolved_includes: Vec::new(),
+                    });
                 }
             }
-            RustSymbol::Symbol { symbol } => {
+            RustSymbol::Symbol {
+                symbol,
+                kind,
+                generics,
+                cfg,
+                is_doc_hidden,
+                span,
+            } => {
+                let cfg = CfgPredicate::conjoin(inherited_cfg, cfg.as_ref());
                 root_symbols.push(ModuleItem::Symbol {
-                    symbol: symbol.clone(),
+                    symbol: Symbol {
+                        name: symbol.name.clone(),
+                        source_code: annotate_source_code(&symbol.source_code, cfg.as_ref()),
+                    },
+                    kind: *kind,
+                    generics: generics.clone(),
+                    is_doc_hidden: *is_doc_hidden,
+                    location: get_location(file_path, span),
+                });
+            }
+            RustSymbol::ExportedMacro {
+                symbol,
+                matchers,
+                cfg,
+                is_doc_hidden,
+                span,
+            } => {
+                let cfg = CfgPredicate::conjoin(inherited_cfg, cfg.as_ref());
+                root_symbols.push(ModuleItem::ExportedMacro {
+                    symbol: Symbol {
+                        name: symbol.name.clone(),
+                        source_code: annotate_source_code(&symbol.source_code, cfg.as_ref()),
+                    },
+                    matchers: matchers.clone(),
+                    is_doc_hidden: *is_doc_hidden,
+                    location: get_location(file_path, span),
                 });
             }
             RustSymbol::Reexport {
                 source_path,
                 import_type,
+                is_doc_hidden,
+                span,
+                ..
             } => {
                 root_symbols.push(ModuleItem::SymbolReexport {
                     source_path: source_path.clone(),
                     import_type: import_type.clone(),
+                    is_doc_hidden: *is_doc_hidden,
+                    location: get_location(file_path, span),
+                });
+            }
+            RustSymbol::Import {
+                source_path,
+                import_type,
+                ..
+            } => {
+                root_symbols.push(ModuleItem::Import {
+                    source_path: source_path.clone(),
+                    import_type: import_type.clone(),
+                });
+            }
+            RustSymbol::ExternCrate {
+                name,
+                alias,
+                visibility,
+                is_doc_hidden,
+                span,
+                ..
+            } => {
+                root_symbols.push(ModuleItem::ExternCrate {
+                    name: name.clone(),
+                    alias: alias.clone(),
                 });
+                // `pub extern crate foo as bar;` is equivalent to `pub use foo as bar;`
+                if *visibility == Visibility::Public {
+                    root_symbols.push(ModuleItem::SymbolReexport {
+                        source_path: name.clone(),
+                        import_type: match alias {
+                            Some(alias) => ImportType::Aliased(alias.clone()),
+                            None => ImportType::Simple,
+                        },
+                        is_doc_hidden: *is_doc_hidden,
+                        location: get_location(file_path, span),
+                    });
+                }
+            }
+            RustSymbol::EnumVariants {
+                enum_name,
+                variants,
+                ..
+            } => {
+                root_symbols.push(ModuleItem::EnumVariants {
+                    enum_name: enum_name.clone(),
+                    variants: variants.clone(),
+                });
+            }
+            RustSymbol::ImplBlock {
+                type_path,
+                source_code,
+                cfg,
+            } => {
+                let cfg = CfgPredicate::conjoin(inherited_cfg, cfg.as_ref());
+                root_symbols.push(ModuleItem::ImplBlock {
+                    type_path: type_path.clone(),
+                    source_code: annotate_source_code(source_code, cfg.as_ref()),
+                });
+            }
+            RustSymbol::SourceInclude {
+                path,
+                source_code,
+                cfg,
+                span,
+            } => {
+                // Includes left at this stage couldn't be spliced
+                let cfg = CfgPredicate::conjoin(inherited_cfg, cfg.as_ref());
+                root_module.unresolved_includes.push(UnresolvedInclude {
+                    path: path.clone(),
+                    source_code: annotate_source_code(source_code, cfg.as_ref()),
+                    location: get_location(file_path, span),
+                });
+            }
+            RustSymbol::IncludedFile { path, symbols, cfg } => {
+                let mut included_modules = extract_modules_from_symbols(
+                    Module {
+                        symbols: Vec::new(),
+                        unresolved_includes: Vec::new(),
+                        ..root_module.clone()
+                    },
+                    symbols,
+                    path,
+                    internal_files,
+                    unresolved_modules,
+                    internal_files_prefix,
+                    CfgPredicate::conjoin(inherited_cfg, cfg.as_ref()).as_ref(),
+                )?;
+                let included_module = included_modules.remove(0);
+                root_symbols.extend(included_module.symbols);
+                root_module
+                    .unresolved_includes
+                    .extend(included_module.unresolved_includes);
+                submodules.extend(included_modules);
             }
         }
     }
@@ -123,11 +432,34 @@ fn extract_modules_from_symbols(
     Ok(submodules)
 }
 
+fn get_location(file_path: &Path, span: &SourceSpan) -> SourceLocation {
+    SourceLocation {
+        path: file_path.to_path_buf(),
+        span: *span,
+    }
+}
+
+fn get_file_location(file: &RustFile) -> SourceLocation {
+    get_location(&file.path, &file.span)
+}
+
+/// Prepend the annotation of the predicate required by a symbol, if any, to its source code.
+fn annotate_source_code(source_code: &str, cfg: Option<&CfgPredicate>) -> String {
+    match cfg {
+        Some(cfg) => format!("// {}\n{}", cfg.get_annotation(), source_code),
+        None => source_code.to_string(),
+    }
+}
+
 fn get_symbol_path(symbol_name: &str, module: &Module) -> String {
-    if module.name.is_empty() {
-        symbol_name.to_string()
+    prefix_module_path(symbol_name, &module.name)
+}
+
+fn prefix_module_path(name: &str, prefix: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
     } else {
-        format!("{}::{}", module.name, symbol_name)
+        format!("{}::{}", prefix, name)
     }
 }
 
@@ -139,11 +471,24 @@ mod tests {
     const STUB_SYMBOL_NAME: &str = "test";
 
     fn stub_rust_symbol(symbol: Symbol) -> RustSymbol {
-        RustSymbol::Symbol { symbol }
+        RustSymbol::Symbol {
+            symbol,
+            kind: SymbolKind::Function,
+            generics: None,
+            cfg: None,
+            is_doc_hidden: false,
+            span: SourceSpan::default(),
+        }
     }
 
     fn stub_module_item(symbol: Symbol) -> ModuleItem {
-        ModuleItem::Symbol { symbol }
+        ModuleItem::Symbol {
+            symbol,
+            kind: SymbolKind::Function,
+            generics: None,
+            is_doc_hidden: false,
+            location: SourceLocation::default(),
+        }
     }
 
     mod module_extraction {
@@ -162,8 +507,14 @@ mod tests {
                 entry_point: RustFile {
                     doc_comment: None,
                     symbols: vec![],
+                    path: PathBuf::new(),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
                 },
                 internal_files: HashMap::new(),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
             };
 
             let modules = directory.extract_modules().unwrap();
@@ -181,8 +532,14 @@ mod tests {
                 entry_point: RustFile {
                     doc_comment: doc_comment.clone(),
                     symbols: vec![],
+                    path: PathBuf::new(),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
                 },
                 internal_files: HashMap::new(),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
             };
 
             let modules = directory.extract_modules().unwrap();
@@ -200,8 +557,14 @@ mod tests {
                 entry_point: RustFile {
                     doc_comment: None,
                     symbols: vec![stub_rust_symbol(symbol.clone())],
+                    path: PathBuf::new(),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
                 },
                 internal_files: HashMap::new(),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
             };
 
             let modules = directory.extract_modules().unwrap();
@@ -223,22 +586,38 @@ mod tests {
                     symbols: vec![
                         RustSymbol::ModuleImport {
                             name: "submodule".to_string(),
-                            is_reexported: false,
+                            visibility: Visibility::Private,
+                            path: None,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
                         },
                         RustSymbol::Reexport {
                             source_path: "submodule::test".to_string(),
                             import_type: ImportType::Simple,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
                         },
                     ],
                     doc_comment: None,
+                    path: PathBuf::new(),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
                 },
                 internal_files: HashMap::from([(
                     "submodule".to_string(),
                     RustFile {
                         symbols: vec![stub_rust_symbol(original_symbol.clone())],
                         doc_comment: None,
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
                     },
                 )]),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
             };
 
             let modules = directory.extract_modules().unwrap();
@@ -250,7 +629,8 @@ mod tests {
                 &root.symbols[0],
                 ModuleItem::SymbolReexport {
                     source_path,
-                    import_type: ImportType::Simple
+                    import_type: ImportType::Simple,
+                    ..
                 } if source_path == "submodule::test"
             );
             let submodule = &modules[1];
@@ -258,7 +638,41 @@ mod tests {
             assert_eq!(submodule.symbols.len(), 1);
             assert_matches!(
                 &submodule.symbols[0],
-                ModuleItem::Symbol { symbol } if symbol.name == original_symbol.name
+                ModuleItem::Symbol { symbol, .. } if symbol.name == original_symbol.name
+            );
+        }
+
+        #[test]
+        fn impl_block() {
+            let directory = ModuleDirectory {
+                name: String::new(),
+                is_public: true,
+                entry_point: RustFile {
+                    doc_comment: None,
+                    symbols: vec![RustSymbol::ImplBlock {
+                        type_path: "Foo".to_string(),
+                        source_code: "impl Foo {}".to_string(),
+                        cfg: None,
+                    }],
+                    path: PathBuf::new(),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
+                },
+                internal_files: HashMap::new(),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
+            };
+
+            let modules = directory.extract_modules().unwrap();
+
+            assert_eq!(modules.len(), 1);
+            assert_eq!(
+                modules[0].symbols,
+                vec![ModuleItem::ImplBlock {
+                    type_path: "Foo".to_string(),
+                    source_code: "impl Foo {}".to_string(),
+                }]
             );
         }
 
@@ -273,8 +687,14 @@ mod tests {
                     entry_point: RustFile {
                         doc_comment: None,
                         symbols: vec![],
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
                     },
                     internal_files: HashMap::new(),
+                    cfg: None,
+                    is_doc_hidden: false,
+                    unresolved_modules: Vec::new(),
                 };
 
                 let modules = directory.extract_modules().unwrap();
@@ -291,8 +711,14 @@ mod tests {
                     entry_point: RustFile {
                         doc_comment: None,
                         symbols: vec![],
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
                     },
                     internal_files: HashMap::new(),
+                    cfg: None,
+                    is_doc_hidden: false,
+                    unresolved_modules: Vec::new(),
                 };
 
                 let modules = directory.extract_modules().unwrap();
@@ -317,10 +743,19 @@ mod tests {
                             name: "public_mod".to_string(),
                             content: vec![stub_rust_symbol(symbol.clone())],
                             doc_comment: None,
-                            is_public: true,
+                            visibility: Visibility::Public,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
                         }],
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
                     },
                     internal_files: HashMap::new(),
+                    cfg: None,
+                    is_doc_hidden: false,
+                    unresolved_modules: Vec::new(),
                 };
 
                 let modules = directory.extract_modules().unwrap();
@@ -333,6 +768,40 @@ mod tests {
                 assert_eq!(submodule.symbols[0], stub_module_item(symbol));
             }
 
+            #[test]
+            fn restricted_module_block() {
+                let directory = ModuleDirectory {
+                    name: String::new(),
+                    is_public: true,
+                    entry_point: RustFile {
+                        doc_comment: None,
+                        symbols: vec![RustSymbol::ModuleBlock {
+                            name: "restricted_mod".to_string(),
+                            content: vec![],
+                            doc_comment: None,
+                            visibility: Visibility::Restricted,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
+                        }],
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
+                    },
+                    internal_files: HashMap::new(),
+                    cfg: None,
+                    is_doc_hidden: false,
+                    unresolved_modules: Vec::new(),
+                };
+
+                let modules = directory.extract_modules().unwrap();
+
+                assert_eq!(modules.len(), 2);
+                let submodule = &modules[1];
+                assert_eq!(submodule.name, "restricted_mod");
+                assert!(!submodule.is_public);
+            }
+
             #[test]
             fn public_nested_module_block() {
                 let symbol = stub_symbol_with_name(STUB_SYMBOL_NAME);
@@ -347,19 +816,31 @@ mod tests {
                                 name: "child".to_string(),
                                 content: vec![stub_rust_symbol(symbol.clone())],
                                 doc_comment: None,
-                                is_public: true,
+                                visibility: Visibility::Public,
+                                cfg: None,
+                                is_doc_hidden: false,
+                                span: SourceSpan::default(),
                             }],
                             doc_comment: None,
-                            is_public: true,
+                            visibility: Visibility::Public,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
                         }],
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
                     },
                     internal_files: HashMap::new(),
+                    cfg: None,
+                    is_doc_hidden: false,
+                    unresolved_modules: Vec::new(),
                 };
 
                 let modules = directory.extract_modules().unwrap();
 
                 assert_eq!(modules.len(), 3);
-                assert_matches!(&modules[0],Module { name, .. } if name == "");
+                assert_matches!(&modules[0],Module { name, .. } if name.is_empty());
                 assert_matches!(&modules[1],Module { name, .. } if name == "parent");
                 assert_matches!(
                     &modules[2],
@@ -379,10 +860,19 @@ mod tests {
                             name: "private_mod".to_string(),
                             content: vec![stub_rust_symbol(symbol.clone())],
                             doc_comment: None,
-                            is_public: false,
+                            visibility: Visibility::Private,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
                         }],
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
                     },
                     internal_files: HashMap::new(),
+                    cfg: None,
+                    is_doc_hidden: false,
+                    unresolved_modules: Vec::new(),
                 };
 
                 let modules = directory.extract_modules().unwrap();
@@ -409,16 +899,29 @@ mod tests {
                         doc_comment: None,
                         symbols: vec![RustSymbol::ModuleImport {
                             name: "submodule".to_string(),
-                            is_reexported: true,
+                            visibility: Visibility::Public,
+                            path: None,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
                         }],
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
                     },
                     internal_files: HashMap::from([(
                         "submodule".to_string(),
                         RustFile {
                             doc_comment: None,
                             symbols: vec![stub_rust_symbol(symbol.clone())],
+                            path: PathBuf::new(),
+                            span: SourceSpan::default(),
+                            syntax_errors: Vec::new(),
                         },
                     )]),
+                    cfg: None,
+                    is_doc_hidden: false,
+                    unresolved_modules: Vec::new(),
                 };
 
                 let modules = directory.extract_modules().unwrap();
@@ -440,16 +943,29 @@ mod tests {
                         doc_comment: None,
                         symbols: vec![RustSymbol::ModuleImport {
                             name: "submodule".to_string(),
-                            is_reexported: false,
+                            visibility: Visibility::Private,
+                            path: None,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
                         }],
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
                     },
                     internal_files: HashMap::from([(
                         "submodule".to_string(),
                         RustFile {
                             doc_comment: None,
                             symbols: vec![stub_rust_symbol(symbol.clone())],
+                            path: PathBuf::new(),
+                            span: SourceSpan::default(),
+                            syntax_errors: Vec::new(),
                         },
                     )]),
+                    cfg: None,
+                    is_doc_hidden: false,
+                    unresolved_modules: Vec::new(),
                 };
 
                 let modules = directory.extract_modules().unwrap();
@@ -466,6 +982,58 @@ mod tests {
                 assert_eq!(submodule.symbols[0], stub_module_item(symbol));
             }
 
+            #[test]
+            fn module_import_inside_module_block() {
+                let symbol = stub_symbol_with_name(STUB_SYMBOL_NAME);
+                let directory = ModuleDirectory {
+                    name: String::new(),
+                    is_public: true,
+                    entry_point: RustFile {
+                        doc_comment: None,
+                        symbols: vec![RustSymbol::ModuleBlock {
+                            name: "inline".to_string(),
+                            content: vec![RustSymbol::ModuleImport {
+                                name: "submodule".to_string(),
+                                visibility: Visibility::Public,
+                                path: None,
+                                cfg: None,
+                                is_doc_hidden: false,
+                                span: SourceSpan::default(),
+                            }],
+                            doc_comment: None,
+                            visibility: Visibility::Public,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
+                        }],
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
+                    },
+                    internal_files: HashMap::from([(
+                        "inline::submodule".to_string(),
+                        RustFile {
+                            doc_comment: None,
+                            symbols: vec![stub_rust_symbol(symbol.clone())],
+                            path: PathBuf::new(),
+                            span: SourceSpan::default(),
+                            syntax_errors: Vec::new(),
+                        },
+                    )]),
+                    cfg: None,
+                    is_doc_hidden: false,
+                    unresolved_modules: Vec::new(),
+                };
+
+                let modules = directory.extract_modules().unwrap();
+
+                assert_eq!(modules.len(), 3);
+                let submodule = &modules[2];
+                assert_eq!(submodule.name, "inline::submodule");
+                assert!(submodule.is_public);
+                assert_eq!(submodule.symbols, vec![stub_module_item(symbol)]);
+            }
+
             #[test]
             fn missing_internal_file() {
                 let directory = ModuleDirectory {
@@ -475,10 +1043,20 @@ mod tests {
                         doc_comment: None,
                         symbols: vec![RustSymbol::ModuleImport {
                             name: "missing_module".to_string(),
-                            is_reexported: true,
+                            visibility: Visibility::Public,
+                            path: None,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
                         }],
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
                     },
                     internal_files: HashMap::new(),
+                    cfg: None,
+                    is_doc_hidden: false,
+                    unresolved_modules: Vec::new(),
                 };
 
                 let modules = directory.extract_modules().unwrap();
@@ -489,6 +1067,442 @@ mod tests {
                 assert!(root.is_public);
                 assert_eq!(root.symbols.len(), 0);
             }
+
+            #[test]
+            fn unresolved_module() {
+                let location = SourceLocation {
+                    path: PathBuf::from("src/lib.rs"),
+                    span: SourceSpan {
+                        start_line: 1,
+                        start_column: 1,
+                        end_line: 1,
+                        end_column: 19,
+                    },
+                };
+                let directory = ModuleDirectory {
+                    name: String::new(),
+                    is_public: true,
+                    entry_point: RustFile {
+                        doc_comment: None,
+                        symbols: vec![RustSymbol::ModuleImport {
+                            name: "generated".to_string(),
+                            visibility: Visibility::Public,
+                            path: None,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: location.span,
+                        }],
+                        path: location.path.clone(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
+                    },
+                    internal_files: HashMap::new(),
+                    cfg: None,
+                    is_doc_hidden: false,
+                    unresolved_modules: vec![UnresolvedModule {
+                        name: "generated".to_string(),
+                        message: "Could not find module generated".to_string(),
+                        location: location.clone(),
+                    }],
+                };
+
+                let modules = directory.extract_modules().unwrap();
+
+                assert_eq!(modules.len(), 2);
+                assert!(!modules[0].is_unresolved);
+                let generated = &modules[1];
+                assert_eq!(generated.name, "generated");
+                assert!(generated.is_public);
+                assert!(generated.is_unresolved);
+                assert!(generated.symbols.is_empty());
+                assert_eq!(generated.location, location);
+            }
+        }
+    }
+
+    mod locations {
+        use super::*;
+        use assertables::assert_matches;
+
+        fn make_span(start_line: usize, end_line: usize) -> SourceSpan {
+            SourceSpan {
+                start_line,
+                start_column: 1,
+                end_line,
+                end_column: 2,
+            }
+        }
+
+        fn make_location(path: &str, span: SourceSpan) -> SourceLocation {
+            SourceLocation {
+                path: PathBuf::from(path),
+                span,
+            }
+        }
+
+        fn make_directory() -> ModuleDirectory {
+            ModuleDirectory {
+                name: String::new(),
+                is_public: true,
+                entry_point: RustFile {
+                    path: PathBuf::from("src/lib.rs"),
+                    span: make_span(1, 10),
+                    doc_comment: None,
+                    symbols: vec![
+                        RustSymbol::ModuleBlock {
+                            name: "inline".to_string(),
+                            visibility: Visibility::Public,
+                            content: vec![RustSymbol::Symbol {
+                                symbol: stub_symbol_with_name(STUB_SYMBOL_NAME),
+                                kind: SymbolKind::Function,
+                                generics: None,
+                                cfg: None,
+                                is_doc_hidden: false,
+                                span: make_span(3, 3),
+                            }],
+                            doc_comment: None,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: make_span(2, 4),
+                        },
+                        RustSymbol::ModuleImport {
+                            name: "submodule".to_string(),
+                            visibility: Visibility::Public,
+                            path: None,
+                            cfg: None,
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
+                        },
+                    ],
+                    syntax_errors: Vec::new(),
+                },
+                internal_files: HashMap::from([(
+                    "submodule".to_string(),
+                    RustFile {
+                        path: PathBuf::from("src/submodule.rs"),
+                        span: make_span(1, 5),
+                        doc_comment: None,
+                        symbols: vec![],
+                        syntax_errors: Vec::new(),
+                    },
+                )]),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
+            }
+        }
+
+        fn get_module<'a>(name: &str, modules: &'a [Module]) -> &'a Module {
+            modules.iter().find(|module| module.name == name).unwrap()
+        }
+
+        #[test]
+        fn entry_point() {
+            let modules = make_directory().extract_modules().unwrap();
+
+            let root = get_module("", &modules);
+            assert_eq!(root.location, make_location("src/lib.rs", make_span(1, 10)));
+        }
+
+        #[test]
+        fn module_block() {
+            let modules = make_directory().extract_modules().unwrap();
+
+            let inline = get_module("inline", &modules);
+            assert_eq!(
+                inline.location,
+                make_location("src/lib.rs", make_span(2, 4))
+            );
+            assert_matches!(
+                inline.symbols.as_slice(),
+                [ModuleItem::Symbol { location, .. }]
+                    if *location == make_location("src/lib.rs", make_span(3, 3))
+            );
+        }
+
+        #[test]
+        fn internal_file() {
+            let modules = make_directory().extract_modules().unwrap();
+
+            let submodule = get_module("submodule", &modules);
+            assert_eq!(
+                submodule.location,
+                make_location("src/submodule.rs", make_span(1, 5))
+            );
+        }
+    }
+
+    mod cfg_annotations {
+        use super::*;
+        use crate::test_helpers::stub_symbol;
+        use assertables::assert_matches;
+
+        fn feature(name: &str) -> CfgPredicate {
+            CfgPredicate::Option {
+                name: "feature".to_string(),
+                value: Some(name.to_string()),
+            }
+        }
+
+        #[test]
+        fn symbol() {
+            let symbol = stub_symbol();
+            let directory = ModuleDirectory {
+                name: String::new(),
+                is_public: true,
+                entry_point: RustFile {
+                    doc_comment: None,
+                    symbols: vec![RustSymbol::Symbol {
+                        symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        cfg: Some(feature("serde")),
+                        is_doc_hidden: false,
+                        span: SourceSpan::default(),
+                    }],
+                    path: PathBuf::new(),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
+                },
+                internal_files: HashMap::new(),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
+            };
+
+            let modules = directory.extract_modules().unwrap();
+
+            assert_matches!(
+                &modules[0].symbols[0],
+                ModuleItem::Symbol { symbol: annotated_symbol, .. }
+                if annotated_symbol.source_code == format!(
+                    "// Available on crate feature `serde` only.\n{}",
+                    symbol.source_code
+                )
+            );
+        }
+
+        #[test]
+        fn source_include() {
+            let directory = ModuleDirectory {
+                name: String::new(),
+                is_public: true,
+                entry_point: RustFile {
+                    doc_comment: None,
+                    symbols: vec![RustSymbol::SourceInclude {
+                        path: None,
+                        source_code: "include!(\"serde.rs\");".to_string(),
+                        cfg: Some(feature("serde")),
+                        span: SourceSpan::default(),
+                    }],
+                    path: PathBuf::new(),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
+                },
+                internal_files: HashMap::new(),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
+            };
+
+            let modules = directory.extract_modules().unwrap();
+
+            assert!(modules[0].symbols.is_empty());
+            assert_eq!(
+                modules[0].unresolved_includes[0].source_code,
+                "// Available on crate feature `serde` only.\n\
+                 include!(\"serde.rs\");"
+            );
+        }
+
+        #[test]
+        fn included_file() {
+            let included_rs = PathBuf::from("/crate/src/included.rs");
+            let directory = ModuleDirectory {
+                name: String::new(),
+                is_public: true,
+                entry_point: RustFile {
+                    doc_comment: None,
+                    symbols: vec![RustSymbol::IncludedFile {
+                        path: included_rs.clone(),
+                        symbols: vec![stub_rust_symbol(stub_symbol_with_name(STUB_SYMBOL_NAME))],
+                        cfg: Some(feature("serde")),
+                    }],
+                    path: PathBuf::from("/crate/src/lib.rs"),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
+                },
+                internal_files: HashMap::new(),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
+            };
+
+            let modules = directory.extract_modules().unwrap();
+
+            assert_eq!(modules.len(), 1);
+            assert_matches!(
+                &modules[0].symbols[..],
+                [ModuleItem::Symbol { symbol, location, .. }]
+                if location.path == included_rs && symbol.source_code.starts_with(
+                    "// Available on crate feature `serde` only.\n"
+                )
+            );
+        }
+
+        #[test]
+        fn impl_block() {
+            let directory = ModuleDirectory {
+                name: String::new(),
+                is_public: true,
+                entry_point: RustFile {
+                    doc_comment: None,
+                    symbols: vec![RustSymbol::ImplBlock {
+                        type_path: "Foo".to_string(),
+                        source_code: "impl Foo {}".to_string(),
+                        cfg: Some(feature("serde")),
+                    }],
+                    path: PathBuf::new(),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
+                },
+                internal_files: HashMap::new(),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
+            };
+
+            let modules = directory.extract_modules().unwrap();
+
+            assert_matches!(
+                &modules[0].symbols[0],
+                ModuleItem::ImplBlock { source_code, .. }
+                if source_code == "// Available on crate feature `serde` only.\nimpl Foo {}"
+            );
+        }
+
+        #[test]
+        fn module_block() {
+            let symbol = stub_symbol();
+            let directory = ModuleDirectory {
+                name: String::new(),
+                is_public: true,
+                entry_point: RustFile {
+                    doc_comment: None,
+                    symbols: vec![RustSymbol::ModuleBlock {
+                        name: "inline".to_string(),
+                        visibility: Visibility::Public,
+                        content: vec![RustSymbol::Symbol {
+                            symbol: symbol.clone(),
+                            kind: SymbolKind::Function,
+                            generics: None,
+                            cfg: Some(feature("b")),
+                            is_doc_hidden: false,
+                            span: SourceSpan::default(),
+                        }],
+                        doc_comment: None,
+                        cfg: Some(feature("a")),
+                        is_doc_hidden: false,
+                        span: SourceSpan::default(),
+                    }],
+                    path: PathBuf::new(),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
+                },
+                internal_files: HashMap::new(),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
+            };
+
+            let modules = directory.extract_modules().unwrap();
+
+            assert_matches!(
+                &modules[1].symbols[0],
+                ModuleItem::Symbol { symbol: annotated_symbol, .. }
+                if annotated_symbol.source_code == format!(
+                    "// Available on crate features `a` and `b` only.\n{}",
+                    symbol.source_code
+                )
+            );
+        }
+
+        #[test]
+        fn module_import() {
+            let symbol = stub_symbol();
+            let directory = ModuleDirectory {
+                name: String::new(),
+                is_public: true,
+                entry_point: RustFile {
+                    doc_comment: None,
+                    symbols: vec![RustSymbol::ModuleImport {
+                        name: "submodule".to_string(),
+                        visibility: Visibility::Public,
+                        path: None,
+                        cfg: Some(feature("a")),
+                        is_doc_hidden: false,
+                        span: SourceSpan::default(),
+                    }],
+                    path: PathBuf::new(),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
+                },
+                internal_files: HashMap::from([(
+                    "submodule".to_string(),
+                    RustFile {
+                        doc_comment: None,
+                        symbols: vec![stub_rust_symbol(symbol.clone())],
+                        path: PathBuf::new(),
+                        span: SourceSpan::default(),
+                        syntax_errors: Vec::new(),
+                    },
+                )]),
+                cfg: None,
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
+            };
+
+            let modules = directory.extract_modules().unwrap();
+
+            assert_matches!(
+                &modules[1].symbols[0],
+                ModuleItem::Symbol { symbol: annotated_symbol, .. }
+                if annotated_symbol.source_code == format!(
+                    "// Available on crate feature `a` only.\n{}",
+                    symbol.source_code
+                )
+            );
+        }
+
+        #[test]
+        fn module_directory() {
+            let symbol = stub_symbol();
+            let directory = ModuleDirectory {
+                name: "submodule".to_string(),
+                is_public: true,
+                entry_point: RustFile {
+                    doc_comment: None,
+                    symbols: vec![stub_rust_symbol(symbol.clone())],
+                    path: PathBuf::new(),
+                    span: SourceSpan::default(),
+                    syntax_errors: Vec::new(),
+                },
+                internal_files: HashMap::new(),
+                cfg: Some(feature("a")),
+                is_doc_hidden: false,
+                unresolved_modules: Vec::new(),
+            };
+
+            let modules = directory.extract_modules().unwrap();
+
+            assert_matches!(
+                &modules[0].symbols[0],
+                ModuleItem::Symbol { symbol: annotated_symbol, .. }
+                if annotated_symbol.source_code == format!(
+                    "// Available on crate feature `a` only.\n{}",
+                    symbol.source_code
+                )
+            );
         }
     }
 }
@@ -1,11 +1,21 @@
-use daipendency_extractor::ExtractionError;
+use daipendency_extractor::{ExtractionError, Symbol};
 
-use super::module_directory::{Module, ModuleDirectory};
+use super::module_directory::{Module, ModuleDirectory, ModuleItem};
+use super::options::ExtractionOptions;
+use super::parsing::{render_macro_rules, SymbolKind};
 
+/// The number of rules rendered for each `macro_rules!` macro by default.
+pub const DEFAULT_MAX_MACRO_RULES: usize = 10;
+
+/// Split the module directories of the crate into their modules.
+///
+/// Macros exported with `#[macro_export]` are moved to the crate root, as that's where rustc
+/// exports them, and only the first rules of each are rendered, up to the maximum in `options`.
 pub fn extract_modules(
     module_directories: &[ModuleDirectory],
+    options: &ExtractionOptions,
 ) -> Result<Vec<Module>, ExtractionError> {
-    let modules = module_directories
+    let mut modules = module_directories
         .iter()
         .map(|m| m.extract_modules())
         .collect::<Result<Vec<Vec<Module>>, ExtractionError>>()?
@@ -13,5 +23,40 @@ pub fn extract_modules(
         .flatten()
         .collect::<Vec<_>>();
 
+    let exported_macros = take_exported_macros(&mut modules, options.max_macro_rules);
+    if let Some(root_module) = modules.iter_mut().find(|module| module.name.is_empty()) {
+        root_module.symbols.extend(exported_macros);
+    }
+
     Ok(modules)
 }
+
+/// Remove the exported macros from `modules`, turning them into regular symbols.
+fn take_exported_macros(modules: &mut [Module], max_rules: usize) -> Vec<ModuleItem> {
+    let mut exported_macros = Vec::new();
+    for module in modules {
+        let (macros, symbols) = std::mem::take(&mut module.symbols)
+            .into_iter()
+            .partition(|item| matches!(item, ModuleItem::ExportedMacro { .. }));
+        module.symbols = symbols;
+        exported_macros.extend(macros.into_iter().filter_map(|item| match item {
+            ModuleItem::ExportedMacro {
+                symbol,
+                matchers,
+                is_doc_hidden,
+                location,
+            } => Some(ModuleItem::Symbol {
+                symbol: Symbol {
+                    source_code: render_macro_rules(&symbol.source_code, &matchers, max_rules),
+                    ..symbol
+                },
+                kind: SymbolKind::Macro,
+                generics: None,
+                is_doc_hidden,
+                location,
+            }),
+            _ => None,
+        }));
+    }
+    exported_macros
+}
@@ -1,34 +1,183 @@
-use super::symbol_resolution::SymbolResolution;
-use daipendency_extractor::Namespace;
+use super::module_directory::{SourceLocation, UnresolvedInclude};
+use super::parsing::SymbolKind;
+use super::symbol_resolution::{SymbolDeclaration, SymbolResolution};
+use daipendency_extractor::{Namespace, Symbol};
 use std::collections::HashMap;
 
+/// The doc comment of the namespaces of the modules whose files couldn't be found, once converted
+/// to [`Namespace`]s
+pub const UNRESOLVED_MODULE_DOC_COMMENT: &str =
+    "The file of this module couldn't be found, so its items are unknown.";
+
+/// The name of the placeholder symbols of the `include!` invocations whose files couldn't be found
+pub const INCLUDE_PLACEHOLDER_NAME: &str = "include!";
+
+/// Origin of the visibility of a symbol in a namespace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VisibilityOrigin {
+    /// The symbol is declared public in the module of the namespace (e.g. `pub fn foo()`)
+    Declaration,
+    /// The symbol is made public in the namespace by a reexport (e.g. `pub use inner::foo;`)
+    Reexport,
+}
+
+/// A symbol of a namespace, along with the details of the item it stands for.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SymbolDetails {
+    /// The symbol, named as it's exposed in the namespace
+    pub symbol: Symbol,
+    /// The kind of item the symbol stands for
+    pub kind: SymbolKind,
+    /// The generic parameters of the symbol, if any (e.g. `<T: Clone>`)
+    pub generics: Option<String>,
+    /// Whether the symbol is declared in the module of the namespace or reexported into it
+    pub visibility_origin: VisibilityOrigin,
+    /// The path to the module defining the symbol (e.g. `my_crate::formatting`)
+    pub module_path: String,
+    /// The path to the item where it's defined (e.g. `my_crate::formatting::Format`)
+    pub canonical_path: String,
+    /// All the public paths the item is reachable at (e.g. `["my_crate::Format",
+    /// "my_crate::formatting::Format"]`)
+    pub public_paths: Vec<String>,
+    /// The location of the symbol's declaration, or of the reexport it stems from if the item
+    /// isn't declared in the crate
+    pub location: SourceLocation,
+}
+
+/// A namespace of the public API of a Rust crate, with the details of its symbols.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RustNamespace {
+    /// The path to the module of the namespace (e.g. `my_crate::formatting`)
+    pub name: String,
+    /// The symbols of the namespace
+    pub symbols: Vec<SymbolDetails>,
+    /// The inner doc comments of the module, if any
+    pub doc_comment: Option<String>,
+    /// The location of the module, if known
+    pub location: Option<SourceLocation>,
+    /// Whether the file of the module couldn't be found, so its symbols are unknown
+    pub is_unresolved: bool,
+    /// The `include!` invocations of the module whose files couldn't be found, so the symbols
+    /// they'd bring are unknown
+    pub unresolved_includes: Vec<UnresolvedInclude>,
+}
+
+impl RustNamespace {
+    /// Find the symbol with the given name (e.g. `Format`).
+    pub fn get_symbol(&self, name: &str) -> Option<&SymbolDetails> {
+        self.symbols
+            .iter()
+            .find(|details| details.symbol.name == name)
+    }
+}
+
+impl From<RustNamespace> for Namespace {
+    /// Convert the namespace, leaving a placeholder symbol named `include!` for each `include!`
+    /// invocation whose file couldn't be found, and marking it as unresolved in its doc comment if
+    /// its module's file couldn't be found.
+    fn from(namespace: RustNamespace) -> Self {
+        let include_placeholders =
+            namespace
+                .unresolved_includes
+                .into_iter()
+                .map(|unresolved_include| Symbol {
+                    name: INCLUDE_PLACEHOLDER_NAME.to_string(),
+                    source_code: format!(
+                        "// The file included here couldn't be found.\n{}",
+                        unresolved_include.source_code
+                    ),
+                });
+        Namespace {
+            name: namespace.name,
+            symbols: namespace
+                .symbols
+                .into_iter()
+                .map(|details| details.symbol)
+                .chain(include_placeholders)
+                .collect(),
+            doc_comment: if namespace.is_unresolved {
+                Some(UNRESOLVED_MODULE_DOC_COMMENT.to_string())
+            } else {
+                namespace.doc_comment
+            },
+        }
+    }
+}
+
 /// Construct the final namespace hierarchy using the resolved symbols.
 pub fn construct_namespaces(
     symbol_resolution: SymbolResolution,
     crate_name: &str,
 ) -> Vec<Namespace> {
-    let mut namespace_by_path: HashMap<String, Namespace> = HashMap::new();
+    construct_rust_namespaces(symbol_resolution, crate_name)
+        .into_iter()
+        .map(Namespace::from)
+        .collect()
+}
+
+/// Construct the final namespace hierarchy using the resolved symbols, retaining their details.
+pub fn construct_rust_namespaces(
+    symbol_resolution: SymbolResolution,
+    crate_name: &str,
+) -> Vec<RustNamespace> {
+    let mut namespace_by_path: HashMap<String, RustNamespace> = HashMap::new();
     let crate_name = crate_name.replace("-", "_");
 
     // Group symbols by namespace
     for resolved_symbol in &symbol_resolution.symbols {
         for module_path in &resolved_symbol.modules {
-            let namespace_name = if module_path.is_empty() {
-                crate_name.to_string()
-            } else {
-                format!("{}::{}", crate_name, module_path)
-            };
+            let namespace_name = get_namespace_name(module_path, &crate_name);
             let namespace = namespace_by_path
                 .entry(namespace_name.clone())
-                .or_insert_with(|| Namespace {
+                .or_insert_with(|| RustNamespace {
                     name: namespace_name,
                     symbols: Vec::new(),
                     doc_comment: symbol_resolution.doc_comments.get(module_path).cloned(),
+                    location: symbol_resolution.module_locations.get(module_path).cloned(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 });
-            namespace.symbols.push(resolved_symbol.symbol.clone());
+            namespace.symbols.push(get_symbol_details(
+                resolved_symbol,
+                module_path,
+                &crate_name,
+            ));
         }
     }
 
+    for module_path in &symbol_resolution.unresolved_modules {
+        let namespace_name = get_namespace_name(module_path, &crate_name);
+        namespace_by_path.insert(
+            namespace_name.clone(),
+            RustNamespace {
+                name: namespace_name,
+                symbols: Vec::new(),
+                doc_comment: None,
+                location: symbol_resolution.module_locations.get(module_path).cloned(),
+                is_unresolved: true,
+                unresolved_includes: Vec::new(),
+            },
+        );
+    }
+
+    for (module_path, unresolved_includes) in &symbol_resolution.unresolved_includes {
+        let namespace_name = get_namespace_name(module_path, &crate_name);
+        let namespace = namespace_by_path
+            .entry(namespace_name.clone())
+            .or_insert_with(|| RustNamespace {
+                name: namespace_name,
+                symbols: Vec::new(),
+                doc_comment: symbol_resolution.doc_comments.get(module_path).cloned(),
+                location: symbol_resolution.module_locations.get(module_path).cloned(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            });
+        namespace.unresolved_includes = unresolved_includes.clone();
+    }
+
     let mut namespaces: Vec<_> = namespace_by_path.into_values().collect();
     namespaces.sort_by(|a, b| {
         let a_components = a.name.matches("::").count();
@@ -38,13 +187,65 @@ pub fn construct_namespaces(
     namespaces
 }
 
+fn get_namespace_name(module_path: &str, crate_name: &str) -> String {
+    if module_path.is_empty() {
+        crate_name.to_string()
+    } else {
+        format!("{}::{}", crate_name, module_path)
+    }
+}
+
+fn get_symbol_details(
+    declaration: &SymbolDeclaration,
+    module_path: &str,
+    crate_name: &str,
+) -> SymbolDetails {
+    let is_reexport = matches!(
+        declaration.kind,
+        SymbolKind::Reexport | SymbolKind::UnresolvedGlob | SymbolKind::Module
+    );
+    SymbolDetails {
+        symbol: declaration.symbol.clone(),
+        kind: declaration.kind,
+        generics: declaration.generics.clone(),
+        visibility_origin: if is_reexport || declaration.module_path != module_path {
+            VisibilityOrigin::Reexport
+        } else {
+            VisibilityOrigin::Declaration
+        },
+        module_path: match declaration.module_path.strip_prefix("::") {
+            Some(external_module_path) => external_module_path.to_string(),
+            None => get_namespace_name(&declaration.module_path, crate_name),
+        },
+        canonical_path: qualify_item_path(&declaration.canonical_path, crate_name),
+        public_paths: declaration
+            .public_paths
+            .iter()
+            .map(|path| qualify_item_path(path, crate_name))
+            .collect(),
+        location: declaration.location.clone(),
+    }
+}
+
+/// Replace the `crate` or `::` prefix of an item path with the name of the crate (e.g.
+/// `my_crate::Format` for `crate::Format`, and `serde_json::Value` for `::serde_json::Value`).
+fn qualify_item_path(item_path: &str, crate_name: &str) -> String {
+    if let Some(external_path) = item_path.strip_prefix("::") {
+        external_path.to_string()
+    } else if let Some(local_path) = item_path.strip_prefix("crate::") {
+        format!("{}::{}", crate_name, local_path)
+    } else {
+        item_path.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assertables::assert_contains;
 
     use super::*;
-    use crate::api::symbol_resolution::SymbolDeclaration;
     use crate::test_helpers::{get_namespace, stub_symbol_with_name};
+    use std::collections::HashSet;
 
     const STUB_CRATE_NAME: &str = "test_crate";
     const STUB_SYMBOL_NAME: &str = "test";
@@ -55,6 +256,9 @@ mod tests {
             SymbolResolution {
                 symbols: Vec::new(),
                 doc_comments: HashMap::new(),
+                module_locations: HashMap::new(),
+                unresolved_modules: HashSet::new(),
+                unresolved_includes: HashMap::new(),
             },
             STUB_CRATE_NAME,
         );
@@ -68,12 +272,23 @@ mod tests {
         let resolved_symbols = vec![SymbolDeclaration {
             symbol: symbol.clone(),
             modules: vec![String::new()],
+            is_doc_hidden: false,
+            kind: SymbolKind::Function,
+            generics: None,
+            module_path: String::new(),
+            canonical_path: String::new(),
+            public_paths: Vec::new(),
+            external_reexport: None,
+            location: SourceLocation::default(),
         }];
 
         let namespaces = construct_namespaces(
             SymbolResolution {
                 symbols: resolved_symbols,
                 doc_comments: HashMap::new(),
+                module_locations: HashMap::new(),
+                unresolved_modules: HashSet::new(),
+                unresolved_includes: HashMap::new(),
             },
             STUB_CRATE_NAME,
         );
@@ -93,10 +308,26 @@ mod tests {
             SymbolDeclaration {
                 symbol: symbol1.clone(),
                 modules: vec![module_name.clone()],
+                is_doc_hidden: false,
+                kind: SymbolKind::Function,
+                generics: None,
+                module_path: String::new(),
+                canonical_path: String::new(),
+                public_paths: Vec::new(),
+                external_reexport: None,
+                location: SourceLocation::default(),
             },
             SymbolDeclaration {
                 symbol: symbol2.clone(),
                 modules: vec![module_name.clone()],
+                is_doc_hidden: false,
+                kind: SymbolKind::Function,
+                generics: None,
+                module_path: String::new(),
+                canonical_path: String::new(),
+                public_paths: Vec::new(),
+                external_reexport: None,
+                location: SourceLocation::default(),
             },
         ];
 
@@ -104,6 +335,9 @@ mod tests {
             SymbolResolution {
                 symbols: resolved_symbols,
                 doc_comments: HashMap::new(),
+                module_locations: HashMap::new(),
+                unresolved_modules: HashSet::new(),
+                unresolved_includes: HashMap::new(),
             },
             STUB_CRATE_NAME,
         );
@@ -123,10 +357,26 @@ mod tests {
             SymbolDeclaration {
                 symbol: symbol1.clone(),
                 modules: vec![String::new()],
+                is_doc_hidden: false,
+                kind: SymbolKind::Function,
+                generics: None,
+                module_path: String::new(),
+                canonical_path: String::new(),
+                public_paths: Vec::new(),
+                external_reexport: None,
+                location: SourceLocation::default(),
             },
             SymbolDeclaration {
                 symbol: symbol2.clone(),
                 modules: vec!["submodule".to_string()],
+                is_doc_hidden: false,
+                kind: SymbolKind::Function,
+                generics: None,
+                module_path: String::new(),
+                canonical_path: String::new(),
+                public_paths: Vec::new(),
+                external_reexport: None,
+                location: SourceLocation::default(),
             },
         ];
 
@@ -134,6 +384,9 @@ mod tests {
             SymbolResolution {
                 symbols: resolved_symbols,
                 doc_comments: HashMap::new(),
+                module_locations: HashMap::new(),
+                unresolved_modules: HashSet::new(),
+                unresolved_includes: HashMap::new(),
             },
             STUB_CRATE_NAME,
         );
@@ -153,12 +406,23 @@ mod tests {
         let resolved_symbols = vec![SymbolDeclaration {
             symbol: symbol.clone(),
             modules: vec!["outer".to_string(), "outer::inner".to_string()],
+            is_doc_hidden: false,
+            kind: SymbolKind::Function,
+            generics: None,
+            module_path: String::new(),
+            canonical_path: String::new(),
+            public_paths: Vec::new(),
+            external_reexport: None,
+            location: SourceLocation::default(),
         }];
 
         let namespaces = construct_namespaces(
             SymbolResolution {
                 symbols: resolved_symbols,
                 doc_comments: HashMap::new(),
+                module_locations: HashMap::new(),
+                unresolved_modules: HashSet::new(),
+                unresolved_includes: HashMap::new(),
             },
             STUB_CRATE_NAME,
         );
@@ -179,12 +443,23 @@ mod tests {
         let resolved_symbols = vec![SymbolDeclaration {
             symbol: symbol.clone(),
             modules: vec![String::new()],
+            is_doc_hidden: false,
+            kind: SymbolKind::Function,
+            generics: None,
+            module_path: String::new(),
+            canonical_path: String::new(),
+            public_paths: Vec::new(),
+            external_reexport: None,
+            location: SourceLocation::default(),
         }];
 
         let namespaces = construct_namespaces(
             SymbolResolution {
                 symbols: resolved_symbols,
                 doc_comments: HashMap::new(),
+                module_locations: HashMap::new(),
+                unresolved_modules: HashSet::new(),
+                unresolved_includes: HashMap::new(),
             },
             crate_name,
         );
@@ -201,12 +476,23 @@ mod tests {
         let resolved_symbols = vec![SymbolDeclaration {
             symbol: stub_symbol_with_name(STUB_SYMBOL_NAME),
             modules: vec![String::new()],
+            is_doc_hidden: false,
+            kind: SymbolKind::Function,
+            generics: None,
+            module_path: String::new(),
+            canonical_path: String::new(),
+            public_paths: Vec::new(),
+            external_reexport: None,
+            location: SourceLocation::default(),
         }];
 
         let namespaces = construct_namespaces(
             SymbolResolution {
                 symbols: resolved_symbols,
                 doc_comments: HashMap::from([(String::new(), doc_comment.to_string())]),
+                module_locations: HashMap::new(),
+                unresolved_modules: HashSet::new(),
+                unresolved_includes: HashMap::new(),
             },
             STUB_CRATE_NAME,
         );
@@ -216,6 +502,62 @@ mod tests {
         assert_eq!(root.doc_comment, Some(doc_comment.to_string()));
     }
 
+    #[test]
+    fn unresolved_module() {
+        let location = SourceLocation {
+            path: "src/lib.rs".into(),
+            ..SourceLocation::default()
+        };
+
+        let namespaces = construct_rust_namespaces(
+            SymbolResolution {
+                symbols: Vec::new(),
+                doc_comments: HashMap::new(),
+                module_locations: HashMap::from([("generated".to_string(), location.clone())]),
+                unresolved_modules: HashSet::from(["generated".to_string()]),
+                unresolved_includes: HashMap::new(),
+            },
+            STUB_CRATE_NAME,
+        );
+
+        assert_eq!(namespaces.len(), 1);
+        let namespace = &namespaces[0];
+        assert_eq!(namespace.name, format!("{STUB_CRATE_NAME}::generated"));
+        assert!(namespace.is_unresolved);
+        assert!(namespace.symbols.is_empty());
+        assert_eq!(namespace.location, Some(location));
+    }
+
+    #[test]
+    fn unresolved_include() {
+        let unresolved_include = UnresolvedInclude {
+            path: None,
+            source_code: "include!(env!(\"BINDINGS\"));".to_string(),
+            location: SourceLocation::default(),
+        };
+
+        let namespaces = construct_rust_namespaces(
+            SymbolResolution {
+                symbols: Vec::new(),
+                doc_comments: HashMap::new(),
+                module_locations: HashMap::new(),
+                unresolved_modules: HashSet::new(),
+                unresolved_includes: HashMap::from([(
+                    String::new(),
+                    vec![unresolved_include.clone()],
+                )]),
+            },
+            STUB_CRATE_NAME,
+        );
+
+        assert_eq!(namespaces.len(), 1);
+        let namespace = &namespaces[0];
+        assert_eq!(namespace.name, STUB_CRATE_NAME);
+        assert!(!namespace.is_unresolved);
+        assert!(namespace.symbols.is_empty());
+        assert_eq!(namespace.unresolved_includes, vec![unresolved_include]);
+    }
+
     mod sorting {
         use super::*;
 
@@ -225,10 +567,26 @@ mod tests {
                 SymbolDeclaration {
                     symbol: stub_symbol_with_name("test1"),
                     modules: vec![String::new()],
+                    is_doc_hidden: false,
+                    kind: SymbolKind::Function,
+                    generics: None,
+                    module_path: String::new(),
+                    canonical_path: String::new(),
+                    public_paths: Vec::new(),
+                    external_reexport: None,
+                    location: SourceLocation::default(),
                 },
                 SymbolDeclaration {
                     symbol: stub_symbol_with_name("test2"),
                     modules: vec!["error".to_string()],
+                    is_doc_hidden: false,
+                    kind: SymbolKind::Function,
+                    generics: None,
+                    module_path: String::new(),
+                    canonical_path: String::new(),
+                    public_paths: Vec::new(),
+                    external_reexport: None,
+                    location: SourceLocation::default(),
                 },
             ];
 
@@ -236,6 +594,9 @@ mod tests {
                 SymbolResolution {
                     symbols: resolved_symbols,
                     doc_comments: HashMap::new(),
+                    module_locations: HashMap::new(),
+                    unresolved_modules: HashSet::new(),
+                    unresolved_includes: HashMap::new(),
                 },
                 STUB_CRATE_NAME,
             );
@@ -250,10 +611,26 @@ mod tests {
                 SymbolDeclaration {
                     symbol: stub_symbol_with_name("test1"),
                     modules: vec!["submodule1".to_string()],
+                    is_doc_hidden: false,
+                    kind: SymbolKind::Function,
+                    generics: None,
+                    module_path: String::new(),
+                    canonical_path: String::new(),
+                    public_paths: Vec::new(),
+                    external_reexport: None,
+                    location: SourceLocation::default(),
                 },
                 SymbolDeclaration {
                     symbol: stub_symbol_with_name("test2"),
                     modules: vec!["submodule".to_string()],
+                    is_doc_hidden: false,
+                    kind: SymbolKind::Function,
+                    generics: None,
+                    module_path: String::new(),
+                    canonical_path: String::new(),
+                    public_paths: Vec::new(),
+                    external_reexport: None,
+                    location: SourceLocation::default(),
                 },
             ];
 
@@ -261,6 +638,9 @@ mod tests {
                 SymbolResolution {
                     symbols: resolved_symbols,
                     doc_comments: HashMap::new(),
+                    module_locations: HashMap::new(),
+                    unresolved_modules: HashSet::new(),
+                    unresolved_includes: HashMap::new(),
                 },
                 STUB_CRATE_NAME,
             );
@@ -272,4 +652,149 @@ mod tests {
             );
         }
     }
+
+    mod symbol_details {
+        use super::*;
+
+        fn make_declaration(
+            kind: SymbolKind,
+            module_path: &str,
+            modules: Vec<String>,
+        ) -> SymbolDeclaration {
+            SymbolDeclaration {
+                symbol: stub_symbol_with_name(STUB_SYMBOL_NAME),
+                modules,
+                is_doc_hidden: false,
+                kind,
+                generics: Some("<T>".to_string()),
+                module_path: module_path.to_string(),
+                canonical_path: String::new(),
+                public_paths: Vec::new(),
+                external_reexport: None,
+                location: SourceLocation::default(),
+            }
+        }
+
+        fn construct(declaration: SymbolDeclaration) -> Vec<RustNamespace> {
+            construct_rust_namespaces(
+                SymbolResolution {
+                    symbols: vec![declaration],
+                    doc_comments: HashMap::new(),
+                    module_locations: HashMap::new(),
+                    unresolved_modules: HashSet::new(),
+                    unresolved_includes: HashMap::new(),
+                },
+                STUB_CRATE_NAME,
+            )
+        }
+
+        fn get_details<'a>(
+            namespace_name: &str,
+            namespaces: &'a [RustNamespace],
+        ) -> &'a SymbolDetails {
+            namespaces
+                .iter()
+                .find(|namespace| namespace.name == namespace_name)
+                .and_then(|namespace| namespace.get_symbol(STUB_SYMBOL_NAME))
+                .unwrap()
+        }
+
+        #[test]
+        fn declaration() {
+            let namespaces = construct(make_declaration(
+                SymbolKind::Function,
+                "inner",
+                vec!["inner".to_string()],
+            ));
+
+            let details = get_details("test_crate::inner", &namespaces);
+            assert_eq!(details.kind, SymbolKind::Function);
+            assert_eq!(details.generics, Some("<T>".to_string()));
+            assert_eq!(details.visibility_origin, VisibilityOrigin::Declaration);
+            assert_eq!(details.module_path, "test_crate::inner");
+        }
+
+        #[test]
+        fn reexported_declaration() {
+            let namespaces = construct(make_declaration(
+                SymbolKind::Function,
+                "inner",
+                vec!["inner".to_string(), String::new()],
+            ));
+
+            let details = get_details(STUB_CRATE_NAME, &namespaces);
+            assert_eq!(details.visibility_origin, VisibilityOrigin::Reexport);
+            assert_eq!(details.module_path, "test_crate::inner");
+        }
+
+        #[test]
+        fn unresolved_reexport() {
+            let namespaces = construct(make_declaration(
+                SymbolKind::Reexport,
+                "",
+                vec![String::new()],
+            ));
+
+            let details = get_details(STUB_CRATE_NAME, &namespaces);
+            assert_eq!(details.visibility_origin, VisibilityOrigin::Reexport);
+            assert_eq!(details.module_path, STUB_CRATE_NAME);
+        }
+
+        #[test]
+        fn external_declaration() {
+            let namespaces = construct(make_declaration(
+                SymbolKind::Struct,
+                "::dependency::inner",
+                vec![String::new()],
+            ));
+
+            let details = get_details(STUB_CRATE_NAME, &namespaces);
+            assert_eq!(details.visibility_origin, VisibilityOrigin::Reexport);
+            assert_eq!(details.module_path, "dependency::inner");
+        }
+
+        #[test]
+        fn local_item_paths() {
+            let mut declaration = make_declaration(
+                SymbolKind::Struct,
+                "inner",
+                vec!["inner".to_string(), String::new()],
+            );
+            declaration.canonical_path = format!("crate::inner::{STUB_SYMBOL_NAME}");
+            declaration.public_paths = vec![
+                format!("crate::{STUB_SYMBOL_NAME}"),
+                format!("crate::inner::{STUB_SYMBOL_NAME}"),
+            ];
+
+            let namespaces = construct(declaration);
+
+            let details = get_details(STUB_CRATE_NAME, &namespaces);
+            assert_eq!(
+                details.canonical_path,
+                format!("test_crate::inner::{STUB_SYMBOL_NAME}")
+            );
+            assert_eq!(
+                details.public_paths,
+                vec![
+                    format!("test_crate::{STUB_SYMBOL_NAME}"),
+                    format!("test_crate::inner::{STUB_SYMBOL_NAME}"),
+                ]
+            );
+        }
+
+        #[test]
+        fn external_item_paths() {
+            let mut declaration =
+                make_declaration(SymbolKind::Struct, "::dependency", vec![String::new()]);
+            declaration.canonical_path = format!("::dependency::{STUB_SYMBOL_NAME}");
+
+            let namespaces = construct(declaration);
+
+            let details = get_details(STUB_CRATE_NAME, &namespaces);
+            assert_eq!(
+                details.canonical_path,
+                format!("dependency::{STUB_SYMBOL_NAME}")
+            );
+        }
+    }
 }
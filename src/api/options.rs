@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use super::module_extraction::DEFAULT_MAX_MACRO_RULES;
+use super::parsing::CfgOptions;
+
+/// The settings of the extraction of the public API of a crate.
+///
+/// By default, items disabled by `#[cfg(...)]` attributes are pruned using no features and the
+/// host's target configuration, and items hidden from the documentation are excluded.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ExtractionOptions {
+    /// The enabled Cargo features (e.g. `std`), or `None` for no features.
+    ///
+    /// The features are enabled as they are, so [`crate::RustExtractor`] resolves them, or the
+    /// crate's default features if `None`, along with the features they enable from its manifest
+    /// beforehand.
+    pub features: Option<Vec<String>>,
+    /// The target configuration options (e.g. `("unix", None)`), or `None` for the host's.
+    pub target_options: Option<HashSet<(String, Option<String>)>>,
+    /// Whether to retain the items disabled by `#[cfg(...)]` attributes and annotate the
+    /// conditionally compiled items with their predicates, instead of pruning the disabled items
+    pub annotate_cfg: bool,
+    /// Whether to include the items hidden from the documentation (i.e. `#[doc(hidden)]`)
+    pub include_doc_hidden: bool,
+    /// Whether to inline the items reexported from dependencies, instead of preserving their
+    /// `pub use` declarations
+    pub inline_reexports: bool,
+    /// Whether to fail if any source file has diagnostics (e.g. syntax errors)
+    pub strict_parsing: bool,
+    /// Whether to carry on when the file of a module can't be found, instead of failing
+    pub tolerate_missing_modules: bool,
+    /// The directories where the files included from `OUT_DIR` are looked up, in order
+    pub out_dirs: Vec<PathBuf>,
+    /// The number of rules rendered for each `macro_rules!` macro
+    pub max_macro_rules: usize,
+}
+
+impl ExtractionOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable the given Cargo features.
+    pub fn with_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.features = Some(features.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Use the given target configuration options instead of the host's.
+    pub fn with_target_options<I>(mut self, target_options: I) -> Self
+    where
+        I: IntoIterator<Item = (String, Option<String>)>,
+    {
+        self.target_options = Some(target_options.into_iter().collect());
+        self
+    }
+
+    /// Retain and annotate the items disabled by `#[cfg(...)]` attributes instead of pruning them.
+    pub fn with_cfg_annotations(mut self) -> Self {
+        self.annotate_cfg = true;
+        self
+    }
+
+    /// Include the items hidden from the documentation with `#[doc(hidden)]`.
+    pub fn with_doc_hidden_items(mut self) -> Self {
+        self.include_doc_hidden = true;
+        self
+    }
+
+    /// Inline the items reexported from dependencies.
+    pub fn with_inlined_reexports(mut self) -> Self {
+        self.inline_reexports = true;
+        self
+    }
+
+    /// Fail if any source file has diagnostics.
+    pub fn with_strict_parsing(mut self) -> Self {
+        self.strict_parsing = true;
+        self
+    }
+
+    /// Carry on when the file of a module can't be found.
+    pub fn with_missing_modules_tolerated(mut self) -> Self {
+        self.tolerate_missing_modules = true;
+        self
+    }
+
+    /// Look up the files included from `OUT_DIR` in `out_dir`, after the directories set so far.
+    pub fn with_out_dir(mut self, out_dir: impl Into<PathBuf>) -> Self {
+        self.out_dirs.push(out_dir.into());
+        self
+    }
+
+    /// Render up to `max_rules` rules of each `macro_rules!` macro.
+    pub fn with_max_macro_rules(mut self, max_rules: usize) -> Self {
+        self.max_macro_rules = max_rules;
+        self
+    }
+
+    /// Get the configuration against which items are pruned, or `None` if they're annotated
+    /// instead.
+    pub fn get_cfg_options(&self) -> Option<CfgOptions> {
+        if self.annotate_cfg {
+            return None;
+        }
+        let default_options = CfgOptions::default();
+        Some(CfgOptions {
+            features: self.features.iter().flatten().cloned().collect(),
+            target_options: self
+                .target_options
+                .clone()
+                .unwrap_or(default_options.target_options),
+        })
+    }
+}
+
+impl Default for ExtractionOptions {
+    fn default() -> Self {
+        Self {
+            features: None,
+            target_options: None,
+            annotate_cfg: false,
+            include_doc_hidden: false,
+            inline_reexports: false,
+            strict_parsing: false,
+            tolerate_missing_modules: false,
+            out_dirs: Vec::new(),
+            max_macro_rules: DEFAULT_MAX_MACRO_RULES,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_cfg_options() {
+        let options = ExtractionOptions::default();
+
+        assert_eq!(options.get_cfg_options(), Some(CfgOptions::default()));
+    }
+
+    #[test]
+    fn custom_cfg_options() {
+        let target_options = [("unix".to_string(), None)];
+        let options = ExtractionOptions::new()
+            .with_features(["std"])
+            .with_target_options(target_options.clone());
+
+        let cfg_options = options.get_cfg_options().unwrap();
+
+        assert_eq!(cfg_options.features, HashSet::from(["std".to_string()]));
+        assert_eq!(cfg_options.target_options, HashSet::from(target_options));
+    }
+
+    #[test]
+    fn cfg_annotations() {
+        let options = ExtractionOptions::new().with_cfg_annotations();
+
+        assert_eq!(options.get_cfg_options(), None);
+    }
+}
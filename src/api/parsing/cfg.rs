@@ -0,0 +1,678 @@
+use std::collections::HashSet;
+use std::fmt;
+use tree_sitter::Node;
+
+/// A configuration predicate, as used in `#[cfg(...)]` attributes (e.g. `all(unix, feature = "std")`).
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum CfgPredicate {
+    /// A configuration option, with an optional value (e.g. `unix` or `feature = "std"`).
+    Option {
+        /// The name of the option (e.g. `feature`)
+        name: String,
+        /// The value of the option, if any (e.g. `std`)
+        value: Option<String>,
+    },
+    /// A predicate that holds if all its predicates hold (e.g. `all(unix, feature = "std")`).
+    All(Vec<CfgPredicate>),
+    /// A predicate that holds if any of its predicates holds (e.g. `any(unix, windows)`).
+    Any(Vec<CfgPredicate>),
+    /// A predicate that holds if its predicate doesn't (e.g. `not(unix)`).
+    Not(Box<CfgPredicate>),
+}
+
+impl CfgPredicate {
+    /// Whether the predicate holds with the given options.
+    pub fn evaluate(&self, options: &CfgOptions) -> bool {
+        match self {
+            CfgPredicate::Option { name, value } => options.is_enabled(name, value.as_deref()),
+            CfgPredicate::All(predicates) => predicates.iter().all(|p| p.evaluate(options)),
+            CfgPredicate::Any(predicates) => predicates.iter().any(|p| p.evaluate(options)),
+            CfgPredicate::Not(predicate) => !predicate.evaluate(options),
+        }
+    }
+
+    /// Combine two optional predicates, so that both must hold.
+    pub fn conjoin(
+        first: Option<&CfgPredicate>,
+        second: Option<&CfgPredicate>,
+    ) -> Option<CfgPredicate> {
+        match (first, second) {
+            (None, None) => None,
+            (Some(predicate), None) | (None, Some(predicate)) => Some(predicate.clone()),
+            (Some(first), Some(second)) => {
+                let mut predicates = match first {
+                    CfgPredicate::All(predicates) => predicates.clone(),
+                    _ => vec![first.clone()],
+                };
+                match second {
+                    CfgPredicate::All(second_predicates) => {
+                        predicates.extend(second_predicates.iter().cloned())
+                    }
+                    _ => predicates.push(second.clone()),
+                }
+                Some(CfgPredicate::All(predicates))
+            }
+        }
+    }
+
+    /// Describe the predicate for humans, like docs.rs does (e.g. "Available on crate feature `std` only.").
+    pub fn get_annotation(&self) -> String {
+        let requirement = match self.get_required_features() {
+            Some((features, _)) if features.len() == 1 => {
+                format!("crate feature `{}`", features[0])
+            }
+            Some((features, conjunction)) => {
+                let (last_feature, other_features) = features.split_last().unwrap();
+                let other_features: Vec<_> =
+                    other_features.iter().map(|f| format!("`{}`", f)).collect();
+                format!(
+                    "crate features {} {} `{}`",
+                    other_features.join(", "),
+                    conjunction,
+                    last_feature
+                )
+            }
+            None => format!("`{}`", self),
+        };
+        format!("Available on {} only.", requirement)
+    }
+
+    /// Get the features required by a predicate made up of features only, with the conjunction
+    /// between them.
+    fn get_required_features(&self) -> Option<(Vec<&str>, &'static str)> {
+        fn get_feature(predicate: &CfgPredicate) -> Option<&str> {
+            match predicate {
+                CfgPredicate::Option {
+                    name,
+                    value: Some(value),
+                } if name == "feature" => Some(value.as_str()),
+                _ => None,
+            }
+        }
+        let (predicates, conjunction) = match self {
+            CfgPredicate::All(predicates) => (predicates, "and"),
+            CfgPredicate::Any(predicates) => (predicates, "or"),
+            _ => return get_feature(self).map(|feature| (vec![feature], "and")),
+        };
+        if predicates.is_empty() {
+            return None;
+        }
+        predicates
+            .iter()
+            .map(get_feature)
+            .collect::<Option<Vec<_>>>()
+            .map(|features| (features, conjunction))
+    }
+}
+
+impl fmt::Display for CfgPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (operator, predicates) = match self {
+            CfgPredicate::Option { name, value: None } => return write!(f, "{}", name),
+            CfgPredicate::Option {
+                name,
+                value: Some(value),
+            } => return write!(f, "{} = \"{}\"", name, value),
+            CfgPredicate::All(predicates) => ("all", predicates.iter().collect::<Vec<_>>()),
+            CfgPredicate::Any(predicates) => ("any", predicates.iter().collect()),
+            CfgPredicate::Not(predicate) => ("not", vec![predicate.as_ref()]),
+        };
+        let predicates: Vec<_> = predicates.iter().map(|p| p.to_string()).collect();
+        write!(f, "{}({})", operator, predicates.join(", "))
+    }
+}
+
+/// The Cargo features and target configuration options against which predicates are evaluated.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct CfgOptions {
+    /// The enabled Cargo features (e.g. `std`).
+    pub features: HashSet<String>,
+    /// The target configuration options, with their values if any (e.g. `("unix", None)` and
+    /// `("target_os", Some("linux"))`).
+    pub target_options: HashSet<(String, Option<String>)>,
+}
+
+impl CfgOptions {
+    fn is_enabled(&self, name: &str, value: Option<&str>) -> bool {
+        match (name, value) {
+            ("feature", Some(feature)) => self.features.contains(feature),
+            _ => self
+                .target_options
+                .contains(&(name.to_string(), value.map(str::to_string))),
+        }
+    }
+}
+
+impl Default for CfgOptions {
+    /// No features, and the configuration options of the host target.
+    fn default() -> Self {
+        Self {
+            features: HashSet::new(),
+            target_options: get_host_target_options(),
+        }
+    }
+}
+
+fn get_host_target_options() -> HashSet<(String, Option<String>)> {
+    let endian = if cfg!(target_endian = "little") {
+        "little"
+    } else {
+        "big"
+    };
+    let mut options = HashSet::from([
+        (
+            "target_os".to_string(),
+            Some(std::env::consts::OS.to_string()),
+        ),
+        (
+            "target_family".to_string(),
+            Some(std::env::consts::FAMILY.to_string()),
+        ),
+        (
+            "target_arch".to_string(),
+            Some(std::env::consts::ARCH.to_string()),
+        ),
+        (
+            "target_pointer_width".to_string(),
+            Some(usize::BITS.to_string()),
+        ),
+        ("target_endian".to_string(), Some(endian.to_string())),
+    ]);
+    if matches!(std::env::consts::FAMILY, "unix" | "windows") {
+        options.insert((std::env::consts::FAMILY.to_string(), None));
+    }
+    options
+}
+
+/// Get the predicate that must hold for the node to be compiled, from its `#[cfg]` and
+/// `#[cfg_attr(..., cfg(...))]` attributes.
+///
+/// Malformed predicates are ignored.
+pub fn extract_cfg_predicate(node: &Node, source_code: &str) -> Option<CfgPredicate> {
+    let mut predicates = Vec::new();
+
+    let mut current = node.prev_sibling();
+    while let Some(sibling) = current {
+        if sibling.kind() != "attribute_item" {
+            break;
+        }
+        if let Some(meta_item) = parse_attribute_item(&sibling, source_code) {
+            predicates.extend(get_attribute_predicate(&meta_item));
+        }
+        current = sibling.prev_sibling();
+    }
+
+    predicates.reverse();
+    match predicates.len() {
+        0 => None,
+        1 => predicates.pop(),
+        _ => Some(CfgPredicate::All(predicates)),
+    }
+}
+
+/// An attribute or one of its arguments (e.g. `unix`, `feature = "std"` or `all(...)`).
+#[derive(Debug, Clone, PartialEq)]
+enum MetaItem {
+    Word(String),
+    NameValue(String, String),
+    List(String, Vec<MetaItem>),
+}
+
+fn get_attribute_predicate(meta_item: &MetaItem) -> Option<CfgPredicate> {
+    let MetaItem::List(name, arguments) = meta_item else {
+        return None;
+    };
+    match (name.as_str(), arguments.as_slice()) {
+        ("cfg", [argument]) => parse_predicate(argument),
+        ("cfg_attr", [condition, attributes @ ..]) => {
+            let condition = parse_predicate(condition)?;
+            let predicates: Vec<_> = attributes
+                .iter()
+                .filter_map(get_attribute_predicate)
+                .map(|predicate| {
+                    CfgPredicate::Any(vec![
+                        CfgPredicate::Not(Box::new(condition.clone())),
+                        predicate,
+                    ])
+                })
+                .collect();
+            match predicates.len() {
+                0 => None,
+                1 => predicates.into_iter().next(),
+                _ => Some(CfgPredicate::All(predicates)),
+            }
+        }
+        _ => None,
+    }
+}
+
+fn parse_predicate(meta_item: &MetaItem) -> Option<CfgPredicate> {
+    match meta_item {
+        MetaItem::Word(name) => Some(match name.as_str() {
+            "true" => CfgPredicate::All(vec![]),
+            "false" => CfgPredicate::Any(vec![]),
+            _ => CfgPredicate::Option {
+                name: name.clone(),
+                value: None,
+            },
+        }),
+        MetaItem::NameValue(name, value) => Some(CfgPredicate::Option {
+            name: name.clone(),
+            value: Some(value.clone()),
+        }),
+        MetaItem::List(name, arguments) => {
+            let predicates = arguments
+                .iter()
+                .map(parse_predicate)
+                .collect::<Option<Vec<_>>>()?;
+            match (name.as_str(), predicates.as_slice()) {
+                ("all", _) => Some(CfgPredicate::All(predicates)),
+                ("any", _) => Some(CfgPredicate::Any(predicates)),
+                ("not", [predicate]) => Some(CfgPredicate::Not(Box::new(predicate.clone()))),
+                _ => None,
+            }
+        }
+    }
+}
+
+fn parse_attribute_item(attribute_item: &Node, source_code: &str) -> Option<MetaItem> {
+    let mut cursor = attribute_item.walk();
+    let attribute = attribute_item
+        .children(&mut cursor)
+        .find(|n| n.kind() == "attribute")?;
+
+    let mut attribute_cursor = attribute.walk();
+    let name_node = attribute.children(&mut attribute_cursor).next()?;
+    if name_node.kind() != "identifier" {
+        return None;
+    }
+    let name = get_text(&name_node, source_code).to_string();
+
+    let arguments = attribute.child_by_field_name("arguments")?;
+    Some(MetaItem::List(
+        name,
+        parse_token_tree(&arguments, source_code)?,
+    ))
+}
+
+fn parse_token_tree(token_tree: &Node, source_code: &str) -> Option<Vec<MetaItem>> {
+    let mut items = Vec::new();
+    let mut current_item: Option<MetaItem> = None;
+    let mut is_awaiting_value = false;
+
+    let mut cursor = token_tree.walk();
+    for token in token_tree.children(&mut cursor) {
+        match (token.kind(), current_item.take()) {
+            ("(" | ")", item) => current_item = item,
+            ("," | ";", item) => {
+                items.push(item?);
+                is_awaiting_value = false;
+            }
+            ("identifier" | "boolean_literal", None) => {
+                current_item = Some(MetaItem::Word(get_text(&token, source_code).to_string()));
+            }
+            ("=", Some(MetaItem::Word(name))) => {
+                current_item = Some(MetaItem::Word(name));
+                is_awaiting_value = true;
+            }
+            ("string_literal" | "raw_string_literal", Some(MetaItem::Word(name)))
+                if is_awaiting_value =>
+            {
+                let value = get_string_content(&token, source_code);
+                current_item = Some(MetaItem::NameValue(name, value));
+                is_awaiting_value = false;
+            }
+            ("token_tree", Some(MetaItem::Word(name))) if !is_awaiting_value => {
+                let arguments = parse_token_tree(&token, source_code)?;
+                current_item = Some(MetaItem::List(name, arguments));
+            }
+            _ => return None,
+        }
+    }
+
+    if is_awaiting_value {
+        return None;
+    }
+    items.extend(current_item);
+    Some(items)
+}
+
+fn get_string_content(literal: &Node, source_code: &str) -> String {
+    let mut cursor = literal.walk();
+    let content = literal
+        .children(&mut cursor)
+        .find(|n| n.kind() == "string_content")
+        .map(|n| get_text(&n, source_code))
+        .unwrap_or_default();
+    content.to_string()
+}
+
+fn get_text<'a>(node: &Node, source_code: &'a str) -> &'a str {
+    &source_code[node.start_byte()..node.end_byte()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::parsing::test_helpers::make_tree, treesitter_test_helpers::find_child_node};
+
+    fn option(name: &str, value: Option<&str>) -> CfgPredicate {
+        CfgPredicate::Option {
+            name: name.to_string(),
+            value: value.map(str::to_string),
+        }
+    }
+
+    fn extract_function_predicate(source_code: &str) -> Option<CfgPredicate> {
+        let tree = make_tree(source_code);
+        let function_node = find_child_node(tree.root_node(), "function_item");
+        extract_cfg_predicate(&function_node, source_code)
+    }
+
+    mod extraction {
+        use super::*;
+
+        #[test]
+        fn no_attributes() {
+            let result = extract_function_predicate("fn f() {}");
+
+            assert_eq!(result, None);
+        }
+
+        #[test]
+        fn unrelated_attributes() {
+            let result = extract_function_predicate("#[inline]\n#[doc(hidden)]\nfn f() {}");
+
+            assert_eq!(result, None);
+        }
+
+        #[test]
+        fn option_without_value() {
+            let result = extract_function_predicate("#[cfg(unix)]\nfn f() {}");
+
+            assert_eq!(result, Some(option("unix", None)));
+        }
+
+        #[test]
+        fn option_with_value() {
+            let result = extract_function_predicate("#[cfg(feature = \"std\")]\nfn f() {}");
+
+            assert_eq!(result, Some(option("feature", Some("std"))));
+        }
+
+        #[test]
+        fn nested_predicates() {
+            let result = extract_function_predicate(
+                "#[cfg(all(unix, any(feature = \"a\", not(feature = \"b\"))))]\nfn f() {}",
+            );
+
+            assert_eq!(
+                result,
+                Some(CfgPredicate::All(vec![
+                    option("unix", None),
+                    CfgPredicate::Any(vec![
+                        option("feature", Some("a")),
+                        CfgPredicate::Not(Box::new(option("feature", Some("b")))),
+                    ]),
+                ]))
+            );
+        }
+
+        #[test]
+        fn boolean_literals() {
+            let result = extract_function_predicate("#[cfg(any(true, false))]\nfn f() {}");
+
+            assert_eq!(
+                result,
+                Some(CfgPredicate::Any(vec![
+                    CfgPredicate::All(vec![]),
+                    CfgPredicate::Any(vec![]),
+                ]))
+            );
+        }
+
+        #[test]
+        fn multiple_attributes() {
+            let result =
+                extract_function_predicate("#[cfg(unix)]\n#[inline]\n#[cfg(test)]\nfn f() {}");
+
+            assert_eq!(
+                result,
+                Some(CfgPredicate::All(vec![
+                    option("unix", None),
+                    option("test", None),
+                ]))
+            );
+        }
+
+        #[test]
+        fn cfg_attr_with_cfg() {
+            let result =
+                extract_function_predicate("#[cfg_attr(docsrs, inline, cfg(unix))]\nfn f() {}");
+
+            assert_eq!(
+                result,
+                Some(CfgPredicate::Any(vec![
+                    CfgPredicate::Not(Box::new(option("docsrs", None))),
+                    option("unix", None),
+                ]))
+            );
+        }
+
+        #[test]
+        fn cfg_attr_without_cfg() {
+            let result = extract_function_predicate(
+                "#[cfg_attr(docsrs, doc(cfg(feature = \"std\")))]\nfn f() {}",
+            );
+
+            assert_eq!(result, None);
+        }
+
+        #[test]
+        fn malformed_predicate() {
+            let result = extract_function_predicate("#[cfg(not(a, b))]\nfn f() {}");
+
+            assert_eq!(result, None);
+        }
+    }
+
+    mod evaluation {
+        use super::*;
+
+        fn make_options() -> CfgOptions {
+            CfgOptions {
+                features: HashSet::from(["std".to_string()]),
+                target_options: HashSet::from([
+                    ("unix".to_string(), None),
+                    ("target_os".to_string(), Some("linux".to_string())),
+                ]),
+            }
+        }
+
+        #[test]
+        fn enabled_feature() {
+            let predicate = option("feature", Some("std"));
+
+            assert!(predicate.evaluate(&make_options()));
+        }
+
+        #[test]
+        fn disabled_feature() {
+            let predicate = option("feature", Some("alloc"));
+
+            assert!(!predicate.evaluate(&make_options()));
+        }
+
+        #[test]
+        fn target_option_without_value() {
+            assert!(option("unix", None).evaluate(&make_options()));
+            assert!(!option("windows", None).evaluate(&make_options()));
+        }
+
+        #[test]
+        fn target_option_with_value() {
+            assert!(option("target_os", Some("linux")).evaluate(&make_options()));
+            assert!(!option("target_os", Some("macos")).evaluate(&make_options()));
+        }
+
+        #[test]
+        fn unknown_option() {
+            let predicate = option("docsrs", None);
+
+            assert!(!predicate.evaluate(&make_options()));
+        }
+
+        #[test]
+        fn all() {
+            let options = make_options();
+
+            assert!(CfgPredicate::All(vec![]).evaluate(&options));
+            assert!(
+                CfgPredicate::All(vec![option("unix", None), option("feature", Some("std"))])
+                    .evaluate(&options)
+            );
+            assert!(
+                !CfgPredicate::All(vec![option("unix", None), option("windows", None)])
+                    .evaluate(&options)
+            );
+        }
+
+        #[test]
+        fn any() {
+            let options = make_options();
+
+            assert!(!CfgPredicate::Any(vec![]).evaluate(&options));
+            assert!(
+                CfgPredicate::Any(vec![option("windows", None), option("unix", None)])
+                    .evaluate(&options)
+            );
+            assert!(
+                !CfgPredicate::Any(vec![option("windows", None), option("wasm", None)])
+                    .evaluate(&options)
+            );
+        }
+
+        #[test]
+        fn not() {
+            let options = make_options();
+
+            assert!(CfgPredicate::Not(Box::new(option("windows", None))).evaluate(&options));
+            assert!(!CfgPredicate::Not(Box::new(option("unix", None))).evaluate(&options));
+        }
+
+        #[test]
+        fn host_options() {
+            let options = CfgOptions::default();
+
+            assert!(option("target_os", Some(std::env::consts::OS)).evaluate(&options));
+            assert_eq!(option("unix", None).evaluate(&options), cfg!(unix),);
+        }
+    }
+
+    mod conjunction {
+        use super::*;
+
+        #[test]
+        fn neither_predicate() {
+            assert_eq!(CfgPredicate::conjoin(None, None), None);
+        }
+
+        #[test]
+        fn single_predicate() {
+            let predicate = option("unix", None);
+
+            assert_eq!(
+                CfgPredicate::conjoin(Some(&predicate), None),
+                Some(predicate.clone())
+            );
+            assert_eq!(
+                CfgPredicate::conjoin(None, Some(&predicate)),
+                Some(predicate)
+            );
+        }
+
+        #[test]
+        fn both_predicates() {
+            let first = CfgPredicate::All(vec![option("unix", None), option("test", None)]);
+            let second = option("feature", Some("std"));
+
+            let result = CfgPredicate::conjoin(Some(&first), Some(&second));
+
+            assert_eq!(
+                result,
+                Some(CfgPredicate::All(vec![
+                    option("unix", None),
+                    option("test", None),
+                    option("feature", Some("std")),
+                ]))
+            );
+        }
+    }
+
+    mod annotation {
+        use super::*;
+
+        #[test]
+        fn display() {
+            let predicate = CfgPredicate::All(vec![
+                option("unix", None),
+                CfgPredicate::Not(Box::new(option("target_os", Some("macos")))),
+            ]);
+
+            assert_eq!(
+                predicate.to_string(),
+                "all(unix, not(target_os = \"macos\"))"
+            );
+        }
+
+        #[test]
+        fn single_feature() {
+            let predicate = option("feature", Some("serde"));
+
+            assert_eq!(
+                predicate.get_annotation(),
+                "Available on crate feature `serde` only."
+            );
+        }
+
+        #[test]
+        fn all_features() {
+            let predicate = CfgPredicate::All(vec![
+                option("feature", Some("a")),
+                option("feature", Some("b")),
+                option("feature", Some("c")),
+            ]);
+
+            assert_eq!(
+                predicate.get_annotation(),
+                "Available on crate features `a`, `b` and `c` only."
+            );
+        }
+
+        #[test]
+        fn any_feature() {
+            let predicate = CfgPredicate::Any(vec![
+                option("feature", Some("a")),
+                option("feature", Some("b")),
+            ]);
+
+            assert_eq!(
+                predicate.get_annotation(),
+                "Available on crate features `a` or `b` only."
+            );
+        }
+
+        #[test]
+        fn other_predicate() {
+            let predicate =
+                CfgPredicate::All(vec![option("unix", None), option("feature", Some("std"))]);
+
+            assert_eq!(
+                predicate.get_annotation(),
+                "Available on `all(unix, feature = \"std\")` only."
+            );
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use super::helpers::{get_attribute_item_value_node, get_string_literal_value};
+use super::includes::read_included_string;
 use daipendency_extractor::ExtractionError;
+use std::path::Path;
 use tree_sitter::Node;
 
 #[derive(Debug, Clone, Copy)]
@@ -16,32 +19,62 @@ impl DocCommentMarker {
     }
 }
 
+/// Extract the outer doc comments preceding a node, including those in `#[doc = "..."]` attributes.
+///
+/// Files included with `#[doc = include_str!("...")]` are resolved relative to `directory`.
 pub fn extract_outer_doc_comments(
     node: &Node,
     source_code: &str,
+    directory: &Path,
 ) -> Result<Option<String>, ExtractionError> {
-    let Some(previous_sibling) = node.prev_sibling() else {
-        return Ok(None);
-    };
-
-    let previous_sibling = skip_preceding_attributes(previous_sibling);
+    let mut items = Vec::new();
 
-    if let Some(comment) = extract_preceding_block_comment(&previous_sibling, source_code)? {
-        return Ok(Some(comment));
+    let mut current = node.prev_sibling();
+    while let Some(sibling) = current {
+        match sibling.kind() {
+            "attribute_item" => {
+                if let Some(value) = get_doc_attribute_value(&sibling, source_code, directory)? {
+                    items.push(convert_doc_attribute_value(&value, DocCommentMarker::Outer));
+                }
+            }
+            "line_comment" if is_doc_comment(&sibling, DocCommentMarker::Outer) => {
+                items.push(get_node_text(&sibling, source_code)?.to_string());
+            }
+            "block_comment"
+                if items.is_empty() && is_doc_comment(&sibling, DocCommentMarker::Outer) =>
+            {
+                items.push(get_node_text(&sibling, source_code)?.to_string() + "\n");
+                break;
+            }
+            _ => break,
+        }
+        current = sibling.prev_sibling();
     }
 
-    extract_preceding_line_doc_comments(previous_sibling, source_code)
+    Ok(if items.is_empty() {
+        None
+    } else {
+        Some(items.into_iter().rev().collect())
+    })
 }
 
-fn skip_preceding_attributes(mut node: Node) -> Node {
-    while node.kind() == "attribute_item" {
-        if let Some(prev) = node.prev_sibling() {
-            node = prev;
-        } else {
-            break;
+/// Get the value of a `doc` attribute, which is either a string literal or the contents of a file
+/// included with `include_str!` relative to `directory`.
+fn get_doc_attribute_value(
+    attribute_item: &Node,
+    source_code: &str,
+    directory: &Path,
+) -> Result<Option<String>, ExtractionError> {
+    let Some(value) = get_attribute_item_value_node(attribute_item, "doc", source_code) else {
+        return Ok(None);
+    };
+    match value.kind() {
+        "string_literal" | "raw_string_literal" => {
+            Ok(Some(get_string_literal_value(&value, source_code)?))
         }
+        "macro_invocation" => read_included_string(&value, source_code, directory),
+        _ => Ok(None),
     }
-    node
 }
 
 fn is_doc_comment(node: &Node, marker: DocCommentMarker) -> bool {
@@ -51,67 +84,52 @@ fn is_doc_comment(node: &Node, marker: DocCommentMarker) -> bool {
         && children.iter().any(|child| child.kind() == "doc_comment")
 }
 
-fn extract_preceding_block_comment(
-    node: &Node,
-    source_code: &str,
-) -> Result<Option<String>, ExtractionError> {
-    if node.kind() == "block_comment" && is_doc_comment(node, DocCommentMarker::Outer) {
-        let text = node
-            .utf8_text(source_code.as_bytes())
-            .map_err(|e| ExtractionError::Malformed(e.to_string()))?;
-        return Ok(Some(text.to_string() + "\n"));
+/// Convert the value of a `doc` attribute to the equivalent line doc comments (e.g. `/// Foo` for
+/// `#[doc = " Foo"]`).
+fn convert_doc_attribute_value(value: &str, marker: DocCommentMarker) -> String {
+    let prefix = match marker {
+        DocCommentMarker::Outer => "///",
+        DocCommentMarker::Inner => "//!",
+    };
+    if value.is_empty() {
+        return format!("{}\n", prefix);
     }
-    Ok(None)
+    value
+        .lines()
+        .map(|line| format!("{}{}\n", prefix, line))
+        .collect()
 }
 
-fn extract_preceding_line_doc_comments(
-    mut node: Node,
-    source_code: &str,
-) -> Result<Option<String>, ExtractionError> {
-    let mut items = Vec::new();
-
-    while node.kind() == "line_comment" {
-        if is_doc_comment(&node, DocCommentMarker::Outer) {
-            let comment_text = node
-                .utf8_text(source_code.as_bytes())
-                .map_err(|e| ExtractionError::Malformed(e.to_string()))?;
-            items.push(comment_text.to_string());
-        } else {
-            break;
-        }
-
-        if let Some(prev) = node.prev_sibling() {
-            node = prev;
-        } else {
-            break;
-        }
-    }
-
-    Ok(if items.is_empty() {
-        None
-    } else {
-        Some(items.into_iter().rev().collect())
-    })
+fn get_node_text<'a>(node: &Node, source_code: &'a str) -> Result<&'a str, ExtractionError> {
+    node.utf8_text(source_code.as_bytes())
+        .map_err(|e| ExtractionError::Malformed(e.to_string()))
 }
 
+/// Extract the inner doc comments of a module, including those in `#![doc = "..."]` attributes.
+///
+/// Files included with `#![doc = include_str!("...")]` are resolved relative to `directory`.
 pub fn extract_inner_doc_comments(
     node: &Node,
     source_code: &str,
+    directory: &Path,
 ) -> Result<Option<String>, ExtractionError> {
     let mut doc_comment = String::new();
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        if child.kind() == "line_comment" {
-            if is_doc_comment(&child, DocCommentMarker::Inner) {
-                let comment_text = child
-                    .utf8_text(source_code.as_bytes())
-                    .map_err(|e| ExtractionError::Malformed(e.to_string()))?;
-                doc_comment.push_str(comment_text);
-            } else {
-                break;
+        match child.kind() {
+            "line_comment" if is_doc_comment(&child, DocCommentMarker::Inner) => {
+                doc_comment.push_str(get_node_text(&child, source_code)?);
             }
-        } else if !is_block_delimiter(&child) {
-            break;
+            "inner_attribute_item" => {
+                if let Some(value) = get_doc_attribute_value(&child, source_code, directory)? {
+                    doc_comment.push_str(&convert_doc_attribute_value(
+                        &value,
+                        DocCommentMarker::Inner,
+                    ));
+                }
+            }
+            _ if is_block_delimiter(&child) => {}
+            _ => break,
         }
     }
     Ok(if doc_comment.is_empty() {
@@ -121,6 +139,20 @@ pub fn extract_inner_doc_comments(
     })
 }
 
+/// Get the text of inner line doc comments, without their markers (e.g. `Foo` for `//! Foo`).
+pub fn strip_inner_doc_comment_markers(doc_comment: &str) -> String {
+    doc_comment
+        .lines()
+        .map(|line| {
+            let text = line.trim_start().trim_start_matches("//!");
+            text.strip_prefix(' ').unwrap_or(text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
 fn is_block_delimiter(node: &Node) -> bool {
     matches!(node.kind(), "{" | "}")
 }
@@ -130,6 +162,7 @@ mod tests {
     use super::*;
     use crate::api::parsing::test_helpers::make_tree;
     use crate::treesitter_test_helpers::{find_child_node, find_child_nodes};
+    use daipendency_testing::tempdir::TempDir;
 
     mod inner_doc_comments {
         use super::*;
@@ -141,7 +174,8 @@ pub struct Test {}
 "#;
             let tree = make_tree(source_code);
 
-            let result = extract_inner_doc_comments(&tree.root_node(), source_code).unwrap();
+            let result =
+                extract_inner_doc_comments(&tree.root_node(), source_code, Path::new("")).unwrap();
 
             assert!(result.is_none());
         }
@@ -154,7 +188,8 @@ pub struct Test {}
 "#;
             let tree = make_tree(source_code);
 
-            let result = extract_inner_doc_comments(&tree.root_node(), source_code).unwrap();
+            let result =
+                extract_inner_doc_comments(&tree.root_node(), source_code, Path::new("")).unwrap();
 
             assert_eq!(
                 result,
@@ -172,7 +207,8 @@ pub struct Test {}
 "#;
             let tree = make_tree(source_code);
 
-            let result = extract_inner_doc_comments(&tree.root_node(), source_code).unwrap();
+            let result =
+                extract_inner_doc_comments(&tree.root_node(), source_code, Path::new("")).unwrap();
 
             assert_eq!(
                 result,
@@ -191,10 +227,117 @@ pub struct Test {}
 "#;
             let tree = make_tree(source_code);
 
-            let result = extract_inner_doc_comments(&tree.root_node(), source_code).unwrap();
+            let result =
+                extract_inner_doc_comments(&tree.root_node(), source_code, Path::new("")).unwrap();
 
             assert!(result.is_none());
         }
+
+        #[test]
+        fn doc_attribute() {
+            let source_code = r#"
+#![doc = " This is a file-level doc comment"]
+#![doc = " It spans multiple lines"]
+pub struct Test {}
+"#;
+            let tree = make_tree(source_code);
+
+            let result =
+                extract_inner_doc_comments(&tree.root_node(), source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                Some(
+                    "//! This is a file-level doc comment\n//! It spans multiple lines\n"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn multiline_doc_attribute() {
+            let source_code = "#![doc = r\"# Title\n\nBody\"]\npub struct Test {}";
+            let tree = make_tree(source_code);
+
+            let result =
+                extract_inner_doc_comments(&tree.root_node(), source_code, Path::new("")).unwrap();
+
+            assert_eq!(result, Some("//!# Title\n//!\n//!Body\n".to_string()));
+        }
+
+        #[test]
+        fn included_doc_attribute() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file("README.md", "# Title\n\nBody\n")
+                .unwrap();
+            let lib_path = temp_dir.create_file("src/lib.rs", "").unwrap();
+            let source_code = "#![doc = include_str!(\"../README.md\")]\npub struct Test {}";
+            let tree = make_tree(source_code);
+
+            let result = extract_inner_doc_comments(
+                &tree.root_node(),
+                source_code,
+                lib_path.parent().unwrap(),
+            )
+            .unwrap();
+
+            assert_eq!(result, Some("//!# Title\n//!\n//!Body\n".to_string()));
+        }
+
+        #[test]
+        fn missing_included_doc_attribute() {
+            let temp_dir = TempDir::new();
+            let lib_path = temp_dir.create_file("src/lib.rs", "").unwrap();
+            let source_code = "#![doc = include_str!(\"../README.md\")]\npub struct Test {}";
+            let tree = make_tree(source_code);
+
+            let result = extract_inner_doc_comments(
+                &tree.root_node(),
+                source_code,
+                lib_path.parent().unwrap(),
+            )
+            .unwrap();
+
+            assert!(result.is_none());
+        }
+
+        #[test]
+        fn doc_comment_with_other_inner_attributes() {
+            let source_code = r#"
+#![allow(unused)]
+//! The doc comment
+#![doc = " More docs"]
+pub struct Test {}
+"#;
+            let tree = make_tree(source_code);
+
+            let result =
+                extract_inner_doc_comments(&tree.root_node(), source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                Some("//! The doc comment\n//! More docs\n".to_string())
+            );
+        }
+    }
+
+    mod marker_stripping {
+        use super::*;
+
+        #[test]
+        fn single_line() {
+            let result = strip_inner_doc_comment_markers("//! Foo\n");
+
+            assert_eq!(result, "Foo");
+        }
+
+        #[test]
+        fn multiple_lines() {
+            let result = strip_inner_doc_comment_markers("//! # Foo\n//!\n//!   Indented\n");
+
+            assert_eq!(result, "# Foo\n\n  Indented");
+        }
     }
 
     mod outer_doc_comments {
@@ -208,7 +351,7 @@ pub struct Test {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "struct_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert!(result.is_none());
         }
@@ -222,7 +365,7 @@ pub struct Test {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "struct_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, Some("/// A documented item\n".to_string()));
         }
@@ -237,7 +380,7 @@ pub struct Test {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "struct_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert_eq!(
                 result,
@@ -255,7 +398,7 @@ pub struct Test {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "struct_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, Some("/// Outer doc\n".to_string()));
         }
@@ -270,7 +413,7 @@ pub struct Test {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "struct_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, Some("/// Doc comment\n".to_string()));
         }
@@ -286,7 +429,7 @@ pub struct Test {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "struct_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert_eq!(
                 result,
@@ -305,7 +448,7 @@ pub struct Test {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "struct_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, Some("/// This is the struct's doc\n".to_string()));
         }
@@ -323,7 +466,7 @@ pub struct SecondStruct {}
             let nodes = find_child_nodes(tree.root_node(), "struct_item");
             let node = &nodes[1];
 
-            let result = extract_outer_doc_comments(node, source_code).unwrap();
+            let result = extract_outer_doc_comments(node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, Some("/// Second struct's doc\n".to_string()));
         }
@@ -340,7 +483,7 @@ pub struct Test {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "struct_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert_eq!(
                 result,
@@ -358,7 +501,7 @@ pub struct Foo {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "struct_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert_eq!(
                 result,
@@ -376,7 +519,7 @@ pub enum Foo {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "enum_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, Some("/// The doc comment\n".to_string()));
         }
@@ -392,7 +535,7 @@ pub enum Foo {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "enum_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, Some("/// The doc comment\n".to_string()));
         }
@@ -406,11 +549,80 @@ pub enum Foo {}
             let tree = make_tree(source_code);
             let node = find_child_node(tree.root_node(), "enum_item");
 
-            let result = extract_outer_doc_comments(&node, source_code).unwrap();
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
 
             assert!(result.is_none());
         }
 
+        #[test]
+        fn doc_attribute() {
+            let source_code = r#"
+#[doc = " The doc comment"]
+pub enum Foo {}
+"#;
+            let tree = make_tree(source_code);
+            let node = find_child_node(tree.root_node(), "enum_item");
+
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(result, Some("/// The doc comment\n".to_string()));
+        }
+
+        #[test]
+        fn included_doc_attribute() {
+            let temp_dir = TempDir::new();
+            let docs_path = temp_dir.create_file("docs/foo.md", "Foo").unwrap();
+            let source_code = r#"
+/// Summary
+#[doc = include_str!("foo.md")]
+pub enum Foo {}
+"#;
+            let tree = make_tree(source_code);
+            let node = find_child_node(tree.root_node(), "enum_item");
+
+            let result =
+                extract_outer_doc_comments(&node, source_code, docs_path.parent().unwrap())
+                    .unwrap();
+
+            assert_eq!(result, Some("/// Summary\n///Foo\n".to_string()));
+        }
+
+        #[test]
+        fn doc_attribute_mixed_with_doc_comments() {
+            let source_code = r#"
+/// First line
+#[doc = " Second line"]
+#[derive(Debug)]
+/// Third line
+pub enum Foo {}
+"#;
+            let tree = make_tree(source_code);
+            let node = find_child_node(tree.root_node(), "enum_item");
+
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                Some("/// First line\n/// Second line\n/// Third line\n".to_string())
+            );
+        }
+
+        #[test]
+        fn empty_doc_attribute() {
+            let source_code = r#"
+/// Title
+#[doc = ""]
+/// Body
+pub enum Foo {}
+"#;
+            let tree = make_tree(source_code);
+            let node = find_child_node(tree.root_node(), "enum_item");
+
+            let result = extract_outer_doc_comments(&node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(result, Some("/// Title\n///\n/// Body\n".to_string()));
+        }
+
         #[test]
         fn trait_method_doc_comments() {
             let source_code = r#"
@@ -426,7 +638,8 @@ pub trait TestTrait {
             let decl_list = find_child_node(trait_node, "declaration_list");
             let method_node = find_child_node(decl_list, "function_item");
 
-            let result = extract_outer_doc_comments(&method_node, source_code).unwrap();
+            let result =
+                extract_outer_doc_comments(&method_node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, Some("/// A documented method\n".to_string()));
         }
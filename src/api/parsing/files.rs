@@ -1,13 +1,105 @@
+use super::cfg::CfgPredicate;
 use daipendency_extractor::Symbol;
+use std::path::PathBuf;
 
+/// A parsed Rust source file
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct RustFile {
+    /// The path to the file, or an empty path if it wasn't read from the file system
+    pub path: PathBuf,
+    /// The span of the whole file
+    pub span: SourceSpan,
+    /// The inner doc comments of the file (e.g. `//! My crate`), if any
     pub doc_comment: Option<String>,
+    /// The symbols of the file that are relevant to its public API
     pub symbols: Vec<RustSymbol>,
+    /// The syntax errors the parser recovered from, if any
+    pub syntax_errors: Vec<SyntaxError>,
+}
+
+/// A syntax error in a source file (e.g. a missing `;`)
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SyntaxError {
+    /// The description of the error (e.g. `Missing ;`)
+    pub message: String,
+    /// The span of the error
+    pub span: SourceSpan,
+}
+
+/// Span of an item in its source file, with 1-based lines and columns (the latter counted in bytes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct SourceSpan {
+    /// The line where the item starts
+    pub start_line: usize,
+    /// The column where the item starts
+    pub start_column: usize,
+    /// The line where the item ends
+    pub end_line: usize,
+    /// The column where the item ends, exclusive
+    pub end_column: usize,
+}
+
+/// Visibility of an item, as declared by its visibility modifier
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Visibility {
+    /// Visible to other crates (e.g. `pub fn foo()`)
+    Public,
+    /// Visible to part of the crate only (e.g. `pub(crate) fn foo()`, `pub(super) fn foo()`, `pub(in crate::bar) fn foo()`)
+    Restricted,
+    /// Visible to the current module only (e.g. `fn foo()`, `pub(self) fn foo()`)
+    Private,
+}
+
+/// Kind of item a symbol stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SymbolKind {
+    /// A function (e.g. `pub fn foo() {}`)
+    Function,
+    /// A struct (e.g. `pub struct Foo;`)
+    Struct,
+    /// A union (e.g. `pub union Foo { ... }`)
+    Union,
+    /// An enum (e.g. `pub enum Foo { ... }`)
+    Enum,
+    /// A trait (e.g. `pub trait Foo { ... }`)
+    Trait,
+    /// A type alias (e.g. `pub type Foo = Bar;`)
+    TypeAlias,
+    /// A constant (e.g. `pub const FOO: u8 = 1;`)
+    Const,
+    /// A static (e.g. `pub static FOO: u8 = 1;`)
+    Static,
+    /// A macro (e.g. `macro_rules! foo { ... }`)
+    Macro,
+    /// A module or crate reexported as a whole (e.g. `pub use serde_json;`)
+    Module,
+    /// A reexport whose item couldn't be resolved (e.g. `pub use serde_json::Value;`)
+    Reexport,
+    /// A glob reexport whose items couldn't be resolved (e.g. `pub use serde_json::prelude::*;`),
+    /// named after the glob as written (e.g. `serde_json::prelude::*`), with the path to the globbed
+    /// module as its canonical path
+    UnresolvedGlob,
+}
+
+/// The path to a file included with `include!`
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum IncludePath {
+    /// A path relative to the directory of the including file (e.g. `include!("bindings.rs")`)
+    Relative(String),
+    /// A path appended to the output directory of the build script (e.g.
+    /// `include!(concat!(env!("OUT_DIR"), "/bindings.rs"))`)
+    OutDir(String),
 }
 
 /// Type of symbol import in a Rust module
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum ImportType {
     /// Direct import (e.g. `use submodule::Foo`)
     Simple,
@@ -19,27 +111,171 @@ pub enum ImportType {
 
 /// The various symbols we care about for the purposes of extracting the public API
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum RustSymbol {
     /// A public symbol (e.g. `pub struct Foo { ... }`)
-    Symbol { symbol: Symbol },
+    Symbol {
+        /// The symbol, with its source code
+        symbol: Symbol,
+        /// The kind of item the symbol stands for
+        kind: SymbolKind,
+        /// The generic parameters of the symbol, if any (e.g. `<T: Clone>`)
+        generics: Option<String>,
+        /// The predicate that must hold for the symbol to be compiled, if any
+        cfg: Option<CfgPredicate>,
+        /// Whether the symbol is hidden from the documentation (i.e. `#[doc(hidden)]`)
+        is_doc_hidden: bool,
+        /// The span of the symbol
+        span: SourceSpan,
+    },
+    /// A `macro_rules!` macro exported at the crate root (i.e. `#[macro_export]`), regardless of
+    /// the module defining it
+    ExportedMacro {
+        /// The macro, whose source code stops before its rules (e.g. `macro_rules! foo`)
+        symbol: Symbol,
+        /// The matchers of the macro's rules (e.g. `($x:expr)`)
+        matchers: Vec<String>,
+        /// The predicate that must hold for the macro to be compiled, if any
+        cfg: Option<CfgPredicate>,
+        /// Whether the macro is hidden from the documentation (i.e. `#[doc(hidden)]`)
+        is_doc_hidden: bool,
+        /// The span of the macro
+        span: SourceSpan,
+    },
     /// A module or symbol reexport (e.g. `pub use serde_json;`, `pub use serde_json::Value;`)
     Reexport {
+        /// The path to the reexported item (e.g. `serde_json::Value`)
         source_path: String,
+        /// How the item is reexported (e.g. with an alias)
         import_type: ImportType,
+        /// The predicate that must hold for the reexport to be compiled, if any
+        cfg: Option<CfgPredicate>,
+        /// Whether the reexport is hidden from the documentation (i.e. `#[doc(hidden)]`)
+        is_doc_hidden: bool,
+        /// The span of the whole `use` declaration
+        span: SourceSpan,
+    },
+    /// A private import (e.g. `use crate::foo::Bar;`), which brings an item into the scope of the
+    /// module without reexporting it
+    Import {
+        /// The path to the imported item (e.g. `crate::foo::Bar`)
+        source_path: String,
+        /// How the item is imported (e.g. with an alias)
+        import_type: ImportType,
+        /// The predicate that must hold for the import to be compiled, if any
+        cfg: Option<CfgPredicate>,
     },
     /// A module block (e.g. `mod foo { ... }`)
     ModuleBlock {
+        /// The name of the module (e.g. `foo`)
         name: String,
-        is_public: bool,
+        /// The visibility of the module
+        visibility: Visibility,
+        /// The symbols of the module
         content: Vec<RustSymbol>,
+        /// The inner doc comments of the module (e.g. `//! My module`), if any
         doc_comment: Option<String>,
+        /// The predicate that must hold for the module to be compiled, if any
+        cfg: Option<CfgPredicate>,
+        /// Whether the module is hidden from the documentation (i.e. `#[doc(hidden)]`)
+        is_doc_hidden: bool,
+        /// The span of the whole block
+        span: SourceSpan,
     },
     /// A module import (e.g. `mod foo;`)
-    ModuleImport { name: String, is_reexported: bool },
+    ModuleImport {
+        /// The name of the module (e.g. `foo`)
+        name: String,
+        /// The visibility of the module
+        visibility: Visibility,
+        /// The value of the `#[path = "..."]` attribute, if any
+        path: Option<String>,
+        /// The predicate that must hold for the module to be compiled, if any
+        cfg: Option<CfgPredicate>,
+        /// Whether the module is hidden from the documentation (i.e. `#[doc(hidden)]`)
+        is_doc_hidden: bool,
+        /// The span of the declaration
+        span: SourceSpan,
+    },
+    /// An external crate declaration (e.g. `extern crate foo;`, `pub extern crate foo as bar;`)
+    ExternCrate {
+        /// The name of the crate (e.g. `foo`)
+        name: String,
+        /// The name the crate is bound to instead, if any (e.g. `bar`)
+        alias: Option<String>,
+        /// The visibility of the declaration
+        visibility: Visibility,
+        /// The predicate that must hold for the declaration to be compiled, if any
+        cfg: Option<CfgPredicate>,
+        /// Whether the declaration is hidden from the documentation (i.e. `#[doc(hidden)]`)
+        is_doc_hidden: bool,
+        /// The span of the declaration
+        span: SourceSpan,
+    },
+    /// The variants of a public enum (e.g. `Red` and `Green` in `pub enum Colour { Red, Green }`),
+    /// which glob imports of the enum (e.g. `pub use Colour::*;`) bring into scope
+    EnumVariants {
+        /// The name of the enum (e.g. `Colour`)
+        enum_name: String,
+        /// The names of the variants (e.g. `["Red", "Green"]`)
+        variants: Vec<String>,
+        /// The predicate that must hold for the enum to be compiled, if any
+        cfg: Option<CfgPredicate>,
+    },
+    /// An implementation block (e.g. `impl Foo { ... }`, `impl Display for Foo { ... }`)
+    ImplBlock {
+        /// The path to the type as written in the block (e.g. `Foo`, `crate::foo::Foo`)
+        type_path: String,
+        /// The block itself
+        source_code: String,
+        /// The predicate that must hold for the block to be compiled, if any
+        cfg: Option<CfgPredicate>,
+    },
+    /// An item-level `include!` invocation (e.g. `include!("bindings.rs");`)
+    SourceInclude {
+        /// The path to the included file, or `None` if it isn't computed in a supported way
+        path: Option<IncludePath>,
+        /// The invocation itself (e.g. `include!("bindings.rs");`)
+        source_code: String,
+        /// The predicate that must hold for the invocation to be compiled, if any
+        cfg: Option<CfgPredicate>,
+        /// The span of the invocation
+        span: SourceSpan,
+    },
+    /// The symbols of a file included with `include!`, spliced in place of the invocation
+    IncludedFile {
+        /// The canonical path to the included file, against which the spans of its symbols are
+        /// relative
+        path: PathBuf,
+        /// The symbols of the included file
+        symbols: Vec<RustSymbol>,
+        /// The predicate that must hold for the invocation to be compiled, if any
+        cfg: Option<CfgPredicate>,
+    },
+}
+
+impl RustSymbol {
+    /// The predicate that must hold for the symbol to be compiled, if any (e.g. `#[cfg(unix)]`)
+    pub fn cfg(&self) -> Option<&CfgPredicate> {
+        match self {
+            RustSymbol::Symbol { cfg, .. }
+            | RustSymbol::ExportedMacro { cfg, .. }
+            | RustSymbol::Reexport { cfg, .. }
+            | RustSymbol::Import { cfg, .. }
+            | RustSymbol::ModuleBlock { cfg, .. }
+            | RustSymbol::ModuleImport { cfg, .. }
+            | RustSymbol::ExternCrate { cfg, .. }
+            | RustSymbol::EnumVariants { cfg, .. }
+            | RustSymbol::ImplBlock { cfg, .. }
+            | RustSymbol::SourceInclude { cfg, .. }
+            | RustSymbol::IncludedFile { cfg, .. } => cfg.as_ref(),
+        }
+    }
 }
 
 #[cfg(test)]
 impl RustFile {
+    /// Get the symbols of the module block at `path` (e.g. `outer::inner`), if any.
     pub fn get_module<'a>(&'a self, path: &str) -> Option<&'a [RustSymbol]> {
         let parts: Vec<&str> = path.split("::").collect();
         let mut current_symbols = &self.symbols;
@@ -65,6 +301,7 @@ impl RustFile {
         Some(current_symbols)
     }
 
+    /// Get the symbol at `path` (e.g. `outer::Foo`), if any.
     pub fn get_symbol<'a>(&'a self, path: &str) -> Option<&'a RustSymbol> {
         let parts: Vec<&str> = path.split("::").collect();
         if parts.is_empty() {
@@ -84,12 +321,22 @@ impl RustFile {
         };
 
         symbols.iter().find(|s| match s {
-            RustSymbol::Symbol { symbol } => symbol.name == symbol_name,
+            RustSymbol::Symbol { symbol, .. } | RustSymbol::ExportedMacro { symbol, .. } => {
+                symbol.name == symbol_name
+            }
             RustSymbol::ModuleBlock { name, .. } => name == symbol_name,
             RustSymbol::ModuleImport { name, .. } => name == symbol_name,
             RustSymbol::Reexport { source_path, .. } => {
                 source_path.split("::").last().unwrap() == symbol_name
             }
+            RustSymbol::ExternCrate { name, alias, .. } => {
+                alias.as_deref().unwrap_or(name) == symbol_name
+            }
+            RustSymbol::Import { .. }
+            | RustSymbol::EnumVariants { .. }
+            | RustSymbol::ImplBlock { .. }
+            | RustSymbol::SourceInclude { .. }
+            | RustSymbol::IncludedFile { .. } => false,
         })
     }
 }
@@ -0,0 +1,143 @@
+use super::cfg::{extract_cfg_predicate, CfgPredicate};
+use super::files::RustSymbol;
+use super::helpers::{
+    extract_name, get_declaration_list, get_generics, get_source_span, is_doc_hidden, is_public,
+};
+use super::symbols::{
+    get_doc_comments_and_attributes, get_symbol_kind, get_symbol_source_code, push_indented_lines,
+};
+use daipendency_extractor::{ExtractionError, Symbol};
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Extract the public functions and statics declared in an extern block (e.g. `extern "C" { ... }`).
+///
+/// Each item is wrapped in its own copy of the block, so that its ABI is retained.
+pub fn extract_foreign_symbols(
+    foreign_mod_node: &Node,
+    source_code: &str,
+    directory: &Path,
+) -> Result<Vec<RustSymbol>, ExtractionError> {
+    let Some(declaration_list) = get_declaration_list(*foreign_mod_node) else {
+        return Ok(Vec::new());
+    };
+
+    let block_header = format!(
+        "{}{}",
+        get_doc_comments_and_attributes(*foreign_mod_node, source_code, directory)?,
+        source_code[foreign_mod_node.start_byte()..declaration_list.start_byte()].trim_end()
+    );
+    let block_cfg = extract_cfg_predicate(foreign_mod_node, source_code);
+    let is_block_doc_hidden = is_doc_hidden(foreign_mod_node, source_code)?;
+
+    let mut symbols = Vec::new();
+    let mut cursor = declaration_list.walk();
+    for item in declaration_list.children(&mut cursor) {
+        if !matches!(item.kind(), "function_signature_item" | "static_item")
+            || !is_public(&item, source_code)
+        {
+            continue;
+        }
+
+        let mut item_source_code = format!("{} {{\n", block_header);
+        push_indented_lines(
+            &mut item_source_code,
+            &get_symbol_source_code(item, source_code, directory)?,
+        );
+        item_source_code.push('}');
+
+        symbols.push(RustSymbol::Symbol {
+            symbol: Symbol {
+                name: extract_name(&item, source_code)?,
+                source_code: item_source_code,
+            },
+            kind: get_symbol_kind(item.kind()),
+            generics: get_generics(&item, source_code),
+            cfg: CfgPredicate::conjoin(
+                block_cfg.as_ref(),
+                extract_cfg_predicate(&item, source_code).as_ref(),
+            ),
+            is_doc_hidden: is_block_doc_hidden || is_doc_hidden(&item, source_code)?,
+            span: get_source_span(&item),
+        });
+    }
+    Ok(symbols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::parsing::test_helpers::make_tree;
+    use crate::treesitter_test_helpers::find_child_node;
+    use assertables::assert_matches;
+
+    fn extract(source_code: &str) -> Vec<RustSymbol> {
+        let tree = make_tree(source_code);
+        let node = find_child_node(tree.root_node(), "foreign_mod_item");
+        extract_foreign_symbols(&node, source_code, Path::new("")).unwrap()
+    }
+
+    #[test]
+    fn function() {
+        let source_code = r#"extern "C" {
+    /// Frees the buffer.
+    pub fn free_buffer(buffer: *mut u8);
+}"#;
+
+        let symbols = extract(source_code);
+
+        assert_eq!(symbols.len(), 1);
+        assert_matches!(
+            &symbols[0],
+            RustSymbol::Symbol { symbol, .. } if symbol.name == "free_buffer"
+                && symbol.source_code == "extern \"C\" {\n    /// Frees the buffer.\n    pub fn free_buffer(buffer: *mut u8);\n}"
+        );
+    }
+
+    #[test]
+    fn static_item() {
+        let source_code = r#"extern "C" {
+    pub static mut errno: i32;
+}"#;
+
+        let symbols = extract(source_code);
+
+        assert_eq!(symbols.len(), 1);
+        assert_matches!(
+            &symbols[0],
+            RustSymbol::Symbol { symbol, .. } if symbol.name == "errno"
+                && symbol.source_code == "extern \"C\" {\n    pub static mut errno: i32;\n}"
+        );
+    }
+
+    #[test]
+    fn private_items() {
+        let source_code = r#"extern "C" {
+    fn private_function();
+    static PRIVATE_STATIC: i32;
+}"#;
+
+        let symbols = extract(source_code);
+
+        assert!(symbols.is_empty());
+    }
+
+    #[test]
+    fn block_attributes() {
+        let source_code = r#"#[link(name = "foo")]
+#[cfg(unix)]
+extern "C" {
+    #[doc(hidden)]
+    pub fn foo();
+}"#;
+
+        let symbols = extract(source_code);
+
+        assert_eq!(symbols.len(), 1);
+        assert_matches!(
+            &symbols[0],
+            RustSymbol::Symbol { symbol, cfg: Some(_), is_doc_hidden: true, .. }
+                if symbol.source_code == "#[link(name = \"foo\")]\n#[cfg(unix)]\nextern \"C\" {\n    #[doc(hidden)]\n    pub fn foo();\n}"
+        );
+    }
+}
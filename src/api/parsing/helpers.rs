@@ -1,12 +1,31 @@
+use super::files::{SourceSpan, Visibility};
 use daipendency_extractor::ExtractionError;
+use std::ops::Range;
 use tree_sitter::Node;
 
-pub fn is_public(node: &Node) -> bool {
+pub fn get_visibility(node: &Node, source_code: &str) -> Visibility {
     let mut cursor = node.walk();
     let children: Vec<_> = node.children(&mut cursor).collect();
-    children
+    match children
         .iter()
-        .any(|child| child.kind() == "visibility_modifier")
+        .find(|child| child.kind() == "visibility_modifier")
+    {
+        Some(modifier) => get_modifier_visibility(modifier, source_code),
+        None => Visibility::Private,
+    }
+}
+
+pub fn get_modifier_visibility(modifier: &Node, source_code: &str) -> Visibility {
+    let modifier_text = remove_whitespace(&source_code[modifier.start_byte()..modifier.end_byte()]);
+    match modifier_text.as_str() {
+        "pub" => Visibility::Public,
+        "pub(self)" => Visibility::Private,
+        _ => Visibility::Restricted,
+    }
+}
+
+pub fn is_public(node: &Node, source_code: &str) -> bool {
+    get_visibility(node, source_code) == Visibility::Public
 }
 
 pub fn get_declaration_list(node: Node) -> Option<Node> {
@@ -38,6 +57,182 @@ pub fn extract_attributes(node: &Node, source_code: &str) -> Result<Vec<String>,
     Ok(items)
 }
 
+/// Get the value of a name-value attribute preceding a node (e.g. `foo.rs` in `#[path = "foo.rs"]`).
+pub fn get_attribute_value(
+    node: &Node,
+    attribute_name: &str,
+    source_code: &str,
+) -> Result<Option<String>, ExtractionError> {
+    let mut current = node.prev_sibling();
+    while let Some(sibling) = current {
+        if sibling.kind() != "attribute_item" {
+            break;
+        }
+        if let Some(value) = get_attribute_item_value(&sibling, attribute_name, source_code)? {
+            return Ok(Some(value));
+        }
+        current = sibling.prev_sibling();
+    }
+    Ok(None)
+}
+
+/// Get the value of a name-value attribute (e.g. `foo.rs` in `#[path = "foo.rs"]`).
+pub fn get_attribute_item_value(
+    attribute_item: &Node,
+    attribute_name: &str,
+    source_code: &str,
+) -> Result<Option<String>, ExtractionError> {
+    let Some(value) = get_attribute_item_value_node(attribute_item, attribute_name, source_code)
+    else {
+        return Ok(None);
+    };
+    if !matches!(value.kind(), "string_literal" | "raw_string_literal") {
+        return Ok(None);
+    }
+    Ok(Some(get_string_literal_value(&value, source_code)?))
+}
+
+/// Get the expression assigned to a name-value attribute (e.g. `include_str!("README.md")` in
+/// `#[doc = include_str!("README.md")]`).
+pub fn get_attribute_item_value_node<'a>(
+    attribute_item: &Node<'a>,
+    attribute_name: &str,
+    source_code: &str,
+) -> Option<Node<'a>> {
+    let mut cursor = attribute_item.walk();
+    let attribute = attribute_item
+        .children(&mut cursor)
+        .find(|n| n.kind() == "attribute")?;
+
+    let mut attribute_cursor = attribute.walk();
+    let is_named_attribute = attribute
+        .children(&mut attribute_cursor)
+        .next()
+        .is_some_and(|n| {
+            n.kind() == "identifier" && &source_code[n.start_byte()..n.end_byte()] == attribute_name
+        });
+    if !is_named_attribute {
+        return None;
+    }
+
+    attribute.child_by_field_name("value")
+}
+
+/// Get the value of a (possibly raw) string literal, with its escape sequences resolved.
+pub fn get_string_literal_value(
+    literal: &Node,
+    source_code: &str,
+) -> Result<String, ExtractionError> {
+    let mut value = String::new();
+    let mut is_line_continuation = false;
+    let mut cursor = literal.walk();
+    for child in literal.children(&mut cursor) {
+        let text = child
+            .utf8_text(source_code.as_bytes())
+            .map_err(|e| ExtractionError::Malformed(e.to_string()))?;
+        match child.kind() {
+            "string_content" if is_line_continuation => value.push_str(text.trim_start()),
+            "string_content" => value.push_str(text),
+            "escape_sequence" => {
+                is_line_continuation = text.starts_with("\\\n") || text.starts_with("\\\r\n");
+                if !is_line_continuation {
+                    value.push_str(&unescape(text));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        is_line_continuation = false;
+    }
+    Ok(value)
+}
+
+fn unescape(escape_sequence: &str) -> String {
+    let escaped = &escape_sequence[1..];
+    let character = match escaped {
+        "n" => Some('\n'),
+        "r" => Some('\r'),
+        "t" => Some('\t'),
+        "0" => Some('\0'),
+        "\\" => Some('\\'),
+        "\"" => Some('"'),
+        "'" => Some('\''),
+        _ => {
+            if let Some(code) = escaped.strip_prefix('x') {
+                u32::from_str_radix(code, 16).ok().and_then(char::from_u32)
+            } else if let Some(code) = escaped.strip_prefix("u{") {
+                u32::from_str_radix(&code.trim_end_matches('}').replace('_', ""), 16)
+                    .ok()
+                    .and_then(char::from_u32)
+            } else {
+                None
+            }
+        }
+    };
+    character.map_or_else(|| escape_sequence.to_string(), String::from)
+}
+
+pub fn is_doc_hidden(node: &Node, source_code: &str) -> Result<bool, ExtractionError> {
+    let attributes = extract_attributes(node, source_code)?;
+    Ok(attributes
+        .iter()
+        .any(|attribute| is_doc_hidden_attribute(attribute)))
+}
+
+pub fn is_doc_hidden_attribute(attribute: &str) -> bool {
+    remove_whitespace(attribute) == "#[doc(hidden)]"
+}
+
+/// Whether the attribute sets documentation (e.g. `#[doc = "Foo"]`), as opposed to
+/// configuring it (e.g. `#[doc(hidden)]`).
+pub fn is_doc_value_attribute(attribute: &str) -> bool {
+    remove_whitespace(attribute).starts_with("#[doc=")
+}
+
+fn remove_whitespace(text: &str) -> String {
+    text.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// Get the generic parameters of an item, if any (e.g. `<T: Clone>` for `fn foo<T: Clone>()`).
+pub fn get_generics(node: &Node, source_code: &str) -> Option<String> {
+    node.child_by_field_name("type_parameters")
+        .and_then(|parameters| parameters.utf8_text(source_code.as_bytes()).ok())
+        .map(str::to_string)
+}
+
+/// Get the span of a node, with 1-based lines and columns.
+pub fn get_source_span(node: &Node) -> SourceSpan {
+    let start = node.start_position();
+    let end = node.end_position();
+    SourceSpan {
+        start_line: start.row + 1,
+        start_column: start.column + 1,
+        end_line: end.row + 1,
+        end_column: end.column + 1,
+    }
+}
+
+/// Get the span of `byte_range` in `source_code`, for items whose extent isn't a node (e.g. those
+/// the grammar doesn't support).
+pub fn get_byte_range_span(source_code: &str, byte_range: &Range<usize>) -> SourceSpan {
+    let get_position = |offset: usize| {
+        let preceding_code = &source_code[..offset];
+        let line_start = preceding_code.rfind('\n').map_or(0, |index| index + 1);
+        (
+            preceding_code.matches('\n').count() + 1,
+            offset - line_start + 1,
+        )
+    };
+    let (start_line, start_column) = get_position(byte_range.start);
+    let (end_line, end_column) = get_position(byte_range.end);
+    SourceSpan {
+        start_line,
+        start_column,
+        end_line,
+        end_column,
+    }
+}
+
 pub fn extract_name(node: &Node, source_code: &str) -> Result<String, ExtractionError> {
     let mut cursor = node.walk();
     let children: Vec<_> = node.children(&mut cursor).collect();
@@ -59,39 +254,76 @@ mod tests {
     use crate::api::parsing::test_helpers::make_tree;
     use crate::treesitter_test_helpers::find_child_node;
 
-    mod is_public {
+    mod visibility {
         use super::*;
 
         #[test]
         fn public_function() {
-            let tree = make_tree("pub fn test() {}");
+            let source_code = "pub fn test() {}";
+            let tree = make_tree(source_code);
             let function = find_child_node(tree.root_node(), "function_item");
 
-            assert!(is_public(&function));
+            assert_eq!(get_visibility(&function, source_code), Visibility::Public);
+            assert!(is_public(&function, source_code));
         }
 
         #[test]
         fn private_function() {
-            let tree = make_tree("fn test() {}");
+            let source_code = "fn test() {}";
+            let tree = make_tree(source_code);
             let function = find_child_node(tree.root_node(), "function_item");
 
-            assert!(!is_public(&function));
+            assert_eq!(get_visibility(&function, source_code), Visibility::Private);
+            assert!(!is_public(&function, source_code));
         }
 
         #[test]
         fn public_crate_function() {
-            let tree = make_tree("pub(crate) fn test() {}");
+            let source_code = "pub(crate) fn test() {}";
+            let tree = make_tree(source_code);
             let function = find_child_node(tree.root_node(), "function_item");
 
-            assert!(is_public(&function));
+            assert_eq!(
+                get_visibility(&function, source_code),
+                Visibility::Restricted
+            );
+            assert!(!is_public(&function, source_code));
         }
 
         #[test]
         fn public_super_function() {
-            let tree = make_tree("pub(super) fn test() {}");
+            let source_code = "pub(super) fn test() {}";
+            let tree = make_tree(source_code);
             let function = find_child_node(tree.root_node(), "function_item");
 
-            assert!(is_public(&function));
+            assert_eq!(
+                get_visibility(&function, source_code),
+                Visibility::Restricted
+            );
+            assert!(!is_public(&function, source_code));
+        }
+
+        #[test]
+        fn public_in_path_function() {
+            let source_code = "pub(in crate::outer) fn test() {}";
+            let tree = make_tree(source_code);
+            let function = find_child_node(tree.root_node(), "function_item");
+
+            assert_eq!(
+                get_visibility(&function, source_code),
+                Visibility::Restricted
+            );
+            assert!(!is_public(&function, source_code));
+        }
+
+        #[test]
+        fn public_self_function() {
+            let source_code = "pub(self) fn test() {}";
+            let tree = make_tree(source_code);
+            let function = find_child_node(tree.root_node(), "function_item");
+
+            assert_eq!(get_visibility(&function, source_code), Visibility::Private);
+            assert!(!is_public(&function, source_code));
         }
     }
 
@@ -146,6 +378,178 @@ mod tests {
         }
     }
 
+    mod attribute_value {
+        use super::*;
+
+        #[test]
+        fn no_attributes() {
+            let source_code = "mod foo;";
+            let tree = make_tree(source_code);
+            let module = find_child_node(tree.root_node(), "mod_item");
+
+            let value = get_attribute_value(&module, "path", source_code).unwrap();
+
+            assert_eq!(value, None);
+        }
+
+        #[test]
+        fn matching_attribute() {
+            let source_code = "#[cfg(unix)]\n#[path = \"platform/unix.rs\"]\nmod foo;";
+            let tree = make_tree(source_code);
+            let module = find_child_node(tree.root_node(), "mod_item");
+
+            let value = get_attribute_value(&module, "path", source_code).unwrap();
+
+            assert_eq!(value, Some("platform/unix.rs".to_string()));
+        }
+
+        #[test]
+        fn raw_string_value() {
+            let source_code = "#[path = r\"platform/unix.rs\"]\nmod foo;";
+            let tree = make_tree(source_code);
+            let module = find_child_node(tree.root_node(), "mod_item");
+
+            let value = get_attribute_value(&module, "path", source_code).unwrap();
+
+            assert_eq!(value, Some("platform/unix.rs".to_string()));
+        }
+
+        #[test]
+        fn escaped_string_value() {
+            let source_code = r#"#[doc = "Line\n\"Quoted\" \u{2014} \x41\\"]
+mod foo;"#;
+            let tree = make_tree(source_code);
+            let module = find_child_node(tree.root_node(), "mod_item");
+
+            let value = get_attribute_value(&module, "doc", source_code).unwrap();
+
+            assert_eq!(value, Some("Line\n\"Quoted\" \u{2014} A\\".to_string()));
+        }
+
+        #[test]
+        fn line_continuation() {
+            let source_code = "#[doc = \"First \\\n    second\"]\nmod foo;";
+            let tree = make_tree(source_code);
+            let module = find_child_node(tree.root_node(), "mod_item");
+
+            let value = get_attribute_value(&module, "doc", source_code).unwrap();
+
+            assert_eq!(value, Some("First second".to_string()));
+        }
+
+        #[test]
+        fn other_attribute() {
+            let source_code = "#[doc = \"foo.rs\"]\nmod foo;";
+            let tree = make_tree(source_code);
+            let module = find_child_node(tree.root_node(), "mod_item");
+
+            let value = get_attribute_value(&module, "path", source_code).unwrap();
+
+            assert_eq!(value, None);
+        }
+    }
+
+    mod doc_hidden {
+        use super::*;
+
+        #[test]
+        fn hidden_function() {
+            let source_code = "#[doc(hidden)]\npub fn test() {}";
+            let tree = make_tree(source_code);
+            let function = find_child_node(tree.root_node(), "function_item");
+
+            assert!(is_doc_hidden(&function, source_code).unwrap());
+        }
+
+        #[test]
+        fn hidden_function_among_other_attributes() {
+            let source_code = "#[inline]\n#[doc( hidden )]\npub fn test() {}";
+            let tree = make_tree(source_code);
+            let function = find_child_node(tree.root_node(), "function_item");
+
+            assert!(is_doc_hidden(&function, source_code).unwrap());
+        }
+
+        #[test]
+        fn documented_function() {
+            let source_code = "#[doc = \"Documented\"]\npub fn test() {}";
+            let tree = make_tree(source_code);
+            let function = find_child_node(tree.root_node(), "function_item");
+
+            assert!(!is_doc_hidden(&function, source_code).unwrap());
+        }
+    }
+
+    mod doc_value {
+        use super::*;
+
+        #[test]
+        fn doc_value() {
+            assert!(is_doc_value_attribute("#[doc = \"Documented\"]"));
+        }
+
+        #[test]
+        fn doc_include() {
+            assert!(is_doc_value_attribute(
+                "#[doc = include_str!(\"README.md\")]"
+            ));
+        }
+
+        #[test]
+        fn doc_hidden() {
+            assert!(!is_doc_value_attribute("#[doc(hidden)]"));
+        }
+    }
+
+    mod generics {
+        use super::*;
+
+        #[test]
+        fn generic_function() {
+            let source_code = "fn test<T: Clone, const N: usize>(value: T) {}";
+            let tree = make_tree(source_code);
+            let function = find_child_node(tree.root_node(), "function_item");
+
+            let generics = get_generics(&function, source_code);
+
+            assert_eq!(generics, Some("<T: Clone, const N: usize>".to_string()));
+        }
+
+        #[test]
+        fn non_generic_struct() {
+            let source_code = "struct Test;";
+            let tree = make_tree(source_code);
+            let struct_node = find_child_node(tree.root_node(), "struct_item");
+
+            let generics = get_generics(&struct_node, source_code);
+
+            assert_eq!(generics, None);
+        }
+    }
+
+    mod source_spans {
+        use super::*;
+
+        #[test]
+        fn multiline_item() {
+            let source_code = "// Comment\n  struct Test {\n    field: u8,\n}";
+            let tree = make_tree(source_code);
+            let struct_node = find_child_node(tree.root_node(), "struct_item");
+
+            let span = get_source_span(&struct_node);
+
+            assert_eq!(
+                span,
+                SourceSpan {
+                    start_line: 2,
+                    start_column: 3,
+                    end_line: 4,
+                    end_column: 2,
+                }
+            );
+        }
+    }
+
     mod extract_name {
         use super::*;
 
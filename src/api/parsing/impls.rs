@@ -0,0 +1,248 @@
+use super::helpers::is_public;
+use super::symbols::{get_doc_comments_and_attributes, get_symbol_source_code};
+use daipendency_extractor::ExtractionError;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Get the path to the type implemented by an `impl` block (e.g. `Foo` in `impl<T> Foo<T> { ... }`).
+///
+/// Returns `None` if the implemented type isn't a named type (e.g. blanket implementations).
+pub fn get_impl_type_path(
+    node: Node,
+    source_code: &str,
+) -> Result<Option<String>, ExtractionError> {
+    let type_node = node
+        .child_by_field_name("type")
+        .ok_or_else(|| ExtractionError::Malformed("Failed to find impl type".to_string()))?;
+    get_type_path(type_node, source_code)
+}
+
+fn get_type_path(type_node: Node, source_code: &str) -> Result<Option<String>, ExtractionError> {
+    match type_node.kind() {
+        "type_identifier" | "scoped_type_identifier" => {
+            let path = type_node
+                .utf8_text(source_code.as_bytes())
+                .map_err(|e| ExtractionError::Malformed(e.to_string()))?;
+            Ok(Some(path.to_string()))
+        }
+        "generic_type" | "reference_type" => match type_node.child_by_field_name("type") {
+            Some(inner_type) => get_type_path(inner_type, source_code),
+            None => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Get the source code of an `impl` block, with the bodies of its members stripped.
+///
+/// Inherent implementations only retain their public members, and are omitted altogether if they
+/// have none. Trait implementations retain all their members.
+pub fn get_impl_source_code(
+    node: Node,
+    source_code: &str,
+    directory: &Path,
+) -> Result<Option<String>, ExtractionError> {
+    let is_trait_impl = node.child_by_field_name("trait").is_some();
+    let body = node
+        .child_by_field_name("body")
+        .ok_or_else(|| ExtractionError::Malformed("Failed to find impl body".to_string()))?;
+
+    let mut members_source = String::new();
+    let mut cursor = body.walk();
+    for member in body.children(&mut cursor) {
+        if !matches!(member.kind(), "function_item" | "const_item" | "type_item") {
+            continue;
+        }
+        if !is_trait_impl && !is_public(&member, source_code) {
+            continue;
+        }
+        let member_source = get_symbol_source_code(member, source_code, directory)?;
+        for line in member_source.lines() {
+            members_source.push_str("    ");
+            members_source.push_str(line);
+            members_source.push('\n');
+        }
+    }
+
+    if !is_trait_impl && members_source.is_empty() {
+        return Ok(None);
+    }
+
+    let mut result = get_doc_comments_and_attributes(node, source_code, directory)?;
+    result.push_str(source_code[node.start_byte()..body.start_byte()].trim_end());
+    if members_source.is_empty() {
+        result.push_str(" {}");
+    } else {
+        result.push_str(" {\n");
+        result.push_str(&members_source);
+        result.push('}');
+    }
+
+    Ok(Some(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::parsing::test_helpers::make_tree, treesitter_test_helpers::find_child_node};
+
+    mod type_path {
+        use super::*;
+
+        #[test]
+        fn simple_type() {
+            let source_code = "impl Foo {}";
+            let tree = make_tree(source_code);
+            let impl_node = find_child_node(tree.root_node(), "impl_item");
+
+            let result = get_impl_type_path(impl_node, source_code).unwrap();
+
+            assert_eq!(result, Some("Foo".to_string()));
+        }
+
+        #[test]
+        fn generic_type() {
+            let source_code = "impl<T> Foo<T> {}";
+            let tree = make_tree(source_code);
+            let impl_node = find_child_node(tree.root_node(), "impl_item");
+
+            let result = get_impl_type_path(impl_node, source_code).unwrap();
+
+            assert_eq!(result, Some("Foo".to_string()));
+        }
+
+        #[test]
+        fn scoped_reference_type() {
+            let source_code = "impl Display for &crate::inner::Foo {}";
+            let tree = make_tree(source_code);
+            let impl_node = find_child_node(tree.root_node(), "impl_item");
+
+            let result = get_impl_type_path(impl_node, source_code).unwrap();
+
+            assert_eq!(result, Some("crate::inner::Foo".to_string()));
+        }
+
+        #[test]
+        fn unnamed_type() {
+            let source_code = "impl Display for [u8] {}";
+            let tree = make_tree(source_code);
+            let impl_node = find_child_node(tree.root_node(), "impl_item");
+
+            let result = get_impl_type_path(impl_node, source_code).unwrap();
+
+            assert_eq!(result, None);
+        }
+    }
+
+    mod source_code {
+        use super::*;
+
+        #[test]
+        fn inherent_impl_public_members() {
+            let source_code = r#"impl Foo {
+    /// Create a new Foo
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub const LIMIT: usize = 42;
+
+    fn private_helper(&self) {}
+}"#;
+            let tree = make_tree(source_code);
+            let impl_node = find_child_node(tree.root_node(), "impl_item");
+
+            let result = get_impl_source_code(impl_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                Some(
+                    "impl Foo {\n    /// Create a new Foo\n    pub fn new() -> Self;\n    pub const LIMIT: usize;\n}"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn inherent_impl_without_public_members() {
+            let source_code = "impl Foo { fn private_helper(&self) {} }";
+            let tree = make_tree(source_code);
+            let impl_node = find_child_node(tree.root_node(), "impl_item");
+
+            let result = get_impl_source_code(impl_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(result, None);
+        }
+
+        #[test]
+        fn generic_impl() {
+            let source_code =
+                "impl<T: Clone> Foo<T> where T: Send { pub fn get(&self) -> T { todo!() } }";
+            let tree = make_tree(source_code);
+            let impl_node = find_child_node(tree.root_node(), "impl_item");
+
+            let result = get_impl_source_code(impl_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                Some(
+                    "impl<T: Clone> Foo<T> where T: Send {\n    pub fn get(&self) -> T;\n}"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn trait_impl() {
+            let source_code = r#"impl Iterator for Foo {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        None
+    }
+}"#;
+            let tree = make_tree(source_code);
+            let impl_node = find_child_node(tree.root_node(), "impl_item");
+
+            let result = get_impl_source_code(impl_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                Some(
+                    "impl Iterator for Foo {\n    type Item = u8;\n    fn next(&mut self) -> Option<u8>;\n}"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn empty_trait_impl() {
+            let source_code = "unsafe impl Send for Foo {}";
+            let tree = make_tree(source_code);
+            let impl_node = find_child_node(tree.root_node(), "impl_item");
+
+            let result = get_impl_source_code(impl_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(result, Some("unsafe impl Send for Foo {}".to_string()));
+        }
+
+        #[test]
+        fn impl_with_doc_comment_and_attributes() {
+            let source_code = r#"/// Formatting support
+#[cfg(feature = "fmt")]
+impl Display for Foo {}"#;
+            let tree = make_tree(source_code);
+            let impl_node = find_child_node(tree.root_node(), "impl_item");
+
+            let result = get_impl_source_code(impl_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                Some(
+                    "/// Formatting support\n#[cfg(feature = \"fmt\")]\nimpl Display for Foo {}"
+                        .to_string()
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use daipendency_extractor::ExtractionError;
+use regex::Regex;
+use std::path::Path;
+use tree_sitter::Node;
+
+use super::files::IncludePath;
+use super::helpers::get_string_literal_value;
+
+/// Read the file included by an `include_str!` invocation (e.g. `include_str!("../README.md")`),
+/// relative to `directory`, or `None` if it isn't one or the file can't be read.
+pub fn read_included_string(
+    invocation: &Node,
+    source_code: &str,
+    directory: &Path,
+) -> Result<Option<String>, ExtractionError> {
+    let is_include_str = invocation
+        .child_by_field_name("macro")
+        .is_some_and(|node| &source_code[node.byte_range()] == "include_str");
+    if invocation.kind() != "macro_invocation" || !is_include_str {
+        return Ok(None);
+    }
+    let Some(arguments) = invocation.named_child(1) else {
+        return Ok(None);
+    };
+    let mut cursor = arguments.walk();
+    let literals: Vec<_> = arguments.named_children(&mut cursor).collect();
+    let [literal] = literals.as_slice() else {
+        return Ok(None);
+    };
+    if literal.kind() != "string_literal" {
+        return Ok(None);
+    }
+    let path = get_string_literal_value(literal, source_code)?;
+    Ok(std::fs::read_to_string(directory.join(path)).ok())
+}
+
+/// Get the path to the file included by an `include!` invocation from its arguments (e.g.
+/// `("bindings.rs")`), or `None` if it isn't a literal or a literal appended to `OUT_DIR`.
+pub fn get_include_path(arguments: &str) -> Option<IncludePath> {
+    let relative_regex = Regex::new(r#"^[(\[{]\s*"([^"]*)"\s*,?\s*[)\]}]$"#).unwrap();
+    let out_dir_regex = Regex::new(
+        r#"^[(\[{]\s*concat!\s*\(\s*env!\s*\(\s*"OUT_DIR"\s*\)\s*,\s*"([^"]*)"\s*,?\s*\)\s*,?\s*[)\]}]$"#,
+    )
+    .unwrap();
+    if let Some(captures) = relative_regex.captures(arguments) {
+        Some(IncludePath::Relative(captures[1].to_string()))
+    } else {
+        out_dir_regex
+            .captures(arguments)
+            .map(|captures| IncludePath::OutDir(captures[1].to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use daipendency_testing::tempdir::TempDir;
+
+    mod included_strings {
+        use super::*;
+        use crate::api::parsing::test_helpers::make_tree;
+        use crate::treesitter_test_helpers::find_child_node;
+
+        fn read_invocation(source_code: &str, directory: &Path) -> Option<String> {
+            let tree = make_tree(source_code);
+            let statement = find_child_node(tree.root_node(), "expression_statement");
+            let invocation = find_child_node(statement, "macro_invocation");
+            read_included_string(&invocation, source_code, directory).unwrap()
+        }
+
+        #[test]
+        fn relative_path() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file("README.md", "# Title").unwrap();
+            let lib_path = temp_dir.create_file("src/lib.rs", "").unwrap();
+
+            let content = read_invocation(
+                "include_str!(\"../README.md\");",
+                lib_path.parent().unwrap(),
+            );
+
+            assert_eq!(content, Some("# Title".to_string()));
+        }
+
+        #[test]
+        fn escaped_path() {
+            let temp_dir = TempDir::new();
+            let readme_path = temp_dir.create_file("READ ME.md", "# Title").unwrap();
+
+            let content = read_invocation(
+                "include_str!(\"READ\\x20ME.md\");",
+                readme_path.parent().unwrap(),
+            );
+
+            assert_eq!(content, Some("# Title".to_string()));
+        }
+
+        #[test]
+        fn missing_file() {
+            let temp_dir = TempDir::new();
+            let lib_path = temp_dir.create_file("src/lib.rs", "").unwrap();
+
+            let content = read_invocation(
+                "include_str!(\"../README.md\");",
+                lib_path.parent().unwrap(),
+            );
+
+            assert_eq!(content, None);
+        }
+
+        #[test]
+        fn other_macro() {
+            let temp_dir = TempDir::new();
+            let readme_path = temp_dir.create_file("README.md", "# Title").unwrap();
+
+            let content = read_invocation(
+                "include_bytes!(\"README.md\");",
+                readme_path.parent().unwrap(),
+            );
+
+            assert_eq!(content, None);
+        }
+    }
+
+    mod include_paths {
+        use super::*;
+
+        #[test]
+        fn relative_path() {
+            let path = get_include_path(r#"("bindings.rs")"#);
+
+            assert_eq!(path, Some(IncludePath::Relative("bindings.rs".to_string())));
+        }
+
+        #[test]
+        fn out_dir_path() {
+            let path = get_include_path(r#"(concat!(env!("OUT_DIR"), "/bindings.rs"))"#);
+
+            assert_eq!(path, Some(IncludePath::OutDir("/bindings.rs".to_string())));
+        }
+
+        #[test]
+        fn braces() {
+            let path = get_include_path(r#"{ "bindings.rs" }"#);
+
+            assert_eq!(path, Some(IncludePath::Relative("bindings.rs".to_string())));
+        }
+
+        #[test]
+        fn unsupported_path() {
+            let path = get_include_path(r#"(concat!(env!("GENERATED_DIR"), "/bindings.rs"))"#);
+
+            assert_eq!(path, None);
+        }
+    }
+}
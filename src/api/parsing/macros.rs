@@ -1,36 +1,31 @@
 use super::doc_comments::extract_outer_doc_comments;
 use daipendency_extractor::ExtractionError;
+use regex::Regex;
+use std::ops::Range;
+use std::path::Path;
 use tree_sitter::Node;
 
+/// Get the source code of a `macro_rules!` macro exported at the crate root (i.e.
+/// `#[macro_export]`) up to its rules, along with the matchers of its rules (e.g. `($x:expr)`), or
+/// `None` if it isn't exported.
+///
+/// The rules are rendered separately with [`render_macro_rules`], once it's known how many to keep.
 pub fn get_macro_source_code(
     node: Node,
     source_code: &str,
-) -> Result<Option<String>, ExtractionError> {
+    directory: &Path,
+) -> Result<Option<(String, Vec<String>)>, ExtractionError> {
     let mut result = String::new();
 
-    if let Some(doc_comment) = extract_outer_doc_comments(&node, source_code)? {
+    if let Some(doc_comment) = extract_outer_doc_comments(&node, source_code, directory)? {
         result.push_str(&doc_comment);
     }
 
-    let mut is_exported = false;
-    let mut prev_sibling = node.prev_sibling();
-    while let Some(sibling) = prev_sibling {
-        if sibling.kind() == "attribute_item" {
-            let attr_text = sibling.utf8_text(source_code.as_bytes()).map_err(|_| {
-                ExtractionError::Malformed("Failed to read attribute text".to_string())
-            })?;
-            if attr_text == "#[macro_export]" {
-                is_exported = true;
-                result.push_str(attr_text);
-                result.push('\n');
-                break;
-            }
-        }
-        prev_sibling = sibling.prev_sibling();
-    }
-    if !is_exported {
+    let Some(export_attribute) = find_macro_export_attribute(&node, source_code) else {
         return Ok(None);
-    }
+    };
+    result.push_str(&source_code[export_attribute.byte_range()]);
+    result.push('\n');
 
     let mut cursor = node.walk();
     let brace = node
@@ -39,9 +34,172 @@ pub fn get_macro_source_code(
         .ok_or_else(|| ExtractionError::Malformed("Failed to find macro body".to_string()))?;
 
     result.push_str(source_code[node.start_byte()..brace.start_byte()].trim_end());
-    result.push(';');
 
-    Ok(Some(result))
+    let matchers = node
+        .children(&mut cursor)
+        .filter(|n| n.kind() == "macro_rule")
+        .filter_map(|rule| rule.child_by_field_name("left"))
+        .map(|matcher| source_code[matcher.byte_range()].to_string())
+        .collect();
+
+    Ok(Some((result, matchers)))
+}
+
+/// Render the rules of a `macro_rules!` macro after `source_code` like rustdoc does, with their
+/// transcribers replaced by `{ ... }`.
+///
+/// Only the first `max_rules` rules are rendered, and the rest are summarised in a comment.
+pub fn render_macro_rules(source_code: &str, matchers: &[String], max_rules: usize) -> String {
+    render_rules(source_code, matchers, max_rules, ';')
+}
+
+/// Render the rules of a macro after `source_code`, each followed by `separator` (i.e. `;` for
+/// `macro_rules!` macros and `,` for declarative macros 2.0).
+fn render_rules(
+    source_code: &str,
+    matchers: &[String],
+    max_rules: usize,
+    separator: char,
+) -> String {
+    if matchers.is_empty() {
+        return format!("{} {{}}", source_code);
+    }
+
+    let mut result = format!("{} {{\n", source_code);
+    for matcher in matchers.iter().take(max_rules) {
+        result.push_str(&format!("    {} => {{ ... }}{}\n", matcher, separator));
+    }
+    let omitted_rules = matchers.len().saturating_sub(max_rules);
+    if omitted_rules > 0 {
+        let noun = if omitted_rules == 1 { "rule" } else { "rules" };
+        result.push_str(&format!("    // {} more {} omitted\n", omitted_rules, noun));
+    }
+    result.push('}');
+    result
+}
+
+/// Find the `#[macro_export]` attribute of a macro, with or without arguments (e.g.
+/// `#[macro_export(local_inner_macros)]`).
+fn find_macro_export_attribute<'a>(node: &Node<'a>, source_code: &str) -> Option<Node<'a>> {
+    let mut current = node.prev_sibling();
+    while let Some(sibling) = current {
+        match sibling.kind() {
+            "attribute_item" => {
+                let is_macro_export = sibling
+                    .named_child(0)
+                    .and_then(|attribute| attribute.named_child(0))
+                    .is_some_and(|path| &source_code[path.byte_range()] == "macro_export");
+                if is_macro_export {
+                    return Some(sibling);
+                }
+            }
+            "line_comment" | "block_comment" => {}
+            _ => return None,
+        }
+        current = sibling.prev_sibling();
+    }
+    None
+}
+
+/// A public declarative macro 2.0 (e.g. `pub macro foo($x:expr) { ... }`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeclMacro {
+    pub name: String,
+    /// The macro, with its body omitted (e.g. `pub macro foo($x:expr);`) or its rules rendered
+    /// like those of `macro_rules!` macros
+    pub source_code: String,
+    /// The byte range of the whole macro, from its visibility modifier to the end of its body
+    pub byte_range: Range<usize>,
+}
+
+/// Get the public declarative macro 2.0 starting at `node`, if any.
+///
+/// The grammar doesn't support these macros yet, so they're recognised from the `ERROR` node
+/// starting them, and their extent is worked out from the source code.
+pub fn get_decl_macro(
+    node: &Node,
+    source_code: &str,
+    directory: &Path,
+) -> Result<Option<DeclMacro>, ExtractionError> {
+    let header_regex = Regex::new(r"^pub\s+macro\s+([A-Za-z_][A-Za-z0-9_]*)").unwrap();
+    let item_code = &source_code[node.start_byte()..];
+    let Some(captures) = header_regex.captures(item_code) else {
+        return Ok(None);
+    };
+    let name = captures[1].to_string();
+    let header_end = captures.get(0).unwrap().end();
+    let parameters_end = header_end + get_group_length(&item_code[header_end..], "(");
+    let body_end = parameters_end + get_group_length(&item_code[parameters_end..], "{");
+
+    let mut result = String::new();
+    if let Some(doc_comment) = extract_outer_doc_comments(node, source_code, directory)? {
+        result.push_str(&doc_comment);
+    }
+    let signature = format!("pub macro {}", name);
+    if parameters_end > header_end {
+        result.push_str(&signature);
+        result.push_str(item_code[header_end..parameters_end].trim());
+        result.push(';');
+    } else {
+        let matchers = get_decl_macro_matchers(&item_code[parameters_end..body_end]);
+        result.push_str(&render_rules(&signature, &matchers, matchers.len(), ','));
+    }
+
+    Ok(Some(DeclMacro {
+        name,
+        source_code: result,
+        byte_range: node.start_byte()..node.start_byte() + body_end,
+    }))
+}
+
+/// Get the matchers of the rules in the body of a declarative macro 2.0 (e.g. `($x:expr)` in
+/// `{ ($x:expr) => { $x }, }`).
+fn get_decl_macro_matchers(body: &str) -> Vec<String> {
+    let body = body.trim();
+    let Some(mut rules) = body
+        .strip_prefix('{')
+        .and_then(|rules| rules.strip_suffix('}'))
+    else {
+        return Vec::new();
+    };
+    let mut matchers = Vec::new();
+    loop {
+        let matcher_length = get_group_length(rules, "([{");
+        if matcher_length == 0 {
+            break;
+        }
+        matchers.push(rules[..matcher_length].trim().to_string());
+        let Some(transcriber) = rules[matcher_length..].trim_start().strip_prefix("=>") else {
+            break;
+        };
+        rules = transcriber[get_group_length(transcriber, "([{")..].trim_start();
+        rules = rules.strip_prefix([',', ';']).unwrap_or(rules);
+    }
+    matchers
+}
+
+/// Get the length of the delimited group at the start of `code`, including any leading
+/// whitespace, if it opens with one of `delimiters` and is balanced (e.g. `($x:expr)` in
+/// `($x:expr) { $x }`).
+fn get_group_length(code: &str, delimiters: &str) -> usize {
+    let group_start = code.len() - code.trim_start().len();
+    if !code[group_start..].starts_with(|character| delimiters.contains(character)) {
+        return 0;
+    }
+    let mut depth = 0;
+    for (index, character) in code[group_start..].char_indices() {
+        match character {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return group_start + index + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    0
 }
 
 #[cfg(test)]
@@ -58,11 +216,14 @@ macro_rules! test_macro {
         let tree = make_tree(source_code);
         let macro_node = find_child_node(tree.root_node(), "macro_definition");
 
-        let result = get_macro_source_code(macro_node, source_code).unwrap();
+        let result = get_macro_source_code(macro_node, source_code, Path::new("")).unwrap();
 
         assert_eq!(
             result,
-            Some("#[macro_export]\nmacro_rules! test_macro;".to_string())
+            Some((
+                "#[macro_export]\nmacro_rules! test_macro".to_string(),
+                vec!["()".to_string()]
+            ))
         );
     }
 
@@ -74,7 +235,7 @@ macro_rules! test_macro {
         let tree = make_tree(source_code);
         let macro_node = find_child_node(tree.root_node(), "macro_definition");
 
-        let result = get_macro_source_code(macro_node, source_code).unwrap();
+        let result = get_macro_source_code(macro_node, source_code, Path::new("")).unwrap();
 
         assert_eq!(result, None);
     }
@@ -89,11 +250,218 @@ macro_rules! test_macro {
         let tree = make_tree(source_code);
         let macro_node = find_child_node(tree.root_node(), "macro_definition");
 
-        let result = get_macro_source_code(macro_node, source_code).unwrap();
+        let result = get_macro_source_code(macro_node, source_code, Path::new("")).unwrap();
+
+        assert_eq!(
+            result,
+            Some((
+                "/// This is a test macro\n#[macro_export]\nmacro_rules! test_macro".to_string(),
+                vec!["()".to_string()]
+            ))
+        );
+    }
+
+    #[test]
+    fn macro_export_with_arguments() {
+        let source_code = r#"#[macro_export(local_inner_macros)]
+macro_rules! test_macro {
+    () => { helper!() }
+}"#;
+        let tree = make_tree(source_code);
+        let macro_node = find_child_node(tree.root_node(), "macro_definition");
+
+        let result = get_macro_source_code(macro_node, source_code, Path::new("")).unwrap();
 
         assert_eq!(
             result,
-            Some("/// This is a test macro\n#[macro_export]\nmacro_rules! test_macro;".to_string())
+            Some((
+                "#[macro_export(local_inner_macros)]\nmacro_rules! test_macro".to_string(),
+                vec!["()".to_string()]
+            ))
         );
     }
+
+    #[test]
+    fn macro_after_exported_macro() {
+        let source_code = r#"#[macro_export]
+macro_rules! exported_macro {
+    () => {};
+}
+
+macro_rules! private_macro {
+    () => {};
+}"#;
+        let tree = make_tree(source_code);
+        let macro_node = tree.root_node().named_child(2).unwrap();
+
+        let result = get_macro_source_code(macro_node, source_code, Path::new("")).unwrap();
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn multiple_rules() {
+        let source_code = r#"#[macro_export]
+macro_rules! test_macro {
+    () => {};
+    ($x:expr) => { $x };
+    [$($x:expr),*] => { ($($x),*) };
+}"#;
+        let tree = make_tree(source_code);
+        let macro_node = find_child_node(tree.root_node(), "macro_definition");
+
+        let result = get_macro_source_code(macro_node, source_code, Path::new("")).unwrap();
+
+        let (_, matchers) = result.unwrap();
+        assert_eq!(matchers, vec!["()", "($x:expr)", "[$($x:expr),*]"]);
+    }
+
+    mod rules_rendering {
+        use super::*;
+
+        const SOURCE_CODE: &str = "#[macro_export]\nmacro_rules! test_macro";
+
+        fn make_matchers(count: usize) -> Vec<String> {
+            (0..count)
+                .map(|index| format!("(${}:expr)", index))
+                .collect()
+        }
+
+        #[test]
+        fn no_rules() {
+            let result = render_macro_rules(SOURCE_CODE, &[], 10);
+
+            assert_eq!(result, "#[macro_export]\nmacro_rules! test_macro {}");
+        }
+
+        #[test]
+        fn rules_within_limit() {
+            let result = render_macro_rules(SOURCE_CODE, &make_matchers(2), 2);
+
+            assert_eq!(
+                result,
+                "#[macro_export]\nmacro_rules! test_macro {\n    ($0:expr) => { ... };\n    ($1:expr) => { ... };\n}"
+            );
+        }
+
+        #[test]
+        fn one_rule_over_limit() {
+            let result = render_macro_rules(SOURCE_CODE, &make_matchers(2), 1);
+
+            assert_eq!(
+                result,
+                "#[macro_export]\nmacro_rules! test_macro {\n    ($0:expr) => { ... };\n    // 1 more rule omitted\n}"
+            );
+        }
+
+        #[test]
+        fn several_rules_over_limit() {
+            let result = render_macro_rules(SOURCE_CODE, &make_matchers(3), 0);
+
+            assert_eq!(
+                result,
+                "#[macro_export]\nmacro_rules! test_macro {\n    // 3 more rules omitted\n}"
+            );
+        }
+    }
+
+    mod decl_macros {
+        use super::*;
+        use crate::treesitter_test_helpers::find_child_nodes;
+
+        fn get_first_decl_macro(source_code: &str) -> Option<(String, String)> {
+            let tree = make_tree(source_code);
+            let error_node = find_child_nodes(tree.root_node(), "ERROR")[0];
+            get_decl_macro(&error_node, source_code, Path::new(""))
+                .unwrap()
+                .map(|decl_macro| (decl_macro.name, decl_macro.source_code))
+        }
+
+        #[test]
+        fn parameters() {
+            let source_code = "pub macro test_macro($($x:expr),*) {\n    ($($x),*)\n}";
+
+            let result = get_first_decl_macro(source_code);
+
+            assert_eq!(
+                result,
+                Some((
+                    "test_macro".to_string(),
+                    "pub macro test_macro($($x:expr),*);".to_string()
+                ))
+            );
+        }
+
+        #[test]
+        fn rules() {
+            let source_code = "pub macro test_macro {\n    ($x:expr) => { $x },\n}";
+
+            let result = get_first_decl_macro(source_code);
+
+            assert_eq!(
+                result,
+                Some((
+                    "test_macro".to_string(),
+                    "pub macro test_macro {\n    ($x:expr) => { ... },\n}".to_string()
+                ))
+            );
+        }
+
+        #[test]
+        fn multiple_rules() {
+            let source_code =
+                "pub macro test_macro {\n    () => {},\n    [$($x:tt)*] => { ($($x)*) };\n}";
+
+            let result = get_first_decl_macro(source_code);
+
+            assert_eq!(
+                result,
+                Some((
+                    "test_macro".to_string(),
+                    "pub macro test_macro {\n    () => { ... },\n    [$($x:tt)*] => { ... },\n}"
+                        .to_string()
+                ))
+            );
+        }
+
+        #[test]
+        fn byte_range() {
+            let source_code = "pub fn before() {}\npub macro test_macro($x:expr) { $x }\n";
+            let tree = make_tree(source_code);
+            let error_node = find_child_nodes(tree.root_node(), "ERROR")[0];
+
+            let decl_macro = get_decl_macro(&error_node, source_code, Path::new(""))
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(
+                &source_code[decl_macro.byte_range],
+                "pub macro test_macro($x:expr) { $x }"
+            );
+        }
+
+        #[test]
+        fn doc_comment() {
+            let source_code = "/// This is a test macro\npub macro test_macro() {}";
+
+            let result = get_first_decl_macro(source_code);
+
+            assert_eq!(
+                result,
+                Some((
+                    "test_macro".to_string(),
+                    "/// This is a test macro\npub macro test_macro();".to_string()
+                ))
+            );
+        }
+
+        #[test]
+        fn restricted_macro() {
+            let source_code = "pub(crate) macro test_macro() {}";
+
+            let result = get_first_decl_macro(source_code);
+
+            assert_eq!(result, None);
+        }
+    }
 }
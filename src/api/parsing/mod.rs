@@ -1,93 +1,210 @@
 use daipendency_extractor::ExtractionError;
 use daipendency_extractor::Symbol;
+use std::ops::Range;
+use std::path::Path;
 use tree_sitter::{Node, Parser};
 
+mod cfg;
 mod doc_comments;
 mod files;
+mod foreign_items;
 mod helpers;
+mod impls;
+mod includes;
 mod macros;
 mod reexports;
 mod symbols;
+mod syntax_errors;
 mod test_helpers;
 
+use cfg::extract_cfg_predicate;
 use doc_comments::extract_inner_doc_comments;
-use helpers::{extract_name, get_declaration_list, is_public};
-use macros::get_macro_source_code;
-use reexports::extract_symbol_reexports;
-use symbols::get_symbol_source_code;
-
-pub use files::{ImportType, RustFile, RustSymbol};
-
-pub fn parse_rust_file(content: &str, parser: &mut Parser) -> Result<RustFile, ExtractionError> {
+pub use doc_comments::strip_inner_doc_comment_markers;
+use foreign_items::extract_foreign_symbols;
+use helpers::{
+    extract_name, get_attribute_value, get_byte_range_span, get_declaration_list, get_generics,
+    get_source_span, get_visibility, is_doc_hidden, is_public,
+};
+use impls::{get_impl_source_code, get_impl_type_path};
+pub use macros::render_macro_rules;
+use macros::{get_decl_macro, get_macro_source_code};
+use reexports::{extract_extern_crate, extract_symbol_reexports};
+use symbols::{get_enum_variant_names, get_symbol_kind, get_symbol_source_code};
+use syntax_errors::collect_syntax_errors;
+
+pub use cfg::{CfgOptions, CfgPredicate};
+pub use files::{
+    ImportType, IncludePath, RustFile, RustSymbol, SourceSpan, SymbolKind, SyntaxError, Visibility,
+};
+use includes::get_include_path;
+
+/// Parse the content of the Rust source file at `path`, keeping the items relevant to the public
+/// API.
+///
+/// Syntax errors don't cause the parsing to fail, as the parser recovers from them, but are
+/// recorded in the file.
+pub fn parse_rust_file(
+    content: &str,
+    path: &Path,
+    parser: &mut Parser,
+) -> Result<RustFile, ExtractionError> {
     let tree = parser
         .parse(content, None)
         .ok_or_else(|| ExtractionError::Malformed("Failed to parse source file".to_string()))?;
 
-    let doc_comment = extract_inner_doc_comments(&tree.root_node(), content)?;
-    let symbols = extract_symbols_from_module(tree.root_node(), content)?;
+    let directory = path.parent().unwrap_or(Path::new(""));
+    let doc_comment = extract_inner_doc_comments(&tree.root_node(), content, directory)?;
+    let mut decl_macro_ranges = Vec::new();
+    let symbols =
+        extract_symbols_from_module(tree.root_node(), content, directory, &mut decl_macro_ranges)?;
     Ok(RustFile {
+        path: path.to_path_buf(),
+        span: get_source_span(&tree.root_node()),
         doc_comment,
         symbols,
+        // The grammar doesn't support declarative macros 2.0, so their errors are spurious
+        syntax_errors: collect_syntax_errors(&tree.root_node(), &decl_macro_ranges),
     })
 }
 
+/// Extract the symbols of the module at `module_node`, recording the byte ranges of the
+/// declarative macros 2.0 found along the way in `decl_macro_ranges`.
 fn extract_symbols_from_module(
     module_node: Node,
     source_code: &str,
+    directory: &Path,
+    decl_macro_ranges: &mut Vec<Range<usize>>,
 ) -> Result<Vec<RustSymbol>, ExtractionError> {
     let mut symbols = Vec::new();
     let mut cursor = module_node.walk();
 
     for child in module_node.children(&mut cursor) {
+        if decl_macro_ranges
+            .last()
+            .is_some_and(|range| range.contains(&child.start_byte()))
+        {
+            // The body of the last declarative macro 2.0, parsed as unrelated nodes
+            continue;
+        }
         match child.kind() {
-            "function_item" | "struct_item" | "enum_item" | "trait_item" | "type_item"
-            | "const_item" => {
-                if !is_public(&child) {
+            "function_item" | "struct_item" | "union_item" | "enum_item" | "trait_item"
+            | "type_item" | "const_item" | "static_item" => {
+                if !is_public(&child, source_code) {
                     continue;
                 }
                 let name = extract_name(&child, source_code)?;
+                let cfg = extract_cfg_predicate(&child, source_code);
                 symbols.push(RustSymbol::Symbol {
                     symbol: Symbol {
-                        name,
-                        source_code: get_symbol_source_code(child, source_code)?,
+                        name: name.clone(),
+                        source_code: get_symbol_source_code(child, source_code, directory)?,
                     },
+                    kind: get_symbol_kind(child.kind()),
+                    generics: get_generics(&child, source_code),
+                    cfg: cfg.clone(),
+                    is_doc_hidden: is_doc_hidden(&child, source_code)?,
+                    span: get_source_span(&child),
                 });
+                if child.kind() == "enum_item" {
+                    symbols.push(RustSymbol::EnumVariants {
+                        enum_name: name,
+                        variants: get_enum_variant_names(child, source_code)?,
+                        cfg,
+                    });
+                }
             }
             "macro_definition" => {
-                let source_code_opt = get_macro_source_code(child, source_code)?;
-                if let Some(macro_source_code) = source_code_opt {
+                let source_code_opt = get_macro_source_code(child, source_code, directory)?;
+                if let Some((macro_source_code, matchers)) = source_code_opt {
                     let name = extract_name(&child, source_code)?;
-                    symbols.push(RustSymbol::Symbol {
+                    symbols.push(RustSymbol::ExportedMacro {
                         symbol: Symbol {
                             name,
                             source_code: macro_source_code,
                         },
+                        matchers,
+                        cfg: extract_cfg_predicate(&child, source_code),
+                        is_doc_hidden: is_doc_hidden(&child, source_code)?,
+                        span: get_source_span(&child),
                     });
                 }
             }
+            "ERROR" => {
+                symbols.extend(extract_decl_macros(
+                    child,
+                    source_code,
+                    directory,
+                    decl_macro_ranges,
+                )?);
+            }
+            "impl_item" => {
+                let Some(type_path) = get_impl_type_path(child, source_code)? else {
+                    continue;
+                };
+                if let Some(impl_source_code) = get_impl_source_code(child, source_code, directory)?
+                {
+                    symbols.push(RustSymbol::ImplBlock {
+                        type_path,
+                        source_code: impl_source_code,
+                        cfg: extract_cfg_predicate(&child, source_code),
+                    });
+                }
+            }
+            "foreign_mod_item" => {
+                symbols.extend(extract_foreign_symbols(&child, source_code, directory)?);
+            }
+            "extern_crate_declaration" => {
+                symbols.push(extract_extern_crate(&child, source_code)?);
+            }
             "use_declaration" => {
                 symbols.extend(extract_symbol_reexports(&child, source_code)?);
             }
+            "macro_invocation" => {
+                symbols.extend(extract_source_include(&child, &child, source_code));
+            }
+            "expression_statement" => {
+                // Item-level macro invocations followed by `;` are parsed as statements
+                if let Some(invocation) = child
+                    .named_child(0)
+                    .filter(|node| node.kind() == "macro_invocation")
+                {
+                    symbols.extend(extract_source_include(&invocation, &child, source_code));
+                }
+            }
             "mod_item" => {
                 let inner_mod_name = extract_name(&child, source_code)?;
-                let is_public = is_public(&child);
+                let visibility = get_visibility(&child, source_code);
+                let cfg = extract_cfg_predicate(&child, source_code);
+                let is_doc_hidden = is_doc_hidden(&child, source_code)?;
 
                 if let Some(declaration_list) = get_declaration_list(child) {
                     // This is a module block (`mod foo { ... }`)
-                    let doc_comment = extract_inner_doc_comments(&declaration_list, source_code)?;
-                    let inner_mod_symbols =
-                        extract_symbols_from_module(declaration_list, source_code)?;
+                    let doc_comment =
+                        extract_inner_doc_comments(&declaration_list, source_code, directory)?;
+                    let inner_mod_symbols = extract_symbols_from_module(
+                        declaration_list,
+                        source_code,
+                        directory,
+                        decl_macro_ranges,
+                    )?;
                     symbols.push(RustSymbol::ModuleBlock {
                         name: inner_mod_name,
-                        is_public,
+                        visibility,
                         content: inner_mod_symbols,
                         doc_comment,
+                        cfg,
+                        is_doc_hidden,
+                        span: get_source_span(&child),
                     });
                 } else {
                     // This is a module declaration or import (`mod foo;`)
                     symbols.push(RustSymbol::ModuleImport {
                         name: inner_mod_name,
-                        is_reexported: is_public,
+                        visibility,
+                        path: get_attribute_value(&child, "path", source_code)?,
+                        cfg,
+                        is_doc_hidden,
+                        span: get_source_span(&child),
                     });
                 }
             }
@@ -98,17 +215,89 @@ fn extract_symbols_from_module(
     Ok(symbols)
 }
 
+/// Extract the declarative macros 2.0 (e.g. `pub macro foo() {}`) in `error_node`, recording their
+/// byte ranges in `decl_macro_ranges`.
+///
+/// The grammar doesn't support these macros, so they end up in ERROR nodes, several of which may
+/// be lumped into the same node.
+fn extract_decl_macros(
+    error_node: Node,
+    source_code: &str,
+    directory: &Path,
+    decl_macro_ranges: &mut Vec<Range<usize>>,
+) -> Result<Vec<RustSymbol>, ExtractionError> {
+    let mut symbols = Vec::new();
+    let mut cursor = error_node.walk();
+    let candidates = std::iter::once(error_node).chain(
+        error_node
+            .children(&mut cursor)
+            .filter(|node| node.kind() == "visibility_modifier"),
+    );
+    for candidate in candidates {
+        if decl_macro_ranges
+            .last()
+            .is_some_and(|range| range.contains(&candidate.start_byte()))
+        {
+            continue;
+        }
+        let Some(decl_macro) = get_decl_macro(&candidate, source_code, directory)? else {
+            continue;
+        };
+        symbols.push(RustSymbol::Symbol {
+            symbol: Symbol {
+                name: decl_macro.name,
+                source_code: decl_macro.source_code,
+            },
+            kind: SymbolKind::Macro,
+            generics: None,
+            cfg: extract_cfg_predicate(&candidate, source_code),
+            is_doc_hidden: is_doc_hidden(&candidate, source_code)?,
+            span: get_byte_range_span(source_code, &decl_macro.byte_range),
+        });
+        decl_macro_ranges.push(decl_macro.byte_range);
+    }
+    Ok(symbols)
+}
+
+/// Extract the `include!` invocation `invocation_node`, if it is one, along with the attributes of
+/// `item_node` (the invocation or the statement wrapping it).
+fn extract_source_include(
+    invocation_node: &Node,
+    item_node: &Node,
+    source_code: &str,
+) -> Option<RustSymbol> {
+    let macro_node = invocation_node.child_by_field_name("macro")?;
+    if &source_code[macro_node.byte_range()] != "include" {
+        return None;
+    }
+    let invocation = &source_code[invocation_node.byte_range()];
+    let arguments = invocation_node
+        .named_child(1)
+        .map_or("", |node| &source_code[node.byte_range()]);
+    Some(RustSymbol::SourceInclude {
+        path: get_include_path(arguments),
+        source_code: if invocation.ends_with('}') {
+            invocation.to_string()
+        } else {
+            format!("{};", invocation)
+        },
+        cfg: extract_cfg_predicate(item_node, source_code),
+        span: get_source_span(item_node),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_helpers::setup_parser;
+    use assertables::assert_matches;
 
     #[test]
     fn empty_source_file() {
         let source_code = "";
         let mut parser = setup_parser();
 
-        let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
         assert!(rust_file.symbols.is_empty());
     }
@@ -118,9 +307,10 @@ mod tests {
         let source_code = "echo 'Hello, World!'";
         let mut parser = setup_parser();
 
-        let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
         assert!(rust_file.symbols.is_empty());
+        assert!(!rust_file.syntax_errors.is_empty());
     }
 
     #[test]
@@ -130,7 +320,7 @@ pub use other::{One, Two};
 "#;
         let mut parser = setup_parser();
 
-        let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
         assert!(rust_file.get_symbol("One").is_some());
         assert!(rust_file.get_symbol("Two").is_some());
@@ -143,16 +333,81 @@ pub use other::{One, Two};
         "#;
         let mut parser = setup_parser();
 
-        let result = parse_rust_file(source, &mut parser).unwrap();
+        let result = parse_rust_file(source, Path::new("lib.rs"), &mut parser).unwrap();
 
         assert_eq!(result.symbols.len(), 1);
         let symbol = result.get_symbol("THINGY").unwrap();
-        let RustSymbol::Symbol { symbol } = symbol else {
+        let RustSymbol::Symbol { symbol, .. } = symbol else {
             panic!("Expected a symbol")
         };
         assert_eq!(symbol.source_code, "pub const THINGY: usize;");
     }
 
+    #[test]
+    fn static_declaration() {
+        let source_code = r#"
+pub static GREETING: &str = "Hello";
+static PRIVATE: &str = "Hidden";
+"#;
+        let mut parser = setup_parser();
+
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+        assert_eq!(rust_file.symbols.len(), 1);
+        let RustSymbol::Symbol { symbol, .. } = rust_file.get_symbol("GREETING").unwrap() else {
+            panic!("Expected a symbol")
+        };
+        assert_eq!(symbol.source_code, "pub static GREETING: &str;");
+    }
+
+    #[test]
+    fn union_declaration() {
+        let source_code = r#"
+pub union Value {
+    pub int: u32,
+    pub float: f32,
+}
+"#;
+        let mut parser = setup_parser();
+
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+        assert!(rust_file.get_symbol("Value").is_some());
+    }
+
+    #[test]
+    fn enum_declaration() {
+        let source_code = "pub enum Colour { Red, Green }";
+        let mut parser = setup_parser();
+
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+        assert_eq!(rust_file.symbols.len(), 2);
+        assert!(rust_file.get_symbol("Colour").is_some());
+        assert_matches!(
+            &rust_file.symbols[1],
+            RustSymbol::EnumVariants { enum_name, variants, .. }
+                if enum_name == "Colour" && variants == &vec!["Red", "Green"]
+        );
+    }
+
+    #[test]
+    fn extern_block() {
+        let source_code = r#"
+extern "C" {
+    pub fn abs(input: i32) -> i32;
+    pub static environ: *const *const u8;
+}
+"#;
+        let mut parser = setup_parser();
+
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+        assert_eq!(rust_file.symbols.len(), 2);
+        assert!(rust_file.get_symbol("abs").is_some());
+        assert!(rust_file.get_symbol("environ").is_some());
+    }
+
     #[test]
     fn function_declaration() {
         let source_code = r#"
@@ -162,10 +417,10 @@ pub fn test_function() -> i32 {
 "#;
         let mut parser = setup_parser();
 
-        let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
         let symbol = rust_file.get_symbol("test_function").unwrap();
-        let RustSymbol::Symbol { symbol } = symbol else {
+        let RustSymbol::Symbol { symbol, .. } = symbol else {
             panic!("Expected a symbol")
         };
         assert_eq!(symbol.source_code, "pub fn test_function() -> i32;");
@@ -176,11 +431,11 @@ pub fn test_function() -> i32 {
         let source_code = "pub type MaybeString = Option<String>;";
         let mut parser = setup_parser();
 
-        let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
         assert_eq!(rust_file.symbols.len(), 1);
         let symbol = rust_file.get_symbol("MaybeString").unwrap();
-        let RustSymbol::Symbol { symbol } = symbol else {
+        let RustSymbol::Symbol { symbol, .. } = symbol else {
             panic!("Expected a symbol")
         };
         assert_eq!(symbol.source_code, source_code);
@@ -196,15 +451,106 @@ macro_rules! test_macro {
 "#;
         let mut parser = setup_parser();
 
-        let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+        let symbol = rust_file.get_symbol("test_macro").unwrap();
+        let RustSymbol::ExportedMacro {
+            symbol, matchers, ..
+        } = symbol
+        else {
+            panic!("Expected an exported macro")
+        };
+        assert_eq!(
+            symbol.source_code,
+            "#[macro_export]\nmacro_rules! test_macro"
+        );
+        assert_eq!(matchers, &vec!["()".to_string()]);
+    }
+
+    #[test]
+    fn decl_macro_declaration() {
+        let source_code = r#"
+#[cfg(unix)]
+pub macro test_macro($x:expr) {
+    $x
+}
+"#;
+        let mut parser = setup_parser();
+
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
         let symbol = rust_file.get_symbol("test_macro").unwrap();
-        let RustSymbol::Symbol { symbol } = symbol else {
+        let RustSymbol::Symbol { symbol, cfg, .. } = symbol else {
+            panic!("Expected a symbol")
+        };
+        assert_eq!(symbol.source_code, "pub macro test_macro($x:expr);");
+        assert!(cfg.is_some());
+    }
+
+    #[test]
+    fn decl_macro_after_item() {
+        let source_code = r#"pub fn before() {}
+pub macro test_macro($x:expr) { $x }
+pub fn after() {}
+"#;
+        let mut parser = setup_parser();
+
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+        assert_eq!(rust_file.syntax_errors, Vec::new());
+        let Some(RustSymbol::Symbol { symbol, span, .. }) = rust_file.get_symbol("test_macro")
+        else {
+            panic!("Expected a symbol")
+        };
+        assert_eq!(symbol.source_code, "pub macro test_macro($x:expr);");
+        assert_eq!((span.start_line, span.end_line), (2, 2));
+        assert!(rust_file.get_symbol("after").is_some());
+    }
+
+    #[test]
+    fn decl_macro_rules() {
+        let source_code = r#"pub macro test_macro {
+    ($a:tt) => { $a },
+    ($a:tt, $b:tt) => { ($a, $b) },
+}
+"#;
+        let mut parser = setup_parser();
+
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+        assert_eq!(rust_file.syntax_errors, Vec::new());
+        let Some(RustSymbol::Symbol { symbol, .. }) = rust_file.get_symbol("test_macro") else {
             panic!("Expected a symbol")
         };
         assert_eq!(
             symbol.source_code,
-            "#[macro_export]\nmacro_rules! test_macro;"
+            "pub macro test_macro {\n    ($a:tt) => { ... },\n    ($a:tt, $b:tt) => { ... },\n}"
+        );
+    }
+
+    #[test]
+    fn impl_block() {
+        let source_code = r#"
+pub struct Foo;
+
+impl Foo {
+    pub fn new() -> Self {
+        Foo
+    }
+}
+"#;
+        let mut parser = setup_parser();
+
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+        assert_eq!(rust_file.symbols.len(), 2);
+        assert_eq!(
+            rust_file.symbols[1],
+            RustSymbol::ImplBlock {
+                type_path: "Foo".to_string(),
+                source_code: "impl Foo {\n    pub fn new() -> Self;\n}".to_string(),
+                cfg: None,
+            }
         );
     }
 
@@ -215,7 +561,21 @@ fn private_function() {}
 "#;
         let mut parser = setup_parser();
 
-        let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+        assert_eq!(rust_file.symbols.len(), 0);
+    }
+
+    #[test]
+    fn restricted_symbols() {
+        let source_code = r#"
+pub(crate) fn crate_function() {}
+pub(super) struct SuperStruct;
+pub(in crate::outer) enum PathEnum {}
+"#;
+        let mut parser = setup_parser();
+
+        let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
         assert_eq!(rust_file.symbols.len(), 0);
     }
@@ -234,13 +594,13 @@ pub mod inner {
 "#;
             let mut parser = setup_parser();
 
-            let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
             let module = rust_file.get_symbol("inner").unwrap();
             assert_matches!(
                 module,
                 RustSymbol::ModuleBlock {
-                    is_public: true,
+                    visibility: Visibility::Public,
                     ..
                 }
             );
@@ -257,14 +617,35 @@ mod private {
 "#;
             let mut parser = setup_parser();
 
-            let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
             assert_eq!(rust_file.symbols.len(), 1);
             let module = rust_file.get_symbol("private").unwrap();
             assert_matches!(
                 module,
                 RustSymbol::ModuleBlock {
-                    is_public: false,
+                    visibility: Visibility::Private,
+                    ..
+                }
+            );
+        }
+
+        #[test]
+        fn restricted_modules() {
+            let source_code = r#"
+pub(crate) mod restricted {
+    pub fn function() -> String {}
+}
+"#;
+            let mut parser = setup_parser();
+
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+            let module = rust_file.get_symbol("restricted").unwrap();
+            assert_matches!(
+                module,
+                RustSymbol::ModuleBlock {
+                    visibility: Visibility::Restricted,
                     ..
                 }
             );
@@ -277,7 +658,7 @@ pub mod empty {}
 "#;
             let mut parser = setup_parser();
 
-            let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
             let empty_content = rust_file.get_module("empty").unwrap();
             assert_eq!(rust_file.symbols.len(), 1);
@@ -297,7 +678,7 @@ pub mod inner {
 "#;
             let mut parser = setup_parser();
 
-            let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
             let deeper_enum = rust_file.get_symbol("inner::deeper::DeeperEnum").unwrap();
             assert!(matches!(deeper_enum, RustSymbol::Symbol { .. }));
@@ -310,7 +691,7 @@ pub mod other;
 "#;
             let mut parser = setup_parser();
 
-            let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
             let module_declaration = rust_file.get_symbol("other").unwrap();
             assert!(matches!(
@@ -318,6 +699,217 @@ pub mod other;
                 RustSymbol::ModuleImport { .. }
             ));
         }
+
+        #[test]
+        fn module_declaration_with_path() {
+            let source_code = r#"
+#[path = "platform/unix.rs"]
+mod sys;
+"#;
+            let mut parser = setup_parser();
+
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+            let module_declaration = rust_file.get_symbol("sys").unwrap();
+            assert_matches!(
+                module_declaration,
+                RustSymbol::ModuleImport { path: Some(path), .. } if path == "platform/unix.rs"
+            );
+        }
+
+        #[test]
+        fn module_declaration_with_cfg() {
+            let source_code = r#"
+#[cfg(unix)]
+mod sys;
+"#;
+            let mut parser = setup_parser();
+
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+            let module_declaration = rust_file.get_symbol("sys").unwrap();
+            assert_matches!(
+                module_declaration.cfg(),
+                Some(CfgPredicate::Option { name, value: None }) if name == "unix"
+            );
+        }
+    }
+
+    mod doc_hidden {
+        use super::*;
+        use assertables::assert_matches;
+
+        #[test]
+        fn hidden_symbol() {
+            let source_code = r#"
+#[doc(hidden)]
+pub fn hidden() {}
+pub fn visible() {}
+"#;
+            let mut parser = setup_parser();
+
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+            assert_matches!(
+                rust_file.get_symbol("hidden").unwrap(),
+                RustSymbol::Symbol {
+                    is_doc_hidden: true,
+                    ..
+                }
+            );
+            assert_matches!(
+                rust_file.get_symbol("visible").unwrap(),
+                RustSymbol::Symbol {
+                    is_doc_hidden: false,
+                    ..
+                }
+            );
+        }
+
+        #[test]
+        fn hidden_module() {
+            let source_code = r#"
+#[doc(hidden)]
+pub mod __private {}
+#[doc(hidden)]
+pub mod imported;
+"#;
+            let mut parser = setup_parser();
+
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+            assert_matches!(
+                rust_file.get_symbol("__private").unwrap(),
+                RustSymbol::ModuleBlock {
+                    is_doc_hidden: true,
+                    ..
+                }
+            );
+            assert_matches!(
+                rust_file.get_symbol("imported").unwrap(),
+                RustSymbol::ModuleImport {
+                    is_doc_hidden: true,
+                    ..
+                }
+            );
+        }
+
+        #[test]
+        fn hidden_reexport() {
+            let source_code = r#"
+#[doc(hidden)]
+pub use inner::{One, Two};
+"#;
+            let mut parser = setup_parser();
+
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+            assert_eq!(rust_file.symbols.len(), 2);
+            for symbol in &rust_file.symbols {
+                assert_matches!(
+                    symbol,
+                    RustSymbol::Reexport {
+                        is_doc_hidden: true,
+                        ..
+                    }
+                );
+            }
+        }
+    }
+
+    mod source_includes {
+        use super::*;
+
+        #[test]
+        fn relative_include() {
+            let source_code = r#"include!("bindings.rs");"#;
+            let mut parser = setup_parser();
+
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+            assert_eq!(
+                rust_file.symbols,
+                vec![RustSymbol::SourceInclude {
+                    path: Some(IncludePath::Relative("bindings.rs".to_string())),
+                    source_code: source_code.to_string(),
+                    cfg: None,
+                    span: SourceSpan {
+                        start_line: 1,
+                        start_column: 1,
+                        end_line: 1,
+                        end_column: 25,
+                    },
+                }]
+            );
+        }
+
+        #[test]
+        fn out_dir_include_with_cfg() {
+            let source_code = r#"
+#[cfg(unix)]
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+"#;
+            let mut parser = setup_parser();
+
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+            assert_eq!(rust_file.symbols.len(), 1);
+            assert_matches!(
+                &rust_file.symbols[0],
+                RustSymbol::SourceInclude {
+                    path: Some(IncludePath::OutDir(path)),
+                    cfg: Some(CfgPredicate::Option { name, value: None }),
+                    ..
+                } if path == "/bindings.rs" && name == "unix"
+            );
+        }
+
+        #[test]
+        fn include_inside_module_block() {
+            let source_code = r#"
+pub mod generated {
+    include!("bindings.rs");
+}
+"#;
+            let mut parser = setup_parser();
+
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+            let content = rust_file.get_module("generated").unwrap();
+            assert_matches!(
+                content,
+                [RustSymbol::SourceInclude {
+                    source_code,
+                    ..
+                }] if source_code == r#"include!("bindings.rs");"#
+            );
+        }
+
+        #[test]
+        fn unsupported_include_path() {
+            let source_code = r#"include!(concat!(env!("GENERATED_DIR"), "/bindings.rs"));"#;
+            let mut parser = setup_parser();
+
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+            assert_matches!(
+                &rust_file.symbols[0],
+                RustSymbol::SourceInclude { path: None, .. }
+            );
+        }
+
+        #[test]
+        fn other_macro_invocation() {
+            let source_code = r#"
+lazy_static! { static ref FOO: u8 = 1; }
+println!("Hello");
+"#;
+            let mut parser = setup_parser();
+
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+
+            assert!(rust_file.symbols.is_empty());
+        }
     }
 
     mod doc_comments {
@@ -330,7 +922,7 @@ pub struct Test {}
 "#;
             let mut parser = setup_parser();
 
-            let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
             assert!(rust_file.doc_comment.is_none());
         }
@@ -343,7 +935,7 @@ pub struct Test {}
 "#;
             let mut parser = setup_parser();
 
-            let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
             assert_eq!(
                 rust_file.doc_comment,
@@ -359,10 +951,10 @@ pub struct Test {}
 "#;
             let mut parser = setup_parser();
 
-            let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
             let symbol = rust_file.get_symbol("Test").unwrap();
-            let RustSymbol::Symbol { symbol } = symbol else {
+            let RustSymbol::Symbol { symbol, .. } = symbol else {
                 panic!("Expected a symbol")
             };
             assert_eq!(
@@ -380,14 +972,14 @@ pub struct Test {}
 "#;
             let mut parser = setup_parser();
 
-            let rust_file = parse_rust_file(source_code, &mut parser).unwrap();
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
 
             assert_eq!(
                 rust_file.doc_comment,
                 Some("//! File-level documentation\n".to_string())
             );
             let symbol = rust_file.get_symbol("Test").unwrap();
-            let RustSymbol::Symbol { symbol } = symbol else {
+            let RustSymbol::Symbol { symbol, .. } = symbol else {
                 panic!("Expected a symbol")
             };
             assert_eq!(
@@ -396,4 +988,151 @@ pub struct Test {}
             );
         }
     }
+
+    mod symbol_kinds {
+        use super::*;
+
+        fn get_kind_and_generics(source_code: &str, name: &str) -> (SymbolKind, Option<String>) {
+            let mut parser = setup_parser();
+            let rust_file = parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap();
+            let Some(RustSymbol::Symbol { kind, generics, .. }) = rust_file.get_symbol(name) else {
+                panic!("Expected a symbol named {}", name)
+            };
+            (*kind, generics.clone())
+        }
+
+        #[test]
+        fn generic_function() {
+            let (kind, generics) = get_kind_and_generics("pub fn foo<T>(value: T) {}", "foo");
+
+            assert_eq!(kind, SymbolKind::Function);
+            assert_eq!(generics, Some("<T>".to_string()));
+        }
+
+        #[test]
+        fn generic_struct() {
+            let (kind, generics) =
+                get_kind_and_generics("pub struct Foo<'a> { value: &'a str }", "Foo");
+
+            assert_eq!(kind, SymbolKind::Struct);
+            assert_eq!(generics, Some("<'a>".to_string()));
+        }
+
+        #[test]
+        fn enum_item() {
+            let (kind, generics) = get_kind_and_generics("pub enum Foo { A, B }", "Foo");
+
+            assert_eq!(kind, SymbolKind::Enum);
+            assert_eq!(generics, None);
+        }
+
+        #[test]
+        fn trait_item() {
+            let (kind, _) = get_kind_and_generics("pub trait Foo {}", "Foo");
+
+            assert_eq!(kind, SymbolKind::Trait);
+        }
+
+        #[test]
+        fn type_alias() {
+            let (kind, generics) = get_kind_and_generics("pub type Foo<T> = Vec<T>;", "Foo");
+
+            assert_eq!(kind, SymbolKind::TypeAlias);
+            assert_eq!(generics, Some("<T>".to_string()));
+        }
+
+        #[test]
+        fn const_and_static() {
+            let source_code = "pub const FOO: u8 = 1;\npub static BAR: u8 = 2;";
+
+            assert_eq!(
+                get_kind_and_generics(source_code, "FOO").0,
+                SymbolKind::Const
+            );
+            assert_eq!(
+                get_kind_and_generics(source_code, "BAR").0,
+                SymbolKind::Static
+            );
+        }
+
+        #[test]
+        fn macro_definition() {
+            let source_code = "pub macro foo() {}";
+
+            let (kind, _) = get_kind_and_generics(source_code, "foo");
+
+            assert_eq!(kind, SymbolKind::Macro);
+        }
+
+        #[test]
+        fn foreign_function() {
+            let source_code = "extern \"C\" { pub fn foo(); }";
+
+            let (kind, _) = get_kind_and_generics(source_code, "foo");
+
+            assert_eq!(kind, SymbolKind::Function);
+        }
+    }
+
+    mod source_spans {
+        use super::*;
+
+        fn parse(source_code: &str) -> RustFile {
+            let mut parser = setup_parser();
+            parse_rust_file(source_code, Path::new("lib.rs"), &mut parser).unwrap()
+        }
+
+        #[test]
+        fn symbol() {
+            let rust_file = parse("\npub fn foo() {}");
+
+            let Some(RustSymbol::Symbol { span, .. }) = rust_file.get_symbol("foo") else {
+                panic!("Expected a symbol named foo")
+            };
+            assert_eq!((span.start_line, span.start_column), (2, 1));
+            assert_eq!((span.end_line, span.end_column), (2, 16));
+        }
+
+        #[test]
+        fn reexports() {
+            let rust_file = parse("pub use inner::{One, Two};");
+
+            let spans: Vec<_> = rust_file
+                .symbols
+                .iter()
+                .map(|symbol| match symbol {
+                    RustSymbol::Reexport { span, .. } => *span,
+                    _ => panic!("Expected a reexport"),
+                })
+                .collect();
+            assert_eq!(spans.len(), 2);
+            assert!(spans
+                .iter()
+                .all(|span| span.start_column == 1 && span.end_column == 27));
+        }
+
+        #[test]
+        fn module_block() {
+            let rust_file = parse("pub mod inner {\n    pub fn foo() {}\n}");
+
+            assert_matches!(
+                rust_file.symbols.as_slice(),
+                [RustSymbol::ModuleBlock { span, content, .. }]
+                    if span.start_line == 1
+                        && span.end_line == 3
+                        && matches!(
+                            content.as_slice(),
+                            [RustSymbol::Symbol { span, .. }] if span.start_line == 2
+                        )
+            );
+        }
+
+        #[test]
+        fn file() {
+            let rust_file = parse("pub fn foo() {}\n");
+
+            assert_eq!(rust_file.path, Path::new("lib.rs"));
+            assert_eq!((rust_file.span.start_line, rust_file.span.end_line), (1, 2));
+        }
+    }
 }
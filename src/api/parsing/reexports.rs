@@ -1,163 +1,201 @@
-use super::files::{ImportType, RustSymbol};
-use super::helpers::is_public;
+use super::cfg::{extract_cfg_predicate, CfgPredicate};
+use super::files::{ImportType, RustSymbol, SourceSpan};
+use super::helpers::{get_source_span, get_visibility, is_doc_hidden, is_public};
 use daipendency_extractor::ExtractionError;
 use tree_sitter::Node;
 
+/// Extract the reexports of a `use` declaration, or its imports if it isn't public.
 pub fn extract_symbol_reexports(
     use_declaration_node: &Node,
     source_code: &str,
 ) -> Result<Vec<RustSymbol>, ExtractionError> {
-    if !is_public(use_declaration_node) {
-        return Ok(Vec::new());
-    }
-
-    let mut cursor = use_declaration_node.walk();
-    let children: Vec<_> = use_declaration_node.children(&mut cursor).collect();
-
-    let result = if let Some(scoped) = children.iter().find(|c| c.kind() == "scoped_identifier") {
-        extract_single_reexport(scoped, source_code)
-    } else if let Some(use_as) = children.iter().find(|c| c.kind() == "use_as_clause") {
-        extract_renamed_reexport(use_as, source_code)
-    } else if let Some(scoped_list) = children.iter().find(|c| c.kind() == "scoped_use_list") {
-        extract_multi_reexports(scoped_list, source_code)
-    } else if let Some(wildcard) = children.iter().find(|c| c.kind() == "use_wildcard") {
-        extract_wildcard_reexport(wildcard, source_code)
-    } else if let Some(identifier) = children.iter().find(|c| c.kind() == "identifier") {
-        extract_external_crate_reexport(identifier, source_code)
-    } else {
-        Err(ExtractionError::Malformed(format!(
+    let result = match use_declaration_node.child_by_field_name("argument") {
+        Some(use_tree) => flatten_use_tree(&use_tree, "", source_code),
+        None => Err(ExtractionError::Malformed(format!(
             "Failed to find symbol reexport: {}",
             use_declaration_node
                 .utf8_text(source_code.as_bytes())
                 .unwrap()
-        )))
+        ))),
     };
 
-    result.map(normalize_raw_identifiers)
+    let cfg = extract_cfg_predicate(use_declaration_node, source_code);
+    if !is_public(use_declaration_node, source_code) {
+        return result.map(|symbols| convert_to_imports(normalize_raw_identifiers(symbols), cfg));
+    }
+    let is_doc_hidden = is_doc_hidden(use_declaration_node, source_code)?;
+    let span = get_source_span(use_declaration_node);
+    result.map(|symbols| {
+        apply_use_declaration_attributes(
+            normalize_raw_identifiers(symbols),
+            cfg,
+            is_doc_hidden,
+            span,
+        )
+    })
 }
 
-fn extract_external_crate_reexport(
-    identifier: &Node,
+/// Extract an external crate declaration (e.g. `extern crate foo as bar;`).
+pub fn extract_extern_crate(
+    extern_crate_node: &Node,
     source_code: &str,
-) -> Result<Vec<RustSymbol>, ExtractionError> {
-    let source_path = identifier
-        .utf8_text(source_code.as_bytes())
-        .map_err(|e| ExtractionError::Malformed(e.to_string()))?
-        .to_string();
-    Ok(vec![RustSymbol::Reexport {
-        source_path,
-        import_type: ImportType::Simple,
-    }])
+) -> Result<RustSymbol, ExtractionError> {
+    let name = extern_crate_node
+        .child_by_field_name("name")
+        .ok_or_else(|| ExtractionError::Malformed("No crate name found".to_string()))?;
+    let alias = extern_crate_node
+        .child_by_field_name("alias")
+        .map(|alias| get_path_text(&alias, source_code))
+        .transpose()?;
+    Ok(RustSymbol::ExternCrate {
+        name: get_path_text(&name, source_code)?
+            .trim_start_matches("r#")
+            .to_string(),
+        alias: alias.map(|alias| alias.trim_start_matches("r#").to_string()),
+        visibility: get_visibility(extern_crate_node, source_code),
+        cfg: extract_cfg_predicate(extern_crate_node, source_code),
+        is_doc_hidden: is_doc_hidden(extern_crate_node, source_code)?,
+        span: get_source_span(extern_crate_node),
+    })
 }
 
-fn extract_wildcard_reexport(
-    wildcard: &Node,
-    source_code: &str,
-) -> Result<Vec<RustSymbol>, ExtractionError> {
-    let mut cursor = wildcard.walk();
-    let children: Vec<_> = wildcard.children(&mut cursor).collect();
-
-    let module_path = children
-        .iter()
-        .find(|c| c.kind() == "identifier" || c.kind() == "scoped_identifier")
-        .ok_or_else(|| {
-            ExtractionError::Malformed(format!(
-                "Failed to find module path in wildcard import: {}",
-                wildcard
-                    .utf8_text(source_code.as_bytes())
-                    .unwrap_or_default()
-            ))
-        })?
-        .utf8_text(source_code.as_bytes())
-        .map_err(|e| ExtractionError::Malformed(e.to_string()))?;
-
-    Ok(vec![RustSymbol::Reexport {
-        source_path: module_path.to_string(),
-        import_type: ImportType::Wildcard,
-    }])
+/// Apply the attributes (e.g. `#[cfg(unix)]`) and span of a use declaration to each of its
+/// reexports.
+fn apply_use_declaration_attributes(
+    symbols: Vec<RustSymbol>,
+    cfg: Option<CfgPredicate>,
+    is_doc_hidden: bool,
+    span: SourceSpan,
+) -> Vec<RustSymbol> {
+    symbols
+        .into_iter()
+        .map(|symbol| match symbol {
+            RustSymbol::Reexport {
+                source_path,
+                import_type,
+                ..
+            } => RustSymbol::Reexport {
+                source_path,
+                import_type,
+                cfg: cfg.clone(),
+                is_doc_hidden,
+                span,
+            },
+            other => other,
+        })
+        .collect()
 }
 
-fn extract_single_reexport(
-    scoped: &Node,
-    source_code: &str,
-) -> Result<Vec<RustSymbol>, ExtractionError> {
-    let mut cursor = scoped.walk();
-    let source_path = scoped
-        .children(&mut cursor)
-        .map(|child| {
-            child
-                .utf8_text(source_code.as_bytes())
-                .map_err(|e| ExtractionError::Malformed(e.to_string()))
+/// Turn the reexports of a private `use` declaration into imports.
+fn convert_to_imports(symbols: Vec<RustSymbol>, cfg: Option<CfgPredicate>) -> Vec<RustSymbol> {
+    symbols
+        .into_iter()
+        .map(|symbol| match symbol {
+            RustSymbol::Reexport {
+                source_path,
+                import_type,
+                ..
+            } => RustSymbol::Import {
+                source_path,
+                import_type,
+                cfg: cfg.clone(),
+            },
+            other => other,
         })
-        .collect::<Result<Vec<_>, _>>()?
-        .join("");
-    Ok(vec![RustSymbol::Reexport {
-        source_path,
-        import_type: ImportType::Simple,
-    }])
+        .collect()
 }
 
-fn extract_renamed_reexport(
-    use_as: &Node,
+/// Flatten a use tree (e.g. `foo::{bar::Baz, Qux as Q, self, inner::*}`) into one reexport per
+/// leaf, with `prefix` being the path of the enclosing use lists.
+fn flatten_use_tree(
+    use_tree: &Node,
+    prefix: &str,
     source_code: &str,
 ) -> Result<Vec<RustSymbol>, ExtractionError> {
-    let mut cursor = use_as.walk();
-    let children: Vec<_> = use_as.children(&mut cursor).collect();
-
-    let source_path = children
-        .first()
-        .ok_or_else(|| ExtractionError::Malformed("Empty use_as clause".to_string()))?
-        .utf8_text(source_code.as_bytes())
-        .map_err(|e| ExtractionError::Malformed(e.to_string()))?
-        .to_string();
-
-    let alias = children
-        .iter()
-        .find(|c| c.kind() == "identifier")
-        .ok_or_else(|| ExtractionError::Malformed("No alias found in use_as clause".to_string()))?
-        .utf8_text(source_code.as_bytes())
-        .map_err(|e| ExtractionError::Malformed(e.to_string()))?
-        .to_string();
-
-    Ok(vec![RustSymbol::Reexport {
+    match use_tree.kind() {
+        "use_list" => {
+            let mut cursor = use_tree.walk();
+            let mut reexports = Vec::new();
+            for item in use_tree.named_children(&mut cursor) {
+                reexports.extend(flatten_use_tree(&item, prefix, source_code)?);
+            }
+            Ok(reexports)
+        }
+        "scoped_use_list" => {
+            let list = use_tree
+                .child_by_field_name("list")
+                .ok_or_else(|| ExtractionError::Malformed("No use list found".to_string()))?;
+            // The path is absent in lists like `::{foo, bar}`
+            let list_prefix = match use_tree.child_by_field_name("path") {
+                Some(path) => join_path(prefix, &get_path_text(&path, source_code)?),
+                None => prefix.to_string(),
+            };
+            flatten_use_tree(&list, &list_prefix, source_code)
+        }
+        "use_as_clause" => {
+            let path = use_tree
+                .child_by_field_name("path")
+                .ok_or_else(|| ExtractionError::Malformed("Empty use_as clause".to_string()))?;
+            let alias = use_tree.child_by_field_name("alias").ok_or_else(|| {
+                ExtractionError::Malformed("No alias found in use_as clause".to_string())
+            })?;
+            Ok(vec![make_reexport(
+                resolve_leaf_path(prefix, &get_path_text(&path, source_code)?),
+                ImportType::Aliased(get_path_text(&alias, source_code)?),
+            )])
+        }
+        "use_wildcard" => {
+            let mut cursor = use_tree.walk();
+            let module_path = use_tree
+                .named_children(&mut cursor)
+                .next()
+                .map(|path| get_path_text(&path, source_code))
+                .transpose()?;
+            let source_path = match module_path {
+                Some(module_path) => join_path(prefix, &module_path),
+                // A glob without a path (e.g. `{*}` in a use list)
+                None => prefix.to_string(),
+            };
+            Ok(vec![make_reexport(source_path, ImportType::Wildcard)])
+        }
+        _ => Ok(vec![make_reexport(
+            resolve_leaf_path(prefix, &get_path_text(use_tree, source_code)?),
+            ImportType::Simple,
+        )]),
+    }
+}
+
+fn make_reexport(source_path: String, import_type: ImportType) -> RustSymbol {
+    RustSymbol::Reexport {
         source_path,
-        import_type: ImportType::Aliased(alias),
-    }])
+        import_type,
+        cfg: None,
+        is_doc_hidden: false,
+        span: SourceSpan::default(),
+    }
 }
 
-fn extract_multi_reexports(
-    scoped_list: &Node,
-    source_code: &str,
-) -> Result<Vec<RustSymbol>, ExtractionError> {
-    let mut scoped_cursor = scoped_list.walk();
-    let scoped_children: Vec<_> = scoped_list.children(&mut scoped_cursor).collect();
-
-    let path_prefix = scoped_children
-        .first()
-        .ok_or_else(|| ExtractionError::Malformed("Empty scoped list".to_string()))?
-        .utf8_text(source_code.as_bytes())
-        .map_err(|e| ExtractionError::Malformed(e.to_string()))?
-        .to_string();
-
-    let use_list = scoped_children
-        .iter()
-        .find(|c| c.kind() == "use_list")
-        .ok_or_else(|| ExtractionError::Malformed("No use list found".to_string()))?;
-
-    let mut list_cursor = use_list.walk();
-    use_list
-        .children(&mut list_cursor)
-        .filter(|item| item.kind() == "identifier")
-        .map(|item| {
-            let name = item
-                .utf8_text(source_code.as_bytes())
-                .map_err(|e| ExtractionError::Malformed(e.to_string()))?;
-            Ok(RustSymbol::Reexport {
-                source_path: format!("{}::{}", path_prefix, name),
-                import_type: ImportType::Simple,
-            })
-        })
-        .collect()
+/// Resolve the path of a leaf in a use list, where `self` refers to the list's prefix.
+fn resolve_leaf_path(prefix: &str, path: &str) -> String {
+    if path == "self" && !prefix.is_empty() {
+        prefix.to_string()
+    } else {
+        join_path(prefix, path)
+    }
+}
+
+fn join_path(prefix: &str, path: &str) -> String {
+    if prefix.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}::{}", prefix, path)
+    }
+}
+
+fn get_path_text(path: &Node, source_code: &str) -> Result<String, ExtractionError> {
+    path.utf8_text(source_code.as_bytes())
+        .map(|text| text.chars().filter(|c| !c.is_whitespace()).collect())
+        .map_err(|e| ExtractionError::Malformed(e.to_string()))
 }
 
 fn normalize_raw_identifiers(symbols: Vec<RustSymbol>) -> Vec<RustSymbol> {
@@ -167,6 +205,9 @@ fn normalize_raw_identifiers(symbols: Vec<RustSymbol>) -> Vec<RustSymbol> {
             RustSymbol::Reexport {
                 source_path,
                 import_type,
+                cfg,
+                is_doc_hidden,
+                span,
             } => {
                 let normalized_path = source_path
                     .split("::")
@@ -184,6 +225,9 @@ fn normalize_raw_identifiers(symbols: Vec<RustSymbol>) -> Vec<RustSymbol> {
                 RustSymbol::Reexport {
                     source_path: normalized_path,
                     import_type: normalized_type,
+                    cfg,
+                    is_doc_hidden,
+                    span,
                 }
             }
             other => other,
@@ -235,7 +279,7 @@ mod tests {
         assert_eq!(symbols.len(), 1);
         assert_matches!(
             &symbols[0],
-            RustSymbol::Reexport { source_path, import_type: ImportType::Simple } if source_path == "serde_json"
+            RustSymbol::Reexport { source_path, import_type: ImportType::Simple, .. } if source_path == "serde_json"
         );
     }
 
@@ -249,7 +293,28 @@ use inner::Format;
 
         let symbols = extract_symbol_reexports(&use_declaration, source_code).unwrap();
 
-        assert!(symbols.is_empty());
+        assert_eq!(symbols.len(), 1);
+        assert_matches!(
+            &symbols[0],
+            RustSymbol::Import { source_path, import_type: ImportType::Simple, .. } if source_path == "inner::Format"
+        );
+    }
+
+    #[test]
+    fn restricted_reexport() {
+        let source_code = r#"
+pub(crate) use inner::Format;
+"#;
+        let tree = make_tree(source_code);
+        let use_declaration = find_child_node(tree.root_node(), "use_declaration");
+
+        let symbols = extract_symbol_reexports(&use_declaration, source_code).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        assert_matches!(
+            &symbols[0],
+            RustSymbol::Import { source_path, import_type: ImportType::Simple, .. } if source_path == "inner::Format"
+        );
     }
 
     #[test]
@@ -281,7 +346,8 @@ pub use inner::Format;
             &symbols[0],
             RustSymbol::Reexport {
                 source_path,
-                import_type: ImportType::Aliased(alias)
+                import_type: ImportType::Aliased(alias),
+                ..
             } if source_path == "inner::Foo" && alias == "Bar"
         );
     }
@@ -317,6 +383,7 @@ pub use inner::*;
             RustSymbol::Reexport {
                 source_path,
                 import_type: ImportType::Wildcard,
+                ..
             } if source_path == "inner"
         );
     }
@@ -337,10 +404,137 @@ pub use crate::inner::*;
             RustSymbol::Reexport {
                 source_path,
                 import_type: ImportType::Wildcard,
+                ..
             } if source_path == "crate::inner"
         );
     }
 
+    mod use_trees {
+        use super::*;
+
+        fn extract_leaves(source_code: &str) -> Vec<(String, ImportType)> {
+            let tree = make_tree(source_code);
+            let use_declaration = find_child_node(tree.root_node(), "use_declaration");
+            extract_symbol_reexports(&use_declaration, source_code)
+                .unwrap()
+                .into_iter()
+                .map(|symbol| match symbol {
+                    RustSymbol::Reexport {
+                        source_path,
+                        import_type,
+                        ..
+                    } => (source_path, import_type),
+                    other => panic!("Expected a reexport, got {:?}", other),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn nested_path() {
+            let leaves = extract_leaves("pub use foo::{bar::Baz};");
+
+            assert_eq!(
+                leaves,
+                vec![("foo::bar::Baz".to_string(), ImportType::Simple)]
+            );
+        }
+
+        #[test]
+        fn alias() {
+            let leaves = extract_leaves("pub use foo::{Qux as Q};");
+
+            assert_eq!(
+                leaves,
+                vec![("foo::Qux".to_string(), ImportType::Aliased("Q".to_string()))]
+            );
+        }
+
+        #[test]
+        fn self_import() {
+            let leaves = extract_leaves("pub use foo::{self};");
+
+            assert_eq!(leaves, vec![("foo".to_string(), ImportType::Simple)]);
+        }
+
+        #[test]
+        fn aliased_self_import() {
+            let leaves = extract_leaves("pub use foo::{self as bar};");
+
+            assert_eq!(
+                leaves,
+                vec![("foo".to_string(), ImportType::Aliased("bar".to_string()))]
+            );
+        }
+
+        #[test]
+        fn glob() {
+            let leaves = extract_leaves("pub use foo::{inner::*};");
+
+            assert_eq!(
+                leaves,
+                vec![("foo::inner".to_string(), ImportType::Wildcard)]
+            );
+        }
+
+        #[test]
+        fn nested_list() {
+            let leaves = extract_leaves("pub use foo::{deep::{A, B as C}};");
+
+            assert_eq!(
+                leaves,
+                vec![
+                    ("foo::deep::A".to_string(), ImportType::Simple),
+                    (
+                        "foo::deep::B".to_string(),
+                        ImportType::Aliased("C".to_string())
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn list_without_prefix() {
+            let leaves = extract_leaves("pub use {a::X, b::Y};");
+
+            assert_eq!(
+                leaves,
+                vec![
+                    ("a::X".to_string(), ImportType::Simple),
+                    ("b::Y".to_string(), ImportType::Simple),
+                ]
+            );
+        }
+
+        #[test]
+        fn mixed_list() {
+            let leaves =
+                extract_leaves("pub use crate::foo::{bar::Baz, Qux as Q, self, inner::*};");
+
+            assert_eq!(
+                leaves,
+                vec![
+                    ("crate::foo::bar::Baz".to_string(), ImportType::Simple),
+                    (
+                        "crate::foo::Qux".to_string(),
+                        ImportType::Aliased("Q".to_string())
+                    ),
+                    ("crate::foo".to_string(), ImportType::Simple),
+                    ("crate::foo::inner".to_string(), ImportType::Wildcard),
+                ]
+            );
+        }
+
+        #[test]
+        fn whitespace_in_paths() {
+            let leaves = extract_leaves("pub use foo :: {\n    bar :: Baz,\n};");
+
+            assert_eq!(
+                leaves,
+                vec![("foo::bar::Baz".to_string(), ImportType::Simple)]
+            );
+        }
+    }
+
     mod raw_identifiers {
         use super::*;
 
@@ -355,7 +549,7 @@ pub use crate::inner::*;
             assert_eq!(symbols.len(), 1);
             assert_matches!(
                 &symbols[0],
-                RustSymbol::Reexport { source_path, import_type: ImportType::Simple } if source_path == "type"
+                RustSymbol::Reexport { source_path, import_type: ImportType::Simple, .. } if source_path == "type"
             );
         }
 
@@ -370,7 +564,7 @@ pub use crate::inner::*;
             assert_eq!(symbols.len(), 1);
             assert_matches!(
                 &symbols[0],
-                RustSymbol::Reexport { source_path, import_type: ImportType::Simple } if source_path == "submodule::fn"
+                RustSymbol::Reexport { source_path, import_type: ImportType::Simple, .. } if source_path == "submodule::fn"
             );
         }
 
@@ -385,7 +579,7 @@ pub use crate::inner::*;
             assert_eq!(symbols.len(), 1);
             assert_matches!(
                 &symbols[0],
-                RustSymbol::Reexport { source_path, import_type: ImportType::Aliased(alias) }
+                RustSymbol::Reexport { source_path, import_type: ImportType::Aliased(alias), .. }
                 if source_path == "submodule::the_type" && alias == "type"
             );
         }
@@ -401,7 +595,7 @@ pub use crate::inner::*;
             assert_eq!(symbols.len(), 1);
             assert_matches!(
                 &symbols[0],
-                RustSymbol::Reexport { source_path, import_type: ImportType::Aliased(alias) }
+                RustSymbol::Reexport { source_path, import_type: ImportType::Aliased(alias), .. }
                 if source_path == "type::Foo" && alias == "Bar"
             );
         }
@@ -432,4 +626,54 @@ pub use crate::inner::*;
             assert_contains!(&reexports, &"submodule::type".to_string());
         }
     }
+
+    mod extern_crates {
+        use super::*;
+        use crate::api::parsing::files::Visibility;
+
+        #[test]
+        fn public_crate() {
+            let source_code = "pub extern crate foo;";
+            let tree = make_tree(source_code);
+            let node = find_child_node(tree.root_node(), "extern_crate_declaration");
+
+            let symbol = extract_extern_crate(&node, source_code).unwrap();
+
+            assert_matches!(
+                symbol,
+                RustSymbol::ExternCrate { name, alias: None, visibility: Visibility::Public, .. }
+                    if name == "foo"
+            );
+        }
+
+        #[test]
+        fn aliased_crate() {
+            let source_code = "extern crate foo as bar;";
+            let tree = make_tree(source_code);
+            let node = find_child_node(tree.root_node(), "extern_crate_declaration");
+
+            let symbol = extract_extern_crate(&node, source_code).unwrap();
+
+            assert_matches!(
+                symbol,
+                RustSymbol::ExternCrate { name, alias: Some(alias), visibility: Visibility::Private, .. }
+                    if name == "foo" && alias == "bar"
+            );
+        }
+
+        #[test]
+        fn self_alias() {
+            let source_code = "extern crate self as my_crate;";
+            let tree = make_tree(source_code);
+            let node = find_child_node(tree.root_node(), "extern_crate_declaration");
+
+            let symbol = extract_extern_crate(&node, source_code).unwrap();
+
+            assert_matches!(
+                symbol,
+                RustSymbol::ExternCrate { name, alias: Some(alias), .. }
+                    if name == "self" && alias == "my_crate"
+            );
+        }
+    }
 }
@@ -1,23 +1,26 @@
 use super::doc_comments::extract_outer_doc_comments;
-use super::helpers::{extract_attributes, get_declaration_list};
+use super::files::{SymbolKind, Visibility};
+use super::helpers::{
+    extract_attributes, extract_name, get_declaration_list, get_modifier_visibility, is_doc_hidden,
+    is_doc_hidden_attribute, is_doc_value_attribute, is_public,
+};
 use daipendency_extractor::ExtractionError;
+use std::path::Path;
 use tree_sitter::Node;
 
-pub fn get_symbol_source_code(node: Node, source_code: &str) -> Result<String, ExtractionError> {
-    let mut source_code_with_docs = String::new();
+const PRIVATE_FIELDS_PLACEHOLDER: &str = "/* private fields */";
+const PRIVATE_TUPLE_FIELD_PLACEHOLDER: &str = "_";
+const HIDDEN_VARIANTS_PLACEHOLDER: &str = "// some variants omitted";
 
-    if let Some(doc_comment) = extract_outer_doc_comments(&node, source_code)? {
-        source_code_with_docs.push_str(&doc_comment);
-    }
-
-    let attributes = extract_attributes(&node, source_code)?;
-    if !attributes.is_empty() {
-        let attributes_str = format!("{}\n", attributes.join("\n"));
-        source_code_with_docs.push_str(&attributes_str);
-    }
+pub fn get_symbol_source_code(
+    node: Node,
+    source_code: &str,
+    directory: &Path,
+) -> Result<String, ExtractionError> {
+    let mut source_code_with_docs = get_doc_comments_and_attributes(node, source_code, directory)?;
 
     let symbol_source = match node.kind() {
-        "function_item" | "function_signature_item" => {
+        "function_item" => {
             let mut cursor = node.walk();
             let block_node = node
                 .children(&mut cursor)
@@ -30,18 +33,17 @@ pub fn get_symbol_source_code(node: Node, source_code: &str) -> Result<String, E
                 &source_code[node.start_byte()..block_node.start_byte()].trim_end()
             )
         }
-        "const_item" => {
+        "const_item" | "static_item" => {
             let mut cursor = node.walk();
-            let equals_pos = node
-                .children(&mut cursor)
-                .find(|n| n.kind() == "=")
-                .ok_or_else(|| {
-                    ExtractionError::Malformed("Failed to find equals sign in const".to_string())
-                })?;
-            format!(
-                "{};",
-                &source_code[node.start_byte()..equals_pos.start_byte()].trim_end()
-            )
+            let equals_node = node.children(&mut cursor).find(|n| n.kind() == "=");
+            match equals_node {
+                Some(equals_node) => format!(
+                    "{};",
+                    &source_code[node.start_byte()..equals_node.start_byte()].trim_end()
+                ),
+                // Without an initialiser (e.g. an associated const or a static in an extern block)
+                None => get_node_text(node, source_code)?,
+            }
         }
         "trait_item" => {
             let declaration_list = get_declaration_list(node).ok_or_else(|| {
@@ -52,31 +54,261 @@ pub fn get_symbol_source_code(node: Node, source_code: &str) -> Result<String, E
             trait_source.push_str(&source_code[node.start_byte()..declaration_list.start_byte()]);
             trait_source.push_str("{\n");
 
-            let mut method_cursor = declaration_list.walk();
-            for method in declaration_list.children(&mut method_cursor) {
-                if method.kind() == "function_item" {
-                    let method_source = get_symbol_source_code(method, source_code)?;
-                    for line in method_source.lines() {
-                        trait_source.push_str("    ");
-                        trait_source.push_str(line);
-                        trait_source.push('\n');
-                    }
+            let mut member_cursor = declaration_list.walk();
+            for member in declaration_list.children(&mut member_cursor) {
+                if is_trait_member(&member) {
+                    let member_source = get_symbol_source_code(member, source_code, directory)?;
+                    push_indented_lines(&mut trait_source, &member_source);
                 }
             }
 
             trait_source.push('}');
             trait_source
         }
-        _ => node
-            .utf8_text(source_code.as_bytes())
-            .map(|s| s.to_string())
-            .map_err(|e| ExtractionError::Malformed(e.to_string()))?,
+        "struct_item" | "union_item" => get_struct_source_code(node, source_code, directory)?,
+        "enum_item" => get_enum_source_code(node, source_code, directory)?,
+        _ => get_node_text(node, source_code)?,
     };
 
     source_code_with_docs.push_str(&symbol_source);
     Ok(source_code_with_docs)
 }
 
+fn is_trait_member(node: &Node) -> bool {
+    matches!(
+        node.kind(),
+        "function_item"
+            | "function_signature_item"
+            | "associated_type"
+            | "type_item"
+            | "const_item"
+    )
+}
+
+/// Get the outer doc comments and attributes preceding a node, each on its own line.
+pub fn get_doc_comments_and_attributes(
+    node: Node,
+    source_code: &str,
+    directory: &Path,
+) -> Result<String, ExtractionError> {
+    let mut result = String::new();
+
+    if let Some(doc_comment) = extract_outer_doc_comments(&node, source_code, directory)? {
+        result.push_str(&doc_comment);
+    }
+
+    let attributes: Vec<_> = extract_attributes(&node, source_code)?
+        .into_iter()
+        .filter(|attribute| !is_doc_value_attribute(attribute))
+        .collect();
+    if !attributes.is_empty() {
+        let attributes_str = format!("{}\n", attributes.join("\n"));
+        result.push_str(&attributes_str);
+    }
+
+    Ok(result)
+}
+
+fn get_node_text(node: Node, source_code: &str) -> Result<String, ExtractionError> {
+    node.utf8_text(source_code.as_bytes())
+        .map(|s| s.to_string())
+        .map_err(|e| ExtractionError::Malformed(e.to_string()))
+}
+
+fn get_struct_source_code(
+    node: Node,
+    source_code: &str,
+    directory: &Path,
+) -> Result<String, ExtractionError> {
+    match node.child_by_field_name("body") {
+        Some(body) if body.kind() == "field_declaration_list" => {
+            get_named_struct_source_code(node, body, source_code, directory)
+        }
+        Some(body) if body.kind() == "ordered_field_declaration_list" => {
+            get_tuple_struct_source_code(node, body, source_code)
+        }
+        _ => get_node_text(node, source_code),
+    }
+}
+
+/// Render a struct with named fields, replacing its private and hidden fields with a placeholder.
+fn get_named_struct_source_code(
+    node: Node,
+    body: Node,
+    source_code: &str,
+    directory: &Path,
+) -> Result<String, ExtractionError> {
+    let mut fields_source = String::new();
+    let mut has_private_fields = false;
+
+    let mut cursor = body.walk();
+    for field in body.children(&mut cursor) {
+        if field.kind() != "field_declaration" {
+            continue;
+        }
+        if !is_public(&field, source_code) || is_doc_hidden(&field, source_code)? {
+            has_private_fields = true;
+            continue;
+        }
+        let field_source = format!(
+            "{}{},",
+            get_doc_comments_and_attributes(field, source_code, directory)?,
+            get_node_text(field, source_code)?
+        );
+        push_indented_lines(&mut fields_source, &field_source);
+    }
+
+    if !has_private_fields {
+        return get_node_text(node, source_code);
+    }
+    push_indented_lines(&mut fields_source, PRIVATE_FIELDS_PLACEHOLDER);
+
+    Ok(format!(
+        "{} {{\n{}}}",
+        source_code[node.start_byte()..body.start_byte()].trim_end(),
+        fields_source
+    ))
+}
+
+/// Render a tuple struct, replacing its private and hidden fields with placeholders.
+fn get_tuple_struct_source_code(
+    node: Node,
+    body: Node,
+    source_code: &str,
+) -> Result<String, ExtractionError> {
+    let mut fields: Vec<Vec<Node>> = vec![Vec::new()];
+    let mut cursor = body.walk();
+    for child in body.children(&mut cursor) {
+        match child.kind() {
+            "(" | ")" => {}
+            "," => fields.push(Vec::new()),
+            _ => fields.last_mut().unwrap().push(child),
+        }
+    }
+    fields.retain(|field| !field.is_empty());
+
+    let mut field_sources = Vec::new();
+    let mut private_field_count = 0;
+    for field in &fields {
+        let is_public_field = field.iter().any(|n| {
+            n.kind() == "visibility_modifier"
+                && get_modifier_visibility(n, source_code) == Visibility::Public
+        });
+        let is_hidden_field = field.iter().any(|n| {
+            n.kind() == "attribute_item"
+                && is_doc_hidden_attribute(&source_code[n.start_byte()..n.end_byte()])
+        });
+        if is_public_field && !is_hidden_field {
+            let first = field.first().unwrap();
+            let last = field.last().unwrap();
+            field_sources.push(source_code[first.start_byte()..last.end_byte()].to_string());
+        } else {
+            private_field_count += 1;
+            field_sources.push(PRIVATE_TUPLE_FIELD_PLACEHOLDER.to_string());
+        }
+    }
+
+    if private_field_count == 0 {
+        return get_node_text(node, source_code);
+    }
+    let fields_source = if private_field_count == fields.len() {
+        PRIVATE_FIELDS_PLACEHOLDER.to_string()
+    } else {
+        field_sources.join(", ")
+    };
+
+    Ok(format!(
+        "{}({}){}",
+        &source_code[node.start_byte()..body.start_byte()],
+        fields_source,
+        &source_code[body.end_byte()..node.end_byte()]
+    ))
+}
+
+/// Render an enum, replacing its hidden variants with a placeholder.
+fn get_enum_source_code(
+    node: Node,
+    source_code: &str,
+    directory: &Path,
+) -> Result<String, ExtractionError> {
+    let Some(body) = node.child_by_field_name("body") else {
+        return get_node_text(node, source_code);
+    };
+
+    let mut variants_source = String::new();
+    let mut has_hidden_variants = false;
+
+    let mut cursor = body.walk();
+    for variant in body.children(&mut cursor) {
+        if variant.kind() != "enum_variant" {
+            continue;
+        }
+        if is_doc_hidden(&variant, source_code)? {
+            has_hidden_variants = true;
+            continue;
+        }
+        let variant_source = format!(
+            "{}{},",
+            get_doc_comments_and_attributes(variant, source_code, directory)?,
+            get_node_text(variant, source_code)?
+        );
+        push_indented_lines(&mut variants_source, &variant_source);
+    }
+
+    if !has_hidden_variants {
+        return get_node_text(node, source_code);
+    }
+    push_indented_lines(&mut variants_source, HIDDEN_VARIANTS_PLACEHOLDER);
+
+    Ok(format!(
+        "{} {{\n{}}}",
+        source_code[node.start_byte()..body.start_byte()].trim_end(),
+        variants_source
+    ))
+}
+
+/// Get the kind of the symbol declared by an item node (e.g. `function_item`).
+pub fn get_symbol_kind(node_kind: &str) -> SymbolKind {
+    match node_kind {
+        "function_item" | "function_signature_item" => SymbolKind::Function,
+        "struct_item" => SymbolKind::Struct,
+        "union_item" => SymbolKind::Union,
+        "enum_item" => SymbolKind::Enum,
+        "trait_item" => SymbolKind::Trait,
+        "type_item" => SymbolKind::TypeAlias,
+        "const_item" => SymbolKind::Const,
+        "static_item" => SymbolKind::Static,
+        _ => unreachable!("Unexpected symbol node kind: {}", node_kind),
+    }
+}
+
+/// Get the names of the variants of an enum, excluding its hidden variants.
+pub fn get_enum_variant_names(
+    node: Node,
+    source_code: &str,
+) -> Result<Vec<String>, ExtractionError> {
+    let Some(body) = node.child_by_field_name("body") else {
+        return Ok(Vec::new());
+    };
+
+    let mut names = Vec::new();
+    let mut cursor = body.walk();
+    for variant in body.children(&mut cursor) {
+        if variant.kind() == "enum_variant" && !is_doc_hidden(&variant, source_code)? {
+            names.push(extract_name(&variant, source_code)?);
+        }
+    }
+    Ok(names)
+}
+
+pub fn push_indented_lines(target: &mut String, source: &str) {
+    for line in source.lines() {
+        target.push_str("    ");
+        target.push_str(line);
+        target.push('\n');
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,7 +322,7 @@ mod tests {
         let tree = make_tree(source_code);
         let function_node = find_child_node(tree.root_node(), "function_item");
 
-        let result = get_symbol_source_code(function_node, source_code).unwrap();
+        let result = get_symbol_source_code(function_node, source_code, Path::new("")).unwrap();
 
         assert_eq!(result, "pub fn test_function(x: i32) -> i32;");
     }
@@ -103,7 +335,7 @@ mod tests {
         let tree = make_tree(source_code);
         let function_node = find_child_node(tree.root_node(), "function_item");
 
-        let result = get_symbol_source_code(function_node, source_code).unwrap();
+        let result = get_symbol_source_code(function_node, source_code, Path::new("")).unwrap();
 
         assert_eq!(result, "pub const fn test_function(x: i32) -> i32;");
     }
@@ -115,7 +347,7 @@ pub fn test_function(x: i32) -> i32 { 42 }"#;
         let tree = make_tree(source_code);
         let function_node = find_child_node(tree.root_node(), "function_item");
 
-        let result = get_symbol_source_code(function_node, source_code).unwrap();
+        let result = get_symbol_source_code(function_node, source_code, Path::new("")).unwrap();
 
         assert_eq!(result, "#[cfg(test)]\npub fn test_function(x: i32) -> i32;");
     }
@@ -130,7 +362,7 @@ pub fn test_function(x: i32) -> i32 { 42 }"#;
         let tree = make_tree(source_code);
         let trait_node = find_child_node(tree.root_node(), "trait_item");
 
-        let result = get_symbol_source_code(trait_node, source_code).unwrap();
+        let result = get_symbol_source_code(trait_node, source_code, Path::new("")).unwrap();
 
         assert_eq!(
             result,
@@ -138,18 +370,270 @@ pub fn test_function(x: i32) -> i32 { 42 }"#;
         );
     }
 
-    #[test]
-    fn struct_with_fields() {
-        let source_code = r#"pub struct TestStruct {
+    mod traits {
+        use super::*;
+
+        fn get_trait_source_code(source_code: &str) -> String {
+            let tree = make_tree(source_code);
+            let trait_node = find_child_node(tree.root_node(), "trait_item");
+            get_symbol_source_code(trait_node, source_code, Path::new("")).unwrap()
+        }
+
+        #[test]
+        fn required_method() {
+            let source_code = r#"pub trait Reader {
+    /// Read a byte.
+    #[must_use]
+    fn read(&mut self) -> u8;
+}"#;
+
+            let result = get_trait_source_code(source_code);
+
+            assert_eq!(
+                result,
+                "pub trait Reader {\n    /// Read a byte.\n    #[must_use]\n    fn read(&mut self) -> u8;\n}"
+            );
+        }
+
+        #[test]
+        fn provided_method() {
+            let source_code = r#"pub trait Reader {
+    /// Skip a byte.
+    fn skip(&mut self) {
+        self.read();
+    }
+}"#;
+
+            let result = get_trait_source_code(source_code);
+
+            assert_eq!(
+                result,
+                "pub trait Reader {\n    /// Skip a byte.\n    fn skip(&mut self);\n}"
+            );
+        }
+
+        #[test]
+        fn associated_types() {
+            let source_code = r#"pub trait Container {
+    /// The item type.
+    type Item: Clone + Send;
+    type Error;
+}"#;
+
+            let result = get_trait_source_code(source_code);
+
+            assert_eq!(
+                result,
+                "pub trait Container {\n    /// The item type.\n    type Item: Clone + Send;\n    type Error;\n}"
+            );
+        }
+
+        #[test]
+        fn associated_consts() {
+            let source_code = r#"pub trait Bounded {
+    const MIN: Self;
+    /// The maximum value.
+    const MAX: usize = 255;
+}"#;
+
+            let result = get_trait_source_code(source_code);
+
+            assert_eq!(
+                result,
+                "pub trait Bounded {\n    const MIN: Self;\n    /// The maximum value.\n    const MAX: usize;\n}"
+            );
+        }
+
+        #[test]
+        fn generics_supertraits_and_where_clause() {
+            let source_code = r#"pub trait Convert<T>: Clone + Send
+where
+    T: Default,
+{
+    fn convert(&self) -> T;
+}"#;
+
+            let result = get_trait_source_code(source_code);
+
+            assert_eq!(
+                result,
+                "pub trait Convert<T>: Clone + Send\nwhere\n    T: Default,\n{\n    fn convert(&self) -> T;\n}"
+            );
+        }
+
+        #[test]
+        fn non_member_items() {
+            let source_code = r#"pub trait Reader {
+    // Not documentation
+    fn read(&mut self) -> u8;
+}"#;
+
+            let result = get_trait_source_code(source_code);
+
+            assert_eq!(
+                result,
+                "pub trait Reader {\n    fn read(&mut self) -> u8;\n}"
+            );
+        }
+    }
+
+    mod structs {
+        use super::*;
+
+        #[test]
+        fn public_fields() {
+            let source_code = r#"pub struct TestStruct {
+    pub field1: i32,
+    pub field2: String,
+}"#;
+            let tree = make_tree(source_code);
+            let struct_node = find_child_node(tree.root_node(), "struct_item");
+
+            let result = get_symbol_source_code(struct_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(result, source_code);
+        }
+
+        #[test]
+        fn private_fields() {
+            let source_code = r#"pub struct TestStruct {
             field1: i32,
             field2: String,
         }"#;
-        let tree = make_tree(source_code);
-        let struct_node = find_child_node(tree.root_node(), "struct_item");
+            let tree = make_tree(source_code);
+            let struct_node = find_child_node(tree.root_node(), "struct_item");
+
+            let result = get_symbol_source_code(struct_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                "pub struct TestStruct {\n    /* private fields */\n}"
+            );
+        }
+
+        #[test]
+        fn mixed_fields() {
+            let source_code = r#"pub struct TestStruct<T> where T: Clone {
+    /// The public field
+    #[serde(default)]
+    pub public_field: T,
+    pub(crate) restricted_field: i32,
+    #[doc(hidden)]
+    pub hidden_field: u8,
+    private_field: internal::Secret,
+}"#;
+            let tree = make_tree(source_code);
+            let struct_node = find_child_node(tree.root_node(), "struct_item");
 
-        let result = get_symbol_source_code(struct_node, source_code).unwrap();
+            let result = get_symbol_source_code(struct_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                "pub struct TestStruct<T> where T: Clone {\n    /// The public field\n    #[serde(default)]\n    pub public_field: T,\n    /* private fields */\n}"
+            );
+        }
+
+        #[test]
+        fn unit_struct() {
+            let source_code = "pub struct TestStruct;";
+            let tree = make_tree(source_code);
+            let struct_node = find_child_node(tree.root_node(), "struct_item");
+
+            let result = get_symbol_source_code(struct_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(result, source_code);
+        }
+
+        #[test]
+        fn public_tuple_fields() {
+            let source_code = "pub struct TestStruct(pub i32, pub String);";
+            let tree = make_tree(source_code);
+            let struct_node = find_child_node(tree.root_node(), "struct_item");
+
+            let result = get_symbol_source_code(struct_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(result, source_code);
+        }
+
+        #[test]
+        fn private_tuple_fields() {
+            let source_code = "pub struct TestStruct(i32, internal::Secret);";
+            let tree = make_tree(source_code);
+            let struct_node = find_child_node(tree.root_node(), "struct_item");
+
+            let result = get_symbol_source_code(struct_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(result, "pub struct TestStruct(/* private fields */);");
+        }
+
+        #[test]
+        fn mixed_tuple_fields() {
+            let source_code =
+                "pub struct TestStruct<T>(#[serde(default)] pub T, i32, #[doc(hidden)] pub u8) where T: Clone;";
+            let tree = make_tree(source_code);
+            let struct_node = find_child_node(tree.root_node(), "struct_item");
 
-        assert_eq!(result, source_code);
+            let result = get_symbol_source_code(struct_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                "pub struct TestStruct<T>(#[serde(default)] pub T, _, _) where T: Clone;"
+            );
+        }
+    }
+
+    mod enums {
+        use super::*;
+
+        #[test]
+        fn visible_variants() {
+            let source_code = r#"pub enum TestEnum {
+    A,
+    B(u8),
+}"#;
+            let tree = make_tree(source_code);
+            let enum_node = find_child_node(tree.root_node(), "enum_item");
+
+            let result = get_symbol_source_code(enum_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(result, source_code);
+        }
+
+        #[test]
+        fn hidden_variants() {
+            let source_code = r#"pub enum TestEnum {
+    /// The first variant
+    A,
+    #[doc(hidden)]
+    __Nonexhaustive,
+    C { field: u8 } = 3,
+}"#;
+            let tree = make_tree(source_code);
+            let enum_node = find_child_node(tree.root_node(), "enum_item");
+
+            let result = get_symbol_source_code(enum_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                "pub enum TestEnum {\n    /// The first variant\n    A,\n    C { field: u8 } = 3,\n    // some variants omitted\n}"
+            );
+        }
+
+        #[test]
+        fn variant_names() {
+            let source_code = r#"pub enum TestEnum {
+    A,
+    #[doc(hidden)]
+    __Nonexhaustive,
+    C { field: u8 } = 3,
+}"#;
+            let tree = make_tree(source_code);
+            let enum_node = find_child_node(tree.root_node(), "enum_item");
+
+            let names = get_enum_variant_names(enum_node, source_code).unwrap();
+
+            assert_eq!(names, vec!["A", "C"]);
+        }
     }
 
     #[test]
@@ -158,11 +642,36 @@ pub fn test_function(x: i32) -> i32 { 42 }"#;
         let tree = make_tree(source_code);
         let const_node = find_child_node(tree.root_node(), "const_item");
 
-        let result = get_symbol_source_code(const_node, source_code).unwrap();
+        let result = get_symbol_source_code(const_node, source_code, Path::new("")).unwrap();
 
         assert_eq!(result, "const THINGY: usize;");
     }
 
+    #[test]
+    fn static_declaration() {
+        let source_code = "pub static mut COUNTER: AtomicUsize = AtomicUsize::new(0);";
+        let tree = make_tree(source_code);
+        let static_node = find_child_node(tree.root_node(), "static_item");
+
+        let result = get_symbol_source_code(static_node, source_code, Path::new("")).unwrap();
+
+        assert_eq!(result, "pub static mut COUNTER: AtomicUsize;");
+    }
+
+    #[test]
+    fn union_with_private_fields() {
+        let source_code = "pub union Value {\n    pub int: u32,\n    float: f32,\n}";
+        let tree = make_tree(source_code);
+        let union_node = find_child_node(tree.root_node(), "union_item");
+
+        let result = get_symbol_source_code(union_node, source_code, Path::new("")).unwrap();
+
+        assert_eq!(
+            result,
+            "pub union Value {\n    pub int: u32,\n    /* private fields */\n}"
+        );
+    }
+
     mod doc_comments {
         use super::*;
 
@@ -172,7 +681,7 @@ pub fn test_function(x: i32) -> i32 { 42 }"#;
             let tree = make_tree(source_code);
             let function_node = find_child_node(tree.root_node(), "function_item");
 
-            let result = get_symbol_source_code(function_node, source_code).unwrap();
+            let result = get_symbol_source_code(function_node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, "pub fn test_function();");
         }
@@ -184,10 +693,26 @@ pub fn test_function(x: i32) -> i32 { 42 }"#;
             let tree = make_tree(source_code);
             let function_node = find_child_node(tree.root_node(), "function_item");
 
-            let result = get_symbol_source_code(function_node, source_code).unwrap();
+            let result = get_symbol_source_code(function_node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, "/// Test function\npub fn test_function();");
         }
+
+        #[test]
+        fn symbol_with_doc_attribute() {
+            let source_code = r#"#[doc = " Test function"]
+            #[inline]
+            pub fn test_function() {}"#;
+            let tree = make_tree(source_code);
+            let function_node = find_child_node(tree.root_node(), "function_item");
+
+            let result = get_symbol_source_code(function_node, source_code, Path::new("")).unwrap();
+
+            assert_eq!(
+                result,
+                "/// Test function\n#[inline]\npub fn test_function();"
+            );
+        }
     }
 
     mod attributes {
@@ -199,7 +724,7 @@ pub fn test_function(x: i32) -> i32 { 42 }"#;
             let tree = make_tree(source_code);
             let function_node = find_child_node(tree.root_node(), "function_item");
 
-            let result = get_symbol_source_code(function_node, source_code).unwrap();
+            let result = get_symbol_source_code(function_node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, "pub fn test_function();");
         }
@@ -211,7 +736,7 @@ pub fn test_function(x: i32) -> i32 { 42 }"#;
             let tree = make_tree(source_code);
             let function_node = find_child_node(tree.root_node(), "function_item");
 
-            let result = get_symbol_source_code(function_node, source_code).unwrap();
+            let result = get_symbol_source_code(function_node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, "#[inline]\npub fn test_function();");
         }
@@ -224,7 +749,7 @@ pub fn test_function(x: i32) -> i32 { 42 }"#;
             let tree = make_tree(source_code);
             let function_node = find_child_node(tree.root_node(), "function_item");
 
-            let result = get_symbol_source_code(function_node, source_code).unwrap();
+            let result = get_symbol_source_code(function_node, source_code, Path::new("")).unwrap();
 
             assert_eq!(result, "#[inline]\n#[deprecated]\npub fn test_function();");
         }
@@ -0,0 +1,114 @@
+use super::files::{SourceSpan, SyntaxError};
+use super::helpers::get_source_span;
+use std::ops::Range;
+use tree_sitter::Node;
+
+/// Collect the syntax errors in the tree under `node`, from the ERROR and MISSING nodes inserted
+/// by the parser to recover from them.
+///
+/// The errors within `ignored_ranges` are skipped (e.g. those in declarative macros 2.0, which the
+/// grammar doesn't support yet but are extracted regardless).
+pub fn collect_syntax_errors(node: &Node, ignored_ranges: &[Range<usize>]) -> Vec<SyntaxError> {
+    let mut syntax_errors = Vec::new();
+    if node.has_error() {
+        push_syntax_errors(node, ignored_ranges, &mut syntax_errors);
+    }
+    syntax_errors
+}
+
+fn push_syntax_errors(
+    node: &Node,
+    ignored_ranges: &[Range<usize>],
+    syntax_errors: &mut Vec<SyntaxError>,
+) {
+    if is_ignored(node, ignored_ranges) {
+        return;
+    }
+    if node.is_error() {
+        // The parser may lump ignored ranges and actual errors into the same ERROR node
+        let mut cursor = node.walk();
+        let unignored_children: Vec<_> = node
+            .children(&mut cursor)
+            .filter(|child| !is_ignored(child, ignored_ranges))
+            .collect();
+        let span = match (unignored_children.first(), unignored_children.last()) {
+            _ if unignored_children.len() == node.child_count() => get_source_span(node),
+            (Some(first_child), Some(last_child)) => SourceSpan {
+                end_line: get_source_span(last_child).end_line,
+                end_column: get_source_span(last_child).end_column,
+                ..get_source_span(first_child)
+            },
+            _ => return,
+        };
+        syntax_errors.push(SyntaxError {
+            message: "Unexpected syntax".to_string(),
+            span,
+        });
+    } else if node.is_missing() {
+        syntax_errors.push(SyntaxError {
+            message: format!("Missing `{}`", node.kind()),
+            span: get_source_span(node),
+        });
+    } else {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.has_error() {
+                push_syntax_errors(&child, ignored_ranges, syntax_errors);
+            }
+        }
+    }
+}
+
+fn is_ignored(node: &Node, ignored_ranges: &[Range<usize>]) -> bool {
+    ignored_ranges
+        .iter()
+        .any(|range| range.start <= node.start_byte() && node.end_byte() <= range.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::parsing::test_helpers::make_tree;
+
+    #[test]
+    fn valid_syntax() {
+        let tree = make_tree("pub fn foo() {}");
+
+        let syntax_errors = collect_syntax_errors(&tree.root_node(), &[]);
+
+        assert!(syntax_errors.is_empty());
+    }
+
+    #[test]
+    fn unexpected_syntax() {
+        let tree = make_tree("pub fn foo() {}\npub struct @;");
+
+        let syntax_errors = collect_syntax_errors(&tree.root_node(), &[]);
+
+        assert_eq!(syntax_errors.len(), 1);
+        assert_eq!(syntax_errors[0].message, "Unexpected syntax");
+        assert_eq!(syntax_errors[0].span.start_line, 2);
+    }
+
+    #[test]
+    fn ignored_range() {
+        let tree = make_tree("fn foo() { let a = 1 }\nfn bar() { let b = 2 }");
+        let foo_range = Range { start: 0, end: 22 };
+
+        let syntax_errors = collect_syntax_errors(&tree.root_node(), &[foo_range]);
+
+        assert_eq!(syntax_errors.len(), 1);
+        assert_eq!(syntax_errors[0].span.start_line, 2);
+    }
+
+    #[test]
+    fn missing_token() {
+        let tree = make_tree("pub struct Foo");
+
+        let syntax_errors = collect_syntax_errors(&tree.root_node(), &[]);
+
+        assert_eq!(syntax_errors.len(), 1);
+        assert_eq!(syntax_errors[0].message, "Missing `;`");
+        assert_eq!(syntax_errors[0].span.start_column, 15);
+    }
+}
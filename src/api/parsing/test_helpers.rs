@@ -8,7 +8,7 @@ pub struct TestTree {
 }
 
 impl TestTree {
-    pub fn root_node(&self) -> Node {
+    pub fn root_node(&self) -> Node<'_> {
         self.tree.root_node()
     }
 }
@@ -3,8 +3,11 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tree_sitter::Parser;
 
-use super::module_directory::ModuleDirectory;
-use super::parsing::{parse_rust_file, RustSymbol};
+use super::module_directory::{ModuleDirectory, SourceLocation, UnresolvedModule};
+use super::options::ExtractionOptions;
+use super::parsing::{
+    parse_rust_file, CfgOptions, CfgPredicate, IncludePath, RustFile, RustSymbol, Visibility,
+};
 
 enum LocalModuleType {
     File,
@@ -16,16 +19,57 @@ struct LocalModuleImport {
     module_type: LocalModuleType,
 }
 
+/// The declaration of a module directory (e.g. `#[cfg(unix)] pub mod foo;`).
+struct DirectoryDeclaration {
+    is_public: bool,
+    /// The predicate required by the declaration and its ancestors, if any.
+    cfg: Option<CfgPredicate>,
+    is_doc_hidden: bool,
+}
+
+/// The settings of the collection, shared by all the module directories.
+#[derive(Clone, Copy)]
+struct CollectionSettings<'a> {
+    cfg_options: Option<&'a CfgOptions>,
+    tolerate_missing_modules: bool,
+    /// The candidates for the output directory of the build script (i.e. `OUT_DIR`), in order of
+    /// preference.
+    out_dirs: &'a [PathBuf],
+}
+
 /// Traverse the source files of the Rust crate and collect all symbols and symbol references (reexports).
+///
+/// Symbols disabled by the cfg options of `options` are pruned, and the modules they declare aren't
+/// loaded. If cfg predicates are annotated instead, all symbols are retained along with the
+/// predicates they require.
+///
+/// Modules whose files can't be found abort the collection, unless `options` tolerate them, in
+/// which case they're recorded as unresolved modules of their module directories.
+///
+/// Item-level `include!` invocations are replaced with the items of the files they include, which
+/// are looked up relative to the including file or, for paths appended to `OUT_DIR`, to the first
+/// output directory of `options` that has them. Invocations whose files can't be found are
+/// retained.
 pub fn collect_module_directories(
     entry_point: &Path,
+    options: &ExtractionOptions,
     parser: &mut Parser,
 ) -> Result<Vec<ModuleDirectory>, ExtractionError> {
+    let cfg_options = options.get_cfg_options();
     recursively_collect_module_directories(
         entry_point,
         entry_point.parent().unwrap(),
-        true,
         "",
+        DirectoryDeclaration {
+            is_public: true,
+            cfg: None,
+            is_doc_hidden: false,
+        },
+        CollectionSettings {
+            cfg_options: cfg_options.as_ref(),
+            tolerate_missing_modules: options.tolerate_missing_modules,
+            out_dirs: &options.out_dirs,
+        },
         parser,
     )
 }
@@ -33,53 +77,420 @@ pub fn collect_module_directories(
 fn recursively_collect_module_directories(
     entry_point_path: &Path,
     directory_path: &Path,
-    is_root_directory_public: bool,
     namespace_prefix: &str,
+    declaration: DirectoryDeclaration,
+    settings: CollectionSettings,
     parser: &mut Parser,
 ) -> Result<Vec<ModuleDirectory>, ExtractionError> {
-    let entry_point_content =
-        std::fs::read_to_string(entry_point_path).map_err(ExtractionError::Io)?;
-
-    let entry_point_file = parse_rust_file(&entry_point_content, parser)?;
-
-    let mut internal_files = HashMap::new();
-    let mut imported_directories = Vec::new();
-    for symbol in &entry_point_file.symbols {
-        if let RustSymbol::ModuleImport {
-            name,
-            is_reexported,
-        } = symbol
-        {
-            let import = categorise_module_import(entry_point_path, directory_path, name)?;
-            match import.module_type {
-                LocalModuleType::File => {
-                    let file = parse_rust_file(&std::fs::read_to_string(&import.path)?, parser)?;
-                    internal_files.insert(name.clone(), file);
-                }
-                LocalModuleType::Directory(ref module_dir) => {
-                    let module_name = prefix_namespace(name, namespace_prefix);
+    let entry_point_file = parse_module_file(entry_point_path, settings, parser)?;
+
+    let mut collector = ModuleImportCollector {
+        declaring_file: entry_point_path,
+        namespace_prefix,
+        settings,
+        parser,
+        internal_files: HashMap::new(),
+        unresolved_modules: Vec::new(),
+        imported_directories: Vec::new(),
+    };
+    collector.collect(
+        &entry_point_file.symbols,
+        "",
+        directory_path,
+        entry_point_path.parent().unwrap(),
+        declaration.cfg.as_ref(),
+    )?;
+
+    let root_module_directory = ModuleDirectory {
+        name: namespace_prefix.to_string(),
+        is_public: declaration.is_public,
+        entry_point: entry_point_file,
+        internal_files: collector.internal_files,
+        cfg: declaration.cfg,
+        is_doc_hidden: declaration.is_doc_hidden,
+        unresolved_modules: collector.unresolved_modules,
+    };
+    let mut directories = vec![root_module_directory];
+    directories.extend(collector.imported_directories);
+    Ok(directories)
+}
+
+/// Collects the modules imported by a module directory's entry point, including those imported
+/// from its inline module blocks (e.g. `mod inline { mod foo; }`).
+struct ModuleImportCollector<'a> {
+    declaring_file: &'a Path,
+    namespace_prefix: &'a str,
+    settings: CollectionSettings<'a>,
+    parser: &'a mut Parser,
+    internal_files: HashMap<String, RustFile>,
+    unresolved_modules: Vec<UnresolvedModule>,
+    imported_directories: Vec<ModuleDirectory>,
+}
+
+impl ModuleImportCollector<'_> {
+    /// Collect the modules imported by `symbols`.
+    ///
+    /// `directory_path` is where modules without a `#[path]` attribute are located, and
+    /// `path_attribute_directory` is what `#[path]` attributes are relative to. These only differ
+    /// outside inline module blocks, where `#[path]` is relative to the declaring file's directory.
+    ///
+    /// `inherited_cfg` is the predicate required by the modules declaring `symbols`, if any.
+    fn collect(
+        &mut self,
+        symbols: &[RustSymbol],
+        inline_module_path: &str,
+        directory_path: &Path,
+        path_attribute_directory: &Path,
+        inherited_cfg: Option<&CfgPredicate>,
+    ) -> Result<(), ExtractionError> {
+        for symbol in symbols {
+            match symbol {
+                RustSymbol::ModuleImport {
+                    name,
+                    visibility,
+                    path,
+                    cfg,
+                    is_doc_hidden,
+                    span,
+                } => {
+                    let import = match path {
+                        Some(path) => {
+                            categorise_path_attribute_import(path_attribute_directory, path)
+                        }
+                        None => categorise_module_import(self.declaring_file, directory_path, name),
+                    };
+                    let relative_module_name = prefix_namespace(name, inline_module_path);
+                    let import = match import {
+                        Ok(import) => import,
+                        Err(ExtractionError::Malformed(message))
+                            if self.settings.tolerate_missing_modules =>
+                        {
+                            self.unresolved_modules.push(UnresolvedModule {
+                                name: relative_module_name,
+                                message,
+                                location: SourceLocation {
+                                    path: self.declaring_file.to_path_buf(),
+                                    span: *span,
+                                },
+                            });
+                            continue;
+                        }
+                        Err(error) => return Err(error),
+                    };
+                    let module_dir = match import.module_type {
+                        LocalModuleType::File => {
+                            let file = parse_module_file(
+                                Path::new(&import.path),
+                                self.settings,
+                                self.parser,
+                            )?;
+                            if !declares_path_modules(&file.symbols) {
+                                self.internal_files.insert(relative_module_name, file);
+                                continue;
+                            }
+                            // The file has no directory of its own, but the modules it declares
+                            // with `#[path]` attributes are relative to its own directory
+                            directory_path.join(name)
+                        }
+                        LocalModuleType::Directory(module_dir) => PathBuf::from(module_dir),
+                    };
+                    let module_name =
+                        prefix_namespace(&relative_module_name, self.namespace_prefix);
                     let directories = recursively_collect_module_directories(
                         &PathBuf::from(&import.path),
-                        &PathBuf::from(module_dir),
-                        *is_reexported,
+                        &module_dir,
                         &module_name,
-                        parser,
+                        DirectoryDeclaration {
+                            is_public: *visibility == Visibility::Public,
+                            cfg: CfgPredicate::conjoin(inherited_cfg, cfg.as_ref()),
+                            is_doc_hidden: *is_doc_hidden,
+                        },
+                        self.settings,
+                        self.parser,
                     )?;
-                    imported_directories.extend(directories);
+                    self.imported_directories.extend(directories);
                 }
+                // Modules declared in included files are looked up as if declared by the includer
+                RustSymbol::IncludedFile { symbols, cfg, .. } => {
+                    self.collect(
+                        symbols,
+                        inline_module_path,
+                        directory_path,
+                        path_attribute_directory,
+                        CfgPredicate::conjoin(inherited_cfg, cfg.as_ref()).as_ref(),
+                    )?;
+                }
+                RustSymbol::ModuleBlock {
+                    name, content, cfg, ..
+                } => {
+                    let inline_directory = directory_path.join(name);
+                    self.collect(
+                        content,
+                        &prefix_namespace(name, inline_module_path),
+                        &inline_directory,
+                        &inline_directory,
+                        CfgPredicate::conjoin(inherited_cfg, cfg.as_ref()).as_ref(),
+                    )?;
+                }
+                _ => {}
             }
         }
+        Ok(())
     }
+}
 
-    let root_module_directory = ModuleDirectory {
-        name: namespace_prefix.to_string(),
-        is_public: is_root_directory_public,
-        entry_point: entry_point_file,
-        internal_files,
+/// Parse a Rust file, splicing the files it includes and pruning the symbols that are disabled by
+/// the cfg options (if any).
+fn parse_module_file(
+    path: &Path,
+    settings: CollectionSettings,
+    parser: &mut Parser,
+) -> Result<RustFile, ExtractionError> {
+    let file = read_rust_file(path, parser)?;
+    let mut including_files = path.canonicalize().into_iter().collect();
+    let file = RustFile {
+        symbols: splice_source_includes(
+            file.symbols,
+            path.parent().unwrap(),
+            settings.out_dirs,
+            &mut including_files,
+            parser,
+        )?,
+        ..file
     };
-    let mut directories = vec![root_module_directory];
-    directories.extend(imported_directories);
-    Ok(directories)
+    match settings.cfg_options {
+        Some(cfg_options) => Ok(RustFile {
+            symbols: prune_disabled_symbols(file.symbols, cfg_options),
+            ..file
+        }),
+        None => Ok(file),
+    }
+}
+
+/// Read and parse a Rust file.
+fn read_rust_file(path: &Path, parser: &mut Parser) -> Result<RustFile, ExtractionError> {
+    let content = std::fs::read_to_string(path).map_err(ExtractionError::Io)?;
+    parse_rust_file(&content, path, parser)
+}
+
+/// Replace the `include!` invocations in `symbols` with the files they include, which inherit the
+/// predicates of the invocations.
+///
+/// `including_files` are the files being spliced, whose inclusion would be cyclic.
+fn splice_source_includes(
+    symbols: Vec<RustSymbol>,
+    directory: &Path,
+    out_dirs: &[PathBuf],
+    including_files: &mut Vec<PathBuf>,
+    parser: &mut Parser,
+) -> Result<Vec<RustSymbol>, ExtractionError> {
+    let mut spliced_symbols = Vec::new();
+    for mut symbol in symbols {
+        if let RustSymbol::ModuleBlock { content, .. } = &mut symbol {
+            *content = splice_source_includes(
+                std::mem::take(content),
+                directory,
+                out_dirs,
+                including_files,
+                parser,
+            )?;
+        }
+        let RustSymbol::SourceInclude {
+            path: Some(include_path),
+            cfg,
+            ..
+        } = &symbol
+        else {
+            spliced_symbols.push(symbol);
+            continue;
+        };
+        let Some(file_path) = find_included_file(include_path, directory, out_dirs)
+            .filter(|file_path| !including_files.contains(file_path))
+        else {
+            spliced_symbols.push(symbol);
+            continue;
+        };
+        let file = read_rust_file(&file_path, parser)?;
+        including_files.push(file_path.clone());
+        let included_symbols = splice_source_includes(
+            file.symbols,
+            file_path.parent().unwrap(),
+            out_dirs,
+            including_files,
+            parser,
+        )?;
+        including_files.pop();
+        spliced_symbols.push(RustSymbol::IncludedFile {
+            path: file_path,
+            symbols: included_symbols,
+            cfg: cfg.clone(),
+        });
+    }
+    Ok(spliced_symbols)
+}
+
+/// Find the file included with `include_path`, returning its canonical path.
+fn find_included_file(
+    include_path: &IncludePath,
+    directory: &Path,
+    out_dirs: &[PathBuf],
+) -> Option<PathBuf> {
+    match include_path {
+        IncludePath::Relative(relative_path) => directory.join(relative_path).canonicalize().ok(),
+        IncludePath::OutDir(suffix) => out_dirs.iter().find_map(|out_dir| {
+            // The suffix is concatenated to `OUT_DIR` as a string (e.g. "/bindings.rs")
+            let mut file_path = out_dir.clone().into_os_string();
+            file_path.push(suffix);
+            PathBuf::from(file_path).canonicalize().ok()
+        }),
+    }
+    .filter(|file_path| file_path.is_file())
+}
+
+/// Prune the symbols disabled by `cfg_options`, and discard the predicates of the remaining
+/// symbols as they hold.
+fn prune_disabled_symbols(symbols: Vec<RustSymbol>, cfg_options: &CfgOptions) -> Vec<RustSymbol> {
+    symbols
+        .into_iter()
+        .filter(|symbol| {
+            symbol
+                .cfg()
+                .is_none_or(|predicate| predicate.evaluate(cfg_options))
+        })
+        .map(|symbol| match symbol {
+            RustSymbol::Symbol {
+                symbol,
+                kind,
+                generics,
+                is_doc_hidden,
+                span,
+                ..
+            } => RustSymbol::Symbol {
+                symbol,
+                kind,
+                generics,
+                cfg: None,
+                is_doc_hidden,
+                span,
+            },
+            RustSymbol::ExportedMacro {
+                symbol,
+                matchers,
+                is_doc_hidden,
+                span,
+                ..
+            } => RustSymbol::ExportedMacro {
+                symbol,
+                matchers,
+                cfg: None,
+                is_doc_hidden,
+                span,
+            },
+            RustSymbol::Reexport {
+                source_path,
+                import_type,
+                is_doc_hidden,
+                span,
+                ..
+            } => RustSymbol::Reexport {
+                source_path,
+                import_type,
+                cfg: None,
+                is_doc_hidden,
+                span,
+            },
+            RustSymbol::Import {
+                source_path,
+                import_type,
+                ..
+            } => RustSymbol::Import {
+                source_path,
+                import_type,
+                cfg: None,
+            },
+            RustSymbol::ModuleBlock {
+                name,
+                visibility,
+                content,
+                doc_comment,
+                is_doc_hidden,
+                span,
+                ..
+            } => RustSymbol::ModuleBlock {
+                name,
+                visibility,
+                content: prune_disabled_symbols(content, cfg_options),
+                doc_comment,
+                cfg: None,
+                is_doc_hidden,
+                span,
+            },
+            RustSymbol::ModuleImport {
+                name,
+                visibility,
+                path,
+                is_doc_hidden,
+                span,
+                ..
+            } => RustSymbol::ModuleImport {
+                name,
+                visibility,
+                path,
+                cfg: None,
+                is_doc_hidden,
+                span,
+            },
+            RustSymbol::ExternCrate {
+                name,
+                alias,
+                visibility,
+                is_doc_hidden,
+                span,
+                ..
+            } => RustSymbol::ExternCrate {
+                name,
+                alias,
+                visibility,
+                cfg: None,
+                is_doc_hidden,
+                span,
+            },
+            RustSymbol::EnumVariants {
+                enum_name,
+                variants,
+                ..
+            } => RustSymbol::EnumVariants {
+                enum_name,
+                variants,
+                cfg: None,
+            },
+            RustSymbol::ImplBlock {
+                type_path,
+                source_code,
+                ..
+            } => RustSymbol::ImplBlock {
+                type_path,
+                source_code,
+                cfg: None,
+            },
+            RustSymbol::SourceInclude {
+                path,
+                source_code,
+                span,
+                ..
+            } => RustSymbol::SourceInclude {
+                path,
+                source_code,
+                cfg: None,
+                span,
+            },
+            RustSymbol::IncludedFile { path, symbols, .. } => RustSymbol::IncludedFile {
+                path,
+                symbols: prune_disabled_symbols(symbols, cfg_options),
+                cfg: None,
+            },
+        })
+        .collect()
 }
 
 fn categorise_module_import(
@@ -121,6 +532,39 @@ fn categorise_module_import(
     )))
 }
 
+/// Categorise a module import with a `#[path]` attribute (e.g. `#[path = "platform/unix.rs"] mod sys;`).
+///
+/// Like `mod.rs` files, the modules it imports are located in the same directory as the file.
+fn categorise_path_attribute_import(
+    path_attribute_directory: &Path,
+    module_path: &str,
+) -> Result<LocalModuleImport, ExtractionError> {
+    let path = path_attribute_directory.join(module_path);
+    if !path.is_file() {
+        return Err(ExtractionError::Malformed(format!(
+            "Could not find module file {}",
+            path.display()
+        )));
+    }
+
+    let module_dir = path.parent().unwrap();
+    Ok(LocalModuleImport {
+        path: path.to_string_lossy().to_string(),
+        module_type: LocalModuleType::Directory(module_dir.to_string_lossy().to_string()),
+    })
+}
+
+/// Whether `symbols` declare any module with a `#[path]` attribute (e.g. `#[path = "unix.rs"] mod
+/// sys;`), including within their module blocks and included files.
+fn declares_path_modules(symbols: &[RustSymbol]) -> bool {
+    symbols.iter().any(|symbol| match symbol {
+        RustSymbol::ModuleImport { path, .. } => path.is_some(),
+        RustSymbol::ModuleBlock { content, .. } => declares_path_modules(content),
+        RustSymbol::IncludedFile { symbols, .. } => declares_path_modules(symbols),
+        _ => false,
+    })
+}
+
 fn prefix_namespace(name: &str, namespace: &str) -> String {
     if namespace.is_empty() {
         name.to_string()
@@ -133,7 +577,7 @@ fn prefix_namespace(name: &str, namespace: &str) -> String {
 mod tests {
     use super::*;
     use crate::test_helpers::setup_parser;
-    use assertables::assert_matches;
+    use assertables::{assert_contains, assert_matches};
     use daipendency_testing::tempdir::TempDir;
 
     #[test]
@@ -141,7 +585,7 @@ mod tests {
         let path = PathBuf::from("non-existing.rs");
         let mut parser = setup_parser();
 
-        let result = collect_module_directories(&path, &mut parser);
+        let result = collect_module_directories(&path, &ExtractionOptions::default(), &mut parser);
 
         assert!(matches!(result, Err(ExtractionError::Io(_))))
     }
@@ -170,7 +614,9 @@ pub fn module_b_function() {}
         let mut parser = setup_parser();
 
         // This should complete without infinite recursion
-        let directories = collect_module_directories(&module_a_rs, &mut parser).unwrap();
+        let directories =
+            collect_module_directories(&module_a_rs, &ExtractionOptions::default(), &mut parser)
+                .unwrap();
 
         assert!(!directories.is_empty())
     }
@@ -188,7 +634,9 @@ pub fn public_function() {}
             .unwrap();
         let mut parser = setup_parser();
 
-        let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+        let directories =
+            collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                .unwrap();
 
         assert_eq!(directories.len(), 1);
         assert!(directories[0].is_public)
@@ -211,7 +659,9 @@ pub fn public_function() {}
                 .unwrap();
             let mut parser = setup_parser();
 
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
             assert_eq!(directories[0].name, "");
@@ -220,7 +670,7 @@ pub fn public_function() {}
             let definitions = &directories[0].entry_point.symbols;
             assert!(matches!(
                 &definitions[0],
-                RustSymbol::Symbol { symbol } if symbol.name == "public_function"
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "public_function"
             ))
         }
 
@@ -237,7 +687,9 @@ fn private_function() {}
                 .unwrap();
             let mut parser = setup_parser();
 
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
             assert_eq!(directories[0].name, "");
@@ -257,15 +709,17 @@ pub mod public_module {}
                 .unwrap();
             let mut parser = setup_parser();
 
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
-            let root = directories.get(0).unwrap();
+            let root = directories.first().unwrap();
             assert_eq!(root.name, "");
             assert_eq!(root.entry_point.symbols.len(), 1);
             assert_matches!(
                 &root.entry_point.symbols[0],
-                RustSymbol::ModuleBlock { name, is_public: true, doc_comment: None, .. }
+                RustSymbol::ModuleBlock { name, visibility: Visibility::Public, doc_comment: None, .. }
                 if name == "public_module"
             )
         }
@@ -283,16 +737,18 @@ mod private_module {}
                 .unwrap();
             let mut parser = setup_parser();
 
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
-            let root = directories.get(0).unwrap();
+            let root = directories.first().unwrap();
             assert_eq!(root.name, "");
             assert_eq!(root.entry_point.symbols.len(), 1);
             assert_matches!(
                 &root.entry_point.symbols[0],
                 RustSymbol::ModuleBlock {
-                    is_public: false,
+                    visibility: Visibility::Private,
                     ..
                 }
             )
@@ -325,14 +781,16 @@ pub struct InnerStruct;
                 .unwrap();
 
             let mut parser = setup_parser();
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
             let root = get_module_directory("", &directories).unwrap();
             assert_eq!(root.entry_point.symbols.len(), 1);
             assert_matches!(
                 &root.entry_point.symbols[0],
-                RustSymbol::ModuleImport { name, is_reexported: true }
+                RustSymbol::ModuleImport { name, visibility: Visibility::Public, .. }
                 if name == "module"
             );
 
@@ -340,7 +798,7 @@ pub struct InnerStruct;
             assert_eq!(module_file.symbols.len(), 1);
             assert_matches!(
                 &module_file.symbols[0],
-                RustSymbol::Symbol { symbol } if symbol.name == "InnerStruct"
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "InnerStruct"
             )
         }
 
@@ -369,7 +827,9 @@ pub enum Format {
                 .unwrap();
             let mut parser = setup_parser();
 
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
             let root = get_module_directory("", &directories).unwrap();
@@ -377,20 +837,24 @@ pub enum Format {
 
             assert_matches!(
                 &root.entry_point.symbols[0],
-                RustSymbol::ModuleImport { name, is_reexported: false }
+                RustSymbol::ModuleImport { name, visibility: Visibility::Private, .. }
                 if name == "formatter"
             );
             assert_matches!(
                 &root.entry_point.symbols[1],
-                RustSymbol::Reexport { source_path, import_type }
+                RustSymbol::Reexport { source_path, import_type, .. }
                 if source_path == "formatter::Format" && matches!(import_type, ImportType::Simple)
             );
 
             let formatter_file = root.internal_files.get("formatter").unwrap();
-            assert_eq!(formatter_file.symbols.len(), 1);
+            assert_eq!(formatter_file.symbols.len(), 2);
             assert_matches!(
                 &formatter_file.symbols[0],
-                RustSymbol::Symbol { symbol } if symbol.name == "Format"
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "Format"
+            );
+            assert_matches!(
+                &formatter_file.symbols[1],
+                RustSymbol::EnumVariants { enum_name, .. } if enum_name == "Format"
             )
         }
 
@@ -425,19 +889,21 @@ pub enum Format {
                 .unwrap();
             let mut parser = setup_parser();
 
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 2);
             let root = get_module_directory("", &directories).unwrap();
             assert_eq!(root.entry_point.symbols.len(), 2);
             assert_matches!(
                 &root.entry_point.symbols[0],
-                RustSymbol::ModuleImport { name, is_reexported: false }
+                RustSymbol::ModuleImport { name, visibility: Visibility::Private, .. }
                 if name == "formatting"
             );
             assert_matches!(
                 &root.entry_point.symbols[1],
-                RustSymbol::Reexport { source_path, import_type }
+                RustSymbol::Reexport { source_path, import_type, .. }
                 if source_path == "formatting::Format" && matches!(import_type, ImportType::Simple)
             );
 
@@ -446,20 +912,24 @@ pub enum Format {
             assert_eq!(formatting.entry_point.symbols.len(), 2);
             assert_matches!(
                 &formatting.entry_point.symbols[0],
-                RustSymbol::ModuleImport { name, is_reexported: false }
+                RustSymbol::ModuleImport { name, visibility: Visibility::Private, .. }
                 if name == "format"
             );
             assert_matches!(
                 &formatting.entry_point.symbols[1],
-                RustSymbol::Reexport { source_path, import_type }
+                RustSymbol::Reexport { source_path, import_type, .. }
                 if source_path == "format::Format" && matches!(import_type, ImportType::Simple)
             );
 
             let format_file = formatting.internal_files.get("format").unwrap();
-            assert_eq!(format_file.symbols.len(), 1);
+            assert_eq!(format_file.symbols.len(), 2);
             assert_matches!(
                 &format_file.symbols[0],
-                RustSymbol::Symbol { symbol } if symbol.name == "Format"
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "Format"
+            );
+            assert_matches!(
+                &format_file.symbols[1],
+                RustSymbol::EnumVariants { enum_name, .. } if enum_name == "Format"
             );
         }
 
@@ -482,22 +952,24 @@ pub use child::grandchild::Format;
                 .unwrap();
             let mut parser = setup_parser();
 
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
             let root = &directories[0];
             assert_eq!(root.entry_point.symbols.len(), 2);
             assert!(matches!(
                 &root.entry_point.symbols[0],
-                RustSymbol::ModuleBlock { name, is_public: true, content: child_content, doc_comment: None }
+                RustSymbol::ModuleBlock { name, visibility: Visibility::Public, content: child_content, doc_comment: None, .. }
                 if name == "child" &&
-                matches!(&child_content[0], RustSymbol::ModuleBlock { name, is_public: true, content: grandchild_content, doc_comment: None } if name == "grandchild" &&
-                  matches!(&grandchild_content[0], RustSymbol::Symbol { symbol } if symbol.name == "Format")
+                matches!(&child_content[0], RustSymbol::ModuleBlock { name, visibility: Visibility::Public, content: grandchild_content, doc_comment: None, .. } if name == "grandchild" &&
+                  matches!(&grandchild_content[0], RustSymbol::Symbol { symbol, .. } if symbol.name == "Format")
                   )
             ));
             assert_matches!(
                 &root.entry_point.symbols[1],
-                RustSymbol::Reexport { source_path, import_type: ImportType::Simple }
+                RustSymbol::Reexport { source_path, import_type: ImportType::Simple, .. }
                 if source_path == "child::grandchild::Format"
             )
         }
@@ -524,19 +996,21 @@ pub use child::grandchild::Format;
                 .unwrap();
             let mut parser = setup_parser();
 
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
             let root = get_module_directory("", &directories).unwrap();
             assert_eq!(root.entry_point.symbols.len(), 2);
             assert_matches!(
                 &root.entry_point.symbols[0],
-                RustSymbol::ModuleImport { name, is_reexported: false }
+                RustSymbol::ModuleImport { name, visibility: Visibility::Private, .. }
                 if name == "module"
             );
             assert_matches!(
                 &root.entry_point.symbols[1],
-                RustSymbol::Reexport { source_path, import_type }
+                RustSymbol::Reexport { source_path, import_type, .. }
                 if source_path == "module" && matches!(import_type, ImportType::Wildcard)
             );
 
@@ -544,7 +1018,7 @@ pub use child::grandchild::Format;
             assert_eq!(module_file.symbols.len(), 1);
             assert_matches!(
                 &module_file.symbols[0],
-                RustSymbol::Symbol { symbol } if symbol.name == "InnerStruct"
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "InnerStruct"
             )
         }
 
@@ -570,19 +1044,21 @@ pub use child::grandchild::Format;
                 .unwrap();
             let mut parser = setup_parser();
 
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
             let root = get_module_directory("", &directories).unwrap();
             assert_eq!(root.entry_point.symbols.len(), 2);
             assert_matches!(
                 &root.entry_point.symbols[0],
-                RustSymbol::ModuleImport { name, is_reexported: false }
+                RustSymbol::ModuleImport { name, visibility: Visibility::Private, .. }
                 if name == "submodule"
             );
             assert_matches!(
                 &root.entry_point.symbols[1],
-                RustSymbol::Reexport { source_path, import_type }
+                RustSymbol::Reexport { source_path, import_type, .. }
                 if source_path == "submodule::Foo" && matches!(import_type, ImportType::Aliased(alias) if alias == "Bar")
             );
 
@@ -590,7 +1066,7 @@ pub use child::grandchild::Format;
             assert_eq!(submodule_file.symbols.len(), 1);
             assert_matches!(
                 &submodule_file.symbols[0],
-                RustSymbol::Symbol { symbol } if symbol.name == "Foo"
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "Foo"
             )
         }
 
@@ -616,18 +1092,20 @@ pub use child::grandchild::Format;
                 .unwrap();
 
             let mut parser = setup_parser();
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
             let root = get_module_directory("", &directories).unwrap();
             assert_eq!(root.entry_point.symbols.len(), 2);
             assert_matches!(
                 &root.entry_point.symbols[0],
-                RustSymbol::ModuleImport { name, is_reexported: false } if name == "my_mod"
+                RustSymbol::ModuleImport { name, visibility: Visibility::Private, .. } if name == "my_mod"
             );
             assert_matches!(
                 &root.entry_point.symbols[1],
-                RustSymbol::Reexport { source_path, import_type }
+                RustSymbol::Reexport { source_path, import_type, .. }
                 if source_path == "my_mod::MyStruct" && matches!(import_type, ImportType::Simple)
             );
 
@@ -635,7 +1113,7 @@ pub use child::grandchild::Format;
             assert_eq!(my_mod_file.symbols.len(), 1);
             assert_matches!(
                 &my_mod_file.symbols[0],
-                RustSymbol::Symbol { symbol } if symbol.name == "MyStruct"
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "MyStruct"
             )
         }
     }
@@ -658,7 +1136,9 @@ pub struct Test {}
                 .unwrap();
 
             let mut parser = setup_parser();
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
             assert_eq!(directories[0].name, "");
@@ -686,15 +1166,17 @@ pub mod inner {
                 .unwrap();
 
             let mut parser = setup_parser();
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 1);
-            let root = directories.get(0).unwrap();
+            let root = directories.first().unwrap();
             assert_eq!(root.name, "");
             assert_eq!(root.entry_point.symbols.len(), 1);
             assert_matches!(
                 &root.entry_point.symbols[0],
-                RustSymbol::ModuleBlock { name, is_public: true, doc_comment, .. }
+                RustSymbol::ModuleBlock { name, visibility: Visibility::Public, doc_comment, .. }
                 if name == "inner" && *doc_comment == Some("//! This is the inner doc comment\n".to_string())
             )
         }
@@ -718,7 +1200,9 @@ pub mod inner {
                 .unwrap();
             let mut parser = setup_parser();
 
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 2);
             assert!(get_module_directory("", &directories).is_some());
@@ -728,7 +1212,7 @@ pub mod inner {
             assert_eq!(submodule.symbols.len(), 1);
             assert_matches!(
                 &submodule.symbols[0],
-                RustSymbol::Symbol { symbol } if symbol.name == "SubStruct"
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "SubStruct"
             )
         }
 
@@ -746,7 +1230,9 @@ pub mod inner {
                 .unwrap();
             let mut parser = setup_parser();
 
-            let directories = collect_module_directories(&lib_rs, &mut parser).unwrap();
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
 
             assert_eq!(directories.len(), 2);
             assert!(get_module_directory("", &directories).is_some());
@@ -756,8 +1242,711 @@ pub mod inner {
             assert_eq!(submodule.symbols.len(), 1);
             assert_matches!(
                 &submodule.symbols[0],
-                RustSymbol::Symbol { symbol } if symbol.name == "SubStruct"
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "SubStruct"
+            )
+        }
+    }
+
+    mod path_attributes {
+        use super::*;
+        use crate::api::test_helpers::get_module_directory;
+
+        #[test]
+        fn relative_to_entry_point() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[path = "platform/unix.rs"]
+pub mod sys;
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/platform/unix.rs", r#"pub struct Handle;"#)
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
+
+            assert_eq!(directories.len(), 2);
+            let sys = get_module_directory("sys", &directories).unwrap();
+            assert!(sys.is_public);
+            assert_matches!(
+                &sys.entry_point.symbols[0],
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "Handle"
+            );
+        }
+
+        #[test]
+        fn relative_to_non_mod_rs_file() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file("src/lib.rs", r#"pub mod module;"#)
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "src/module.rs",
+                    r#"
+pub mod submodule;
+#[path = "other.rs"]
+pub mod other;
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/module/submodule.rs", r#"pub struct Sub;"#)
+                .unwrap();
+            temp_dir
+                .create_file("src/other.rs", r#"pub struct Other;"#)
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
+
+            let other = get_module_directory("module::other", &directories).unwrap();
+            assert_matches!(
+                &other.entry_point.symbols[0],
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "Other"
+            );
+        }
+
+        #[test]
+        fn inside_leaf_module_file() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file("src/lib.rs", r#"pub mod module;"#)
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "src/module.rs",
+                    r#"
+pub struct Module;
+#[path = "platform/unix.rs"]
+pub mod sys;
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/platform/unix.rs", r#"pub struct Handle;"#)
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
+
+            let module = get_module_directory("module", &directories).unwrap();
+            assert_matches!(
+                &module.entry_point.symbols[0],
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "Module"
+            );
+            let sys = get_module_directory("module::sys", &directories).unwrap();
+            assert_matches!(
+                &sys.entry_point.symbols[0],
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "Handle"
+            );
+        }
+
+        #[test]
+        fn nested_modules_relative_to_file() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[path = "platform/unix.rs"]
+pub mod sys;
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/platform/unix.rs", r#"pub mod fd;"#)
+                .unwrap();
+            temp_dir
+                .create_file("src/platform/fd.rs", r#"pub struct Fd;"#)
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
+
+            let sys = get_module_directory("sys", &directories).unwrap();
+            let fd = sys.internal_files.get("fd").unwrap();
+            assert_matches!(
+                &fd.symbols[0],
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "Fd"
+            );
+        }
+
+        #[test]
+        fn inside_inline_module() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+pub mod inline {
+    #[path = "unix.rs"]
+    pub mod sys;
+}
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/inline/unix.rs", r#"pub struct Handle;"#)
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
+
+            let sys = get_module_directory("inline::sys", &directories).unwrap();
+            assert_matches!(
+                &sys.entry_point.symbols[0],
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "Handle"
+            );
+        }
+
+        #[test]
+        fn missing_file() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[path = "missing.rs"]
+mod sys;
+"#,
+                )
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let result =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser);
+
+            assert_matches!(result, Err(ExtractionError::Malformed(_)));
+        }
+    }
+
+    mod inline_module_imports {
+        use super::*;
+
+        #[test]
+        fn module_file_inside_inline_module() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+pub mod inline {
+    pub mod submodule;
+}
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/inline/submodule.rs", r#"pub struct Sub;"#)
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
+
+            assert_eq!(directories.len(), 1);
+            let submodule = directories[0]
+                .internal_files
+                .get("inline::submodule")
+                .unwrap();
+            assert_matches!(
+                &submodule.symbols[0],
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "Sub"
+            );
+        }
+    }
+
+    mod cfg_pruning {
+        use super::*;
+
+        fn make_options(features: &[&str]) -> ExtractionOptions {
+            ExtractionOptions::new()
+                .with_features(features.iter().copied())
+                .with_target_options([("unix".to_string(), None)])
+        }
+
+        #[test]
+        fn disabled_symbol() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[cfg(windows)]
+pub struct Handle;
+
+#[cfg(unix)]
+pub struct Fd;
+"#,
+                )
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &make_options(&[]), &mut parser).unwrap();
+
+            let symbols = &directories[0].entry_point.symbols;
+            assert_eq!(symbols.len(), 1);
+            assert_matches!(
+                &symbols[0],
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "Fd"
+            );
+        }
+
+        #[test]
+        fn disabled_symbol_in_module_block() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+pub mod inline {
+    #[cfg(feature = "serde")]
+    pub fn serialize() {}
+}
+"#,
+                )
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &make_options(&[]), &mut parser).unwrap();
+
+            assert_matches!(
+                &directories[0].entry_point.symbols[0],
+                RustSymbol::ModuleBlock { content, .. } if content.is_empty()
+            );
+        }
+
+        #[test]
+        fn disabled_module_import() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[cfg(feature = "serde")]
+pub mod serde;
+"#,
+                )
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &make_options(&[]), &mut parser).unwrap();
+
+            assert_eq!(directories.len(), 1);
+            assert!(directories[0].entry_point.symbols.is_empty());
+            assert!(directories[0].internal_files.is_empty());
+        }
+
+        #[test]
+        fn enabled_module_import() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[cfg(feature = "serde")]
+pub mod serde;
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "src/serde.rs",
+                    r#"
+pub fn serialize() {}
+
+#[cfg(not(unix))]
+pub fn serialize_handle() {}
+"#,
+                )
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &make_options(&["serde"]), &mut parser)
+                    .unwrap();
+
+            let serde = directories[0].internal_files.get("serde").unwrap();
+            assert_eq!(serde.symbols.len(), 1);
+            assert_matches!(
+                &serde.symbols[0],
+                RustSymbol::Symbol { symbol, .. } if symbol.name == "serialize"
+            );
+        }
+
+        #[test]
+        fn disabled_reexport() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[cfg_attr(not(feature = "std"), cfg(any()))]
+pub use std::io::Error;
+"#,
+                )
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &make_options(&[]), &mut parser).unwrap();
+
+            assert!(directories[0].entry_point.symbols.is_empty());
+        }
+    }
+
+    mod cfg_retention {
+        use super::*;
+
+        #[test]
+        fn disabled_symbols_retained() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[cfg(target_os = "plan9")]
+pub struct Handle;
+"#,
+                )
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories = collect_module_directories(
+                &lib_rs,
+                &ExtractionOptions::new().with_cfg_annotations(),
+                &mut parser,
+            )
+            .unwrap();
+
+            assert_matches!(
+                &directories[0].entry_point.symbols[0],
+                RustSymbol::Symbol { symbol, cfg: Some(_), .. } if symbol.name == "Handle"
+            );
+        }
+
+        #[test]
+        fn module_directory_predicate() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[cfg(feature = "a")]
+pub mod inline {
+    #[cfg(feature = "b")]
+    pub mod submodule;
+}
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/inline/submodule/mod.rs", "pub struct Sub;")
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories = collect_module_directories(
+                &lib_rs,
+                &ExtractionOptions::new().with_cfg_annotations(),
+                &mut parser,
+            )
+            .unwrap();
+
+            assert_eq!(directories.len(), 2);
+            assert_eq!(directories[0].cfg, None);
+            assert_eq!(directories[1].name, "inline::submodule");
+            assert_eq!(
+                directories[1].cfg.as_ref().map(|cfg| cfg.to_string()),
+                Some("all(feature = \"a\", feature = \"b\")".to_string())
+            );
+        }
+    }
+
+    mod missing_modules {
+        use super::*;
+
+        #[test]
+        fn rejected_by_default() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file("src/lib.rs", "pub mod generated;")
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let result =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser);
+
+            assert_matches!(result, Err(ExtractionError::Malformed(_)));
+        }
+
+        #[test]
+        fn tolerated_on_request() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+pub mod generated;
+pub mod present;
+mod inline {
+    #[path = "missing.rs"]
+    mod sys;
+}
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/present.rs", "pub fn present_function() {}")
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories = collect_module_directories(
+                &lib_rs,
+                &ExtractionOptions::new().with_missing_modules_tolerated(),
+                &mut parser,
             )
+            .unwrap();
+
+            assert_eq!(directories.len(), 1);
+            let directory = &directories[0];
+            assert!(directory.internal_files.contains_key("present"));
+            let unresolved_modules: Vec<_> = directory
+                .unresolved_modules
+                .iter()
+                .map(|module| module.name.as_str())
+                .collect();
+            assert_eq!(unresolved_modules, vec!["generated", "inline::sys"]);
+            let generated = &directory.unresolved_modules[0];
+            assert_eq!(generated.location.path, lib_rs);
+            assert_eq!(generated.location.span.start_line, 2);
+            assert_contains!(generated.message, "generated");
+        }
+    }
+
+    mod source_includes {
+        use super::*;
+
+        fn get_symbol_names(symbols: &[RustSymbol]) -> Vec<&str> {
+            symbols
+                .iter()
+                .flat_map(|symbol| match symbol {
+                    RustSymbol::Symbol { symbol, .. } => vec![symbol.name.as_str()],
+                    RustSymbol::IncludedFile { symbols, .. } => get_symbol_names(symbols),
+                    _ => Vec::new(),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn relative_include() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+pub mod generated {
+    include!("generated/mod.rs");
+}
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "src/generated/mod.rs",
+                    "pub struct Message;\ninclude!(\"nested.rs\");",
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/generated/nested.rs", "pub struct Nested;")
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
+
+            let content = directories[0].entry_point.get_module("generated").unwrap();
+            assert_eq!(get_symbol_names(content), vec!["Message", "Nested"]);
+        }
+
+        #[test]
+        fn included_file_path() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    "pub struct Root;
+include!(\"generated.rs\");",
+                )
+                .unwrap();
+            let generated_rs = temp_dir
+                .create_file("src/generated.rs", "\npub struct Generated;")
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
+
+            assert_matches!(
+                &directories[0].entry_point.symbols[1],
+                RustSymbol::IncludedFile { path, symbols, .. }
+                if *path == generated_rs.canonicalize().unwrap() && matches!(
+                    &symbols[..],
+                    [RustSymbol::Symbol { span, .. }] if span.start_line == 2
+                )
+            );
+        }
+
+        #[test]
+        fn out_dir_include() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"include!(concat!(env!("OUT_DIR"), "/bindings.rs"));"#,
+                )
+                .unwrap();
+            let stale_bindings_rs = temp_dir
+                .create_file("target/stale/out/other.rs", "pub struct Stale;")
+                .unwrap();
+            let bindings_rs = temp_dir
+                .create_file("target/debug/out/bindings.rs", "pub struct Bindings;")
+                .unwrap();
+            let options = ExtractionOptions::new()
+                .with_out_dir(stale_bindings_rs.parent().unwrap())
+                .with_out_dir(bindings_rs.parent().unwrap());
+            let mut parser = setup_parser();
+
+            let directories = collect_module_directories(&lib_rs, &options, &mut parser).unwrap();
+
+            assert_eq!(
+                get_symbol_names(&directories[0].entry_point.symbols),
+                vec!["Bindings"]
+            );
+        }
+
+        #[test]
+        fn missing_file() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"include!(concat!(env!("OUT_DIR"), "/bindings.rs"));"#,
+                )
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
+
+            assert_matches!(
+                &directories[0].entry_point.symbols[..],
+                [RustSymbol::SourceInclude { .. }]
+            );
+        }
+
+        #[test]
+        fn cyclic_include() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file("src/lib.rs", r#"include!("generated.rs");"#)
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "src/generated.rs",
+                    "pub struct Generated;\ninclude!(\"generated.rs\");",
+                )
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
+
+            let symbols = &directories[0].entry_point.symbols;
+            assert_eq!(get_symbol_names(symbols), vec!["Generated"]);
+            assert_matches!(
+                &symbols[..],
+                [RustSymbol::IncludedFile { symbols: included_symbols, .. }]
+                if matches!(included_symbols[1], RustSymbol::SourceInclude { .. })
+            );
+        }
+
+        #[test]
+        fn disabled_include() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[cfg(feature = "serde")]
+include!("serde.rs");
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/serde.rs", "pub fn serialize() {}")
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories =
+                collect_module_directories(&lib_rs, &ExtractionOptions::default(), &mut parser)
+                    .unwrap();
+
+            assert!(directories[0].entry_point.symbols.is_empty());
+        }
+
+        #[test]
+        fn inherited_predicate() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[cfg(feature = "serde")]
+include!("serde.rs");
+"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/serde.rs", "pub fn serialize() {}")
+                .unwrap();
+            let mut parser = setup_parser();
+
+            let directories = collect_module_directories(
+                &lib_rs,
+                &ExtractionOptions::new().with_cfg_annotations(),
+                &mut parser,
+            )
+            .unwrap();
+
+            assert_matches!(
+                &directories[0].entry_point.symbols[..],
+                [RustSymbol::IncludedFile { symbols, cfg: Some(_), .. }]
+                if get_symbol_names(symbols) == vec!["serialize"]
+            );
         }
     }
 }
@@ -2,21 +2,72 @@ use daipendency_extractor::ExtractionError;
 use daipendency_extractor::Symbol;
 use regex::escape;
 use regex::Regex;
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 
-use super::module_directory::{Module, ModuleItem};
-use super::parsing::ImportType;
+use super::module_directory::{Module, ModuleItem, SourceLocation, UnresolvedInclude};
+use super::options::ExtractionOptions;
+use super::parsing::{ImportType, SymbolKind};
 
+/// A public symbol, along with the modules it's exposed in.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct SymbolDeclaration {
+    /// The symbol, named as it's exposed (e.g. under its alias if reexported as such)
     pub symbol: Symbol,
+    /// The modules where the symbol is public, whether by declaration or reexport (e.g.
+    /// `["", "formatting"]`)
     pub modules: Vec<String>,
+    /// Whether the symbol is hidden from the documentation (i.e. `#[doc(hidden)]`)
+    pub is_doc_hidden: bool,
+    /// The kind of item the symbol stands for
+    pub kind: SymbolKind,
+    /// The generic parameters of the symbol, if any (e.g. `<T: Clone>`)
+    pub generics: Option<String>,
+    /// The module defining the symbol (e.g. `formatting`), or the path to it prefixed with `::` if
+    /// it's defined in an external crate (e.g. `::serde_json::value`)
+    pub module_path: String,
+    /// The path to the item the symbol stands for, where it's defined (e.g.
+    /// `crate::internal::codec::Format` or `::serde_json::Value`), or relative to the crate root if
+    /// it couldn't be resolved (e.g. `crate::missing::Foo`), or the path to the globbed module for
+    /// unresolved globs
+    pub canonical_path: String,
+    /// All the public paths the item is reachable at, including under other names (e.g.
+    /// `["crate::Format", "crate::codec::Format"]`)
+    pub public_paths: Vec<String>,
+    /// The location of the symbol's declaration (e.g. `pub struct Foo;`), or of the reexport it
+    /// stems from if the item isn't declared in the crate (e.g. `pub use serde_json::Value;`)
+    pub location: SourceLocation,
+    /// The reexport the symbol stands for, if it's reexported from an external crate
+    pub external_reexport: Option<ExternalReexport>,
 }
 
+/// A reexport of an item from an external crate (e.g. `pub use serde_json::Value;`).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ExternalReexport {
+    /// The path to the item, starting with the name of its crate (e.g. `serde_json::Value`)
+    pub source_path: String,
+    /// How the item is imported (e.g. with an alias)
+    pub import_type: ImportType,
+}
+
+/// The public symbols of the crate, as resolved from the declarations and reexports in its modules.
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct SymbolResolution {
+    /// The public symbols of the crate
     pub symbols: Vec<SymbolDeclaration>,
+    /// The inner doc comments of the public modules, keyed by module path (e.g. "" for the crate
+    /// root)
     pub doc_comments: HashMap<String, String>,
+    /// The locations of the modules, keyed by module path
+    pub module_locations: HashMap<String, SourceLocation>,
+    /// The paths to the public modules whose files couldn't be found
+    pub unresolved_modules: HashSet<String>,
+    /// The `include!` invocations of the public modules whose files couldn't be found, keyed by
+    /// module path
+    pub unresolved_includes: HashMap<String, Vec<UnresolvedInclude>>,
 }
 
 #[derive(Debug)]
@@ -24,20 +75,314 @@ struct SymbolReference {
     source_path: String,
     referencing_module: String,
     import_type: ImportType,
+    is_doc_hidden: bool,
+    location: SourceLocation,
 }
 
 /// Resolve symbol references by matching them with their corresponding definitions.
-pub fn resolve_symbols(modules: &[Module]) -> Result<SymbolResolution, ExtractionError> {
-    let symbols = resolve_public_symbols(modules)?;
+///
+/// Items hidden from the documentation (i.e. `#[doc(hidden)]`) are excluded unless `options`
+/// include them.
+///
+/// `dependencies` are the external crates keyed by the name they're referred to in the code, and
+/// reexports from them are preserved as `pub use` declarations of the crates they refer to.
+pub fn resolve_symbols(
+    modules: &[Module],
+    options: &ExtractionOptions,
+    dependencies: &HashMap<String, String>,
+) -> Result<SymbolResolution, ExtractionError> {
+    let include_doc_hidden = options.include_doc_hidden;
+    let mut modules = attach_impl_blocks(modules)?;
+    mark_external_reexports(&mut modules, dependencies);
+    if !include_doc_hidden {
+        hide_doc_hidden_items(&mut modules);
+    }
 
-    let doc_comments = get_doc_comments_by_module(modules);
+    let mut symbols = resolve_public_symbols(&modules)?;
+    if !include_doc_hidden {
+        symbols.retain(|declaration| !declaration.is_doc_hidden);
+    }
+    assign_public_paths(&mut symbols);
+
+    let doc_comments = get_doc_comments_by_module(&modules);
+    let module_locations = modules
+        .iter()
+        .map(|module| (module.name.clone(), module.location.clone()))
+        .collect();
+    let unresolved_modules = modules
+        .iter()
+        .filter(|module| module.is_unresolved && module.is_public)
+        .map(|module| module.name.clone())
+        .collect();
+    let unresolved_includes = modules
+        .iter()
+        .filter(|module| !module.unresolved_includes.is_empty() && module.is_public)
+        .map(|module| (module.name.clone(), module.unresolved_includes.clone()))
+        .collect();
 
     Ok(SymbolResolution {
         symbols,
         doc_comments,
+        module_locations,
+        unresolved_modules,
+        unresolved_includes,
     })
 }
 
+/// Append each implementation block to the source code of the type it implements, so that the
+/// implementation travels with the type wherever it's reexported.
+///
+/// The type paths of the implementation blocks are resolved against the scopes of their modules,
+/// and the blocks whose types can't be found in the crate are dropped.
+fn attach_impl_blocks(modules: &[Module]) -> Result<Vec<Module>, ExtractionError> {
+    let mut attached_modules: Vec<Module> = modules.to_vec();
+    let scopes = ModuleScopes::new(modules)?;
+
+    for module in modules {
+        for item in &module.symbols {
+            let ModuleItem::ImplBlock {
+                type_path,
+                source_code,
+            } = item
+            else {
+                continue;
+            };
+
+            if let Some((module_index, item_index)) =
+                scopes.resolve_type_path(type_path, &module.name)?
+            {
+                if let ModuleItem::Symbol { symbol, .. } =
+                    &mut attached_modules[module_index].symbols[item_index]
+                {
+                    symbol.source_code.push_str("\n\n");
+                    symbol.source_code.push_str(source_code);
+                }
+            }
+        }
+    }
+
+    for module in &mut attached_modules {
+        module
+            .symbols
+            .retain(|item| !matches!(item, ModuleItem::ImplBlock { .. }));
+    }
+
+    Ok(attached_modules)
+}
+
+/// The items in scope in each module: the symbols it defines, and the items it imports or
+/// reexports.
+struct ModuleScopes {
+    /// The location of each symbol (module index and item index), keyed by its path.
+    symbol_locations: HashMap<String, (usize, usize)>,
+    module_paths: HashSet<String>,
+    /// The imports and reexports of each module, keyed by the module path.
+    imports: HashMap<String, Vec<(String, ImportType)>>,
+}
+
+/// The number of imports followed when resolving a path, so that import cycles terminate.
+const MAX_IMPORT_DEPTH: usize = 16;
+
+/// The crates of the standard library, which every crate may refer to without depending on them.
+const STANDARD_LIBRARY_CRATES: [&str; 3] = ["std", "core", "alloc"];
+
+impl ModuleScopes {
+    fn new(modules: &[Module]) -> Result<Self, ExtractionError> {
+        let mut symbol_locations = HashMap::new();
+        let mut imports: HashMap<String, Vec<(String, ImportType)>> = HashMap::new();
+        for (module_index, module) in modules.iter().enumerate() {
+            for (item_index, item) in module.symbols.iter().enumerate() {
+                match item {
+                    ModuleItem::Symbol { symbol, .. } => {
+                        symbol_locations.insert(
+                            get_symbol_path_from_module(&symbol.name, module),
+                            (module_index, item_index),
+                        );
+                    }
+                    ModuleItem::Import {
+                        source_path,
+                        import_type,
+                    }
+                    | ModuleItem::SymbolReexport {
+                        source_path,
+                        import_type,
+                        ..
+                    } => {
+                        if is_external_path(source_path) {
+                            continue;
+                        }
+                        let normalised_path =
+                            normalise_module_reference(source_path, &module.name)?;
+                        imports
+                            .entry(module.name.clone())
+                            .or_default()
+                            .push((normalised_path, import_type.clone()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(Self {
+            symbol_locations,
+            module_paths: modules.iter().map(|module| module.name.clone()).collect(),
+            imports,
+        })
+    }
+
+    /// Resolve the path of a type (e.g. `Foo`, `super::Foo` or `inner::Foo`) from `module_path`.
+    fn resolve_type_path(
+        &self,
+        type_path: &str,
+        module_path: &str,
+    ) -> Result<Option<(usize, usize)>, ExtractionError> {
+        let is_relative = ["crate::", "super::", "self::"]
+            .iter()
+            .any(|prefix| type_path.starts_with(prefix));
+        Ok(if is_relative {
+            self.resolve(&normalise_reference(type_path, module_path)?, "", 0)
+        } else {
+            self.resolve(type_path, module_path, 0)
+        })
+    }
+
+    /// Resolve `path` in the scope of `module_path`.
+    fn resolve(&self, path: &str, module_path: &str, depth: usize) -> Option<(usize, usize)> {
+        if depth > MAX_IMPORT_DEPTH {
+            return None;
+        }
+        let (head, rest) = match path.split_once("::") {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (path, None),
+        };
+
+        if rest.is_none() {
+            let symbol_path = get_symbol_path_from_module_path(head, module_path);
+            if let Some(location) = self.symbol_locations.get(&symbol_path) {
+                return Some(*location);
+            }
+        }
+
+        let imports = self.imports.get(module_path).into_iter().flatten();
+        for (source_path, import_type) in imports.clone() {
+            let imported_name = match import_type {
+                ImportType::Simple => source_path.rsplit("::").next().unwrap(),
+                ImportType::Aliased(alias) => alias,
+                ImportType::Wildcard => continue,
+            };
+            if imported_name == head {
+                let imported_path = match rest {
+                    Some(rest) => format!("{}::{}", source_path, rest),
+                    None => source_path.clone(),
+                };
+                if let Some(location) = self.resolve(&imported_path, "", depth + 1) {
+                    return Some(location);
+                }
+            }
+        }
+
+        if let Some(rest) = rest {
+            let submodule_path = get_symbol_path_from_module_path(head, module_path);
+            if self.module_paths.contains(&submodule_path) {
+                return self.resolve(rest, &submodule_path, depth + 1);
+            }
+        }
+
+        imports
+            .filter(|(_, import_type)| *import_type == ImportType::Wildcard)
+            .filter(|(source_path, _)| self.module_paths.contains(source_path))
+            .find_map(|(source_path, _)| self.resolve(path, source_path, depth + 1))
+    }
+}
+
+/// Prefix the paths of reexports from external crates with `::` (e.g. `::foo::Bar`), so that they
+/// aren't mistaken for local paths, and replace crate aliases with the crates they refer to.
+///
+/// The crates of the standard library are external crates too, even though they aren't
+/// dependencies. Crate aliases (e.g. `extern crate foo as bar;`) are assumed to apply to the whole
+/// crate.
+fn mark_external_reexports(modules: &mut [Module], dependencies: &HashMap<String, String>) {
+    let mut external_crates = dependencies.clone();
+    for crate_name in STANDARD_LIBRARY_CRATES {
+        external_crates
+            .entry(crate_name.to_string())
+            .or_insert_with(|| crate_name.to_string());
+    }
+    for module in modules.iter() {
+        for item in &module.symbols {
+            if let ModuleItem::ExternCrate {
+                name,
+                alias: Some(alias),
+            } = item
+            {
+                let crate_name = dependencies.get(name).unwrap_or(name).clone();
+                external_crates.insert(alias.clone(), crate_name);
+            }
+        }
+    }
+    let local_module_paths: HashSet<String> =
+        modules.iter().map(|module| module.name.clone()).collect();
+
+    for module in modules.iter_mut() {
+        for item in &mut module.symbols {
+            let ModuleItem::SymbolReexport { source_path, .. } = item else {
+                continue;
+            };
+            let path = source_path.trim_start_matches("::");
+            let (root, rest) = match path.split_once("::") {
+                Some((root, rest)) => (root, Some(rest)),
+                None => (path, None),
+            };
+            let is_explicitly_external = source_path.starts_with("::");
+            let is_local_module =
+                local_module_paths.contains(&get_symbol_path_from_module_path(root, &module.name));
+            let crate_name = match external_crates.get(root) {
+                Some(crate_name) if is_explicitly_external || !is_local_module => crate_name,
+                _ if is_explicitly_external => root,
+                _ => continue,
+            };
+            // `extern crate self as foo;` makes `foo` an alias of the current crate
+            let crate_root = if crate_name == "self" {
+                "crate".to_string()
+            } else {
+                format!("::{}", crate_name)
+            };
+            *source_path = match rest {
+                Some(rest) => format!("{}::{}", crate_root, rest),
+                None => crate_root,
+            };
+        }
+    }
+}
+
+/// Make hidden modules (and their descendants) private, and drop hidden reexports.
+///
+/// Hidden symbols are retained so that reexports of them are resolved (and then excluded) rather
+/// than recreated.
+fn hide_doc_hidden_items(modules: &mut [Module]) {
+    let hidden_module_paths: Vec<String> = modules
+        .iter()
+        .filter(|module| module.is_doc_hidden)
+        .map(|module| module.name.clone())
+        .collect();
+    for module in modules {
+        let is_hidden = hidden_module_paths.iter().any(|hidden_path| {
+            module.name == *hidden_path || module.name.starts_with(&format!("{}::", hidden_path))
+        });
+        if is_hidden {
+            module.is_public = false;
+        }
+        module.symbols.retain(|item| {
+            !matches!(
+                item,
+                ModuleItem::SymbolReexport {
+                    is_doc_hidden: true,
+                    ..
+                }
+            )
+        });
+    }
+}
+
 fn resolve_public_symbols(
     all_modules: &[Module],
 ) -> Result<Vec<SymbolDeclaration>, ExtractionError> {
@@ -90,27 +435,59 @@ fn collect_symbols_and_references(
     for module in all_modules {
         for symbol in &module.symbols {
             match symbol {
-                ModuleItem::Symbol { symbol } => {
+                ModuleItem::Symbol {
+                    symbol,
+                    kind,
+                    generics,
+                    is_doc_hidden,
+                    location,
+                } => {
                     let symbol_path = get_symbol_path_from_module(&symbol.name, module);
-                    resolved_symbols.insert(
-                        symbol_path.clone(),
-                        SymbolDeclaration {
-                            symbol: symbol.clone(),
-                            modules: vec![module.name.clone()],
-                        },
-                    );
+                    match resolved_symbols.entry(symbol_path) {
+                        // Symbols sharing a path are alternatives (e.g. `#[cfg(unix)]` and
+                        // `#[cfg(windows)]` variants), or live in different namespaces (e.g. a
+                        // function and a macro), so we keep them all
+                        Entry::Occupied(mut entry) => {
+                            let source_code = &mut entry.get_mut().symbol.source_code;
+                            source_code.push_str("\n\n");
+                            source_code.push_str(&symbol.source_code);
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(SymbolDeclaration {
+                                symbol: symbol.clone(),
+                                modules: vec![module.name.clone()],
+                                is_doc_hidden: *is_doc_hidden,
+                                kind: *kind,
+                                generics: generics.clone(),
+                                module_path: module.name.clone(),
+                                canonical_path: get_item_path(&symbol.name, &module.name),
+                                public_paths: Vec::new(),
+                                location: location.clone(),
+                                external_reexport: None,
+                            });
+                        }
+                    }
                 }
                 ModuleItem::SymbolReexport {
                     source_path,
                     import_type,
+                    is_doc_hidden,
+                    location,
                 } => {
                     let normalised_path = normalise_reference(source_path, &module.name)?;
                     references.push(SymbolReference {
                         source_path: normalised_path,
                         referencing_module: module.name.clone(),
                         import_type: import_type.clone(),
+                        is_doc_hidden: *is_doc_hidden,
+                        location: location.clone(),
                     });
                 }
+                ModuleItem::ExportedMacro { .. }
+                | ModuleItem::Import { .. }
+                | ModuleItem::ExternCrate { .. }
+                | ModuleItem::EnumVariants { .. }
+                | ModuleItem::ImplBlock { .. } => {}
             }
         }
     }
@@ -134,7 +511,7 @@ fn resolve_references(
         )?;
 
         if declarations.is_empty() {
-            declarations = vec![recreate_reexport(reference)];
+            declarations = vec![recreate_reexport(reference, all_modules)];
         }
 
         for declaration in declarations {
@@ -153,12 +530,26 @@ fn resolve_references(
                         symbol: Symbol {
                             name: alias.clone(),
                             source_code: if all_public_in_chain {
-                                format!("pub use {} as {};", reference.source_path, alias)
+                                format!(
+                                    "pub use {} as {};",
+                                    reference.source_path.trim_start_matches("::"),
+                                    alias
+                                )
                             } else {
-                                rename_symbol_in_source_code(&declaration, alias)
+                                rename_symbol_in_source_code(&declaration.symbol, alias)
                             },
                         },
                         modules: vec![reference.referencing_module.clone()],
+                        // The alias is a new name, so whether it's hidden is up to the reexport
+                        // (e.g. `pub use __hidden_impl as nice;`)
+                        is_doc_hidden: reference.is_doc_hidden,
+                        kind: declaration.kind,
+                        generics: declaration.generics.clone(),
+                        module_path: declaration.module_path.clone(),
+                        canonical_path: declaration.canonical_path.clone(),
+                        public_paths: Vec::new(),
+                        location: declaration.location.clone(),
+                        external_reexport: declaration.external_reexport.clone(),
                     };
 
                     all_declarations.insert(alias_key, aliased_symbol);
@@ -201,11 +592,11 @@ fn resolve_references(
     Ok(())
 }
 
-fn rename_symbol_in_source_code(declaration: &SymbolDeclaration, alias: &String) -> String {
-    let old_name = &declaration.symbol.name;
+pub fn rename_symbol_in_source_code(symbol: &Symbol, alias: &str) -> String {
+    let old_name = &symbol.name;
     let old_name_regex = Regex::new(&format!(r"\b{}\b", escape(old_name))).unwrap();
     let new_source_code = old_name_regex
-        .replace_all(&declaration.symbol.source_code, alias)
+        .replace_all(&symbol.source_code, alias)
         .to_string();
     new_source_code
 }
@@ -217,7 +608,8 @@ fn resolve_symbol_reference(
     visited: &mut HashSet<String>,
     all_modules: &[Module],
 ) -> Result<Vec<SymbolDeclaration>, ExtractionError> {
-    if !visited.insert(target_ref.source_path.clone()) {
+    if !visited.insert(target_ref.source_path.clone()) || is_external_path(&target_ref.source_path)
+    {
         return Ok(Vec::new());
     }
 
@@ -242,6 +634,34 @@ fn resolve_symbol_reference(
             }
             return Ok(target_module_declarations);
         }
+
+        let enum_variants = find_enum_variants(&target_module_path, all_modules)
+            .or_else(|| find_enum_variants(&target_ref.source_path, all_modules));
+        if let Some((enum_module, enum_name, variants)) = enum_variants {
+            return Ok(variants
+                .iter()
+                .map(|variant| {
+                    let variant_path = format!("{}::{}", target_ref.source_path, variant);
+                    make_variant_declaration(target_ref, &variant_path, enum_module, enum_name)
+                })
+                .collect());
+        }
+    } else if let Some((enum_path, variant)) = target_ref.source_path.rsplit_once("::") {
+        let enum_variants = find_enum_variants(
+            &get_symbol_path_from_module_path(enum_path, &target_ref.referencing_module),
+            all_modules,
+        )
+        .or_else(|| find_enum_variants(enum_path, all_modules));
+        if let Some((enum_module, enum_name, variants)) = enum_variants {
+            if variants.iter().any(|name| name == variant) {
+                return Ok(vec![make_variant_declaration(
+                    target_ref,
+                    &target_ref.source_path,
+                    enum_module,
+                    enum_name,
+                )]);
+            }
+        }
     }
 
     let full_path =
@@ -298,6 +718,51 @@ fn resolve_symbol_reference(
     Ok(found_symbols)
 }
 
+/// Find the variants of the enum at `enum_path` (e.g. `colours::Colour`), along with the module
+/// defining the enum and its name, if any.
+fn find_enum_variants<'a>(
+    enum_path: &str,
+    all_modules: &'a [Module],
+) -> Option<(&'a Module, &'a str, &'a Vec<String>)> {
+    all_modules.iter().find_map(|module| {
+        module.symbols.iter().find_map(|item| match item {
+            ModuleItem::EnumVariants {
+                enum_name,
+                variants,
+            } if get_symbol_path_from_module(enum_name, module) == enum_path => {
+                Some((module, enum_name.as_str(), variants))
+            }
+            _ => None,
+        })
+    })
+}
+
+/// Make the declaration of an enum variant reexported by `target_ref`, whether on its own or with
+/// the other variants of the enum, given the path to the variant as written (e.g. `Colour::Red`).
+fn make_variant_declaration(
+    target_ref: &SymbolReference,
+    variant_path: &str,
+    enum_module: &Module,
+    enum_name: &str,
+) -> SymbolDeclaration {
+    let variant = variant_path.rsplit("::").next().unwrap();
+    SymbolDeclaration {
+        symbol: Symbol {
+            name: variant.to_string(),
+            source_code: format!("pub use {};", variant_path),
+        },
+        modules: vec![target_ref.referencing_module.clone()],
+        is_doc_hidden: false,
+        kind: SymbolKind::Reexport,
+        generics: None,
+        module_path: enum_module.name.clone(),
+        canonical_path: get_item_path(&format!("{}::{}", enum_name, variant), &enum_module.name),
+        public_paths: Vec::new(),
+        location: target_ref.location.clone(),
+        external_reexport: None,
+    }
+}
+
 fn get_module_declarations(
     target_module: &Module,
     all_declarations: &HashMap<String, SymbolDeclaration>,
@@ -308,21 +773,39 @@ fn get_module_declarations(
     let mut target_module_declarations = Vec::new();
     for symbol in &target_module.symbols {
         match symbol {
-            ModuleItem::Symbol { symbol } => {
+            ModuleItem::Symbol {
+                symbol,
+                kind,
+                generics,
+                is_doc_hidden,
+                location,
+            } => {
                 target_module_declarations.push(SymbolDeclaration {
                     symbol: symbol.clone(),
                     modules: vec![target_module.name.clone()],
+                    is_doc_hidden: *is_doc_hidden,
+                    kind: *kind,
+                    generics: generics.clone(),
+                    module_path: target_module.name.clone(),
+                    canonical_path: get_item_path(&symbol.name, &target_module.name),
+                    public_paths: Vec::new(),
+                    location: location.clone(),
+                    external_reexport: None,
                 });
             }
             ModuleItem::SymbolReexport {
                 source_path,
                 import_type,
+                is_doc_hidden,
+                location,
             } => {
                 let normalised_path = normalise_reference(source_path, &target_module.name)?;
                 let reexport_ref = SymbolReference {
                     source_path: normalised_path,
                     referencing_module: target_module.name.clone(),
                     import_type: import_type.clone(),
+                    is_doc_hidden: *is_doc_hidden,
+                    location: location.clone(),
                 };
                 let resolved_declarations = resolve_symbol_reference(
                     &reexport_ref,
@@ -333,43 +816,116 @@ fn get_module_declarations(
                 )?;
                 target_module_declarations.extend(resolved_declarations);
             }
+            ModuleItem::ExportedMacro { .. }
+            | ModuleItem::Import { .. }
+            | ModuleItem::ExternCrate { .. }
+            | ModuleItem::EnumVariants { .. }
+            | ModuleItem::ImplBlock { .. } => {}
         }
     }
     Ok(target_module_declarations)
 }
 
-fn recreate_reexport(target_ref: &SymbolReference) -> SymbolDeclaration {
+fn is_external_path(path: &str) -> bool {
+    path.starts_with("::")
+}
+
+fn recreate_reexport(target_ref: &SymbolReference, all_modules: &[Module]) -> SymbolDeclaration {
     let modules = vec![target_ref.referencing_module.clone()];
-    match &target_ref.import_type {
-        ImportType::Simple => {
-            let symbol_name = target_ref.source_path.split("::").last().unwrap();
-            SymbolDeclaration {
-                symbol: Symbol {
-                    name: symbol_name.to_string(),
-                    source_code: format!("pub use {};", target_ref.source_path),
-                },
-                modules,
-            }
-        }
-        ImportType::Aliased(alias) => SymbolDeclaration {
-            symbol: Symbol {
-                name: alias.clone(),
-                source_code: format!("pub use {} as {};", target_ref.source_path, alias),
-            },
-            modules,
+    let source_path = target_ref.source_path.trim_start_matches("::");
+    let symbol = match &target_ref.import_type {
+        ImportType::Simple => Symbol {
+            name: source_path.split("::").last().unwrap().to_string(),
+            source_code: format!("pub use {};", source_path),
         },
-        ImportType::Wildcard => SymbolDeclaration {
-            symbol: Symbol {
-                name: target_ref
-                    .source_path
-                    .split("::")
-                    .last()
-                    .unwrap_or(&target_ref.source_path)
-                    .to_string(),
-                source_code: format!("pub use {}::*;", target_ref.source_path),
-            },
-            modules,
+        ImportType::Aliased(alias) => Symbol {
+            name: alias.clone(),
+            source_code: format!("pub use {} as {};", source_path, alias),
         },
+        // Unresolved globs may stand for any number of symbols, so they're named after the glob
+        // itself, which also keeps those of different modules apart
+        ImportType::Wildcard => Symbol {
+            name: format!("{}::*", source_path),
+            source_code: format!("pub use {}::*;", source_path),
+        },
+    };
+    let is_external = is_external_path(&target_ref.source_path);
+    let local_module = all_modules.iter().find(|module| {
+        module.name == source_path
+            || module.name
+                == get_symbol_path_from_module_path(source_path, &target_ref.referencing_module)
+    });
+    let kind = match &target_ref.import_type {
+        ImportType::Wildcard => SymbolKind::UnresolvedGlob,
+        _ if is_external && !source_path.contains("::") => SymbolKind::Module,
+        _ if local_module.is_some() => SymbolKind::Module,
+        _ => SymbolKind::Reexport,
+    };
+    let canonical_path = match local_module {
+        _ if is_external => target_ref.source_path.clone(),
+        Some(module) => format!("crate::{}", module.name),
+        None => get_item_path(source_path, ""),
+    };
+    let external_reexport = is_external.then(|| ExternalReexport {
+        source_path: source_path.to_string(),
+        import_type: target_ref.import_type.clone(),
+    });
+    SymbolDeclaration {
+        symbol,
+        modules,
+        is_doc_hidden: false,
+        kind,
+        generics: None,
+        module_path: target_ref.referencing_module.clone(),
+        canonical_path,
+        public_paths: Vec::new(),
+        location: target_ref.location.clone(),
+        external_reexport,
+    }
+}
+
+/// Set the public paths of each symbol to those of all the symbols standing for the same item,
+/// so that the item can be recognised across namespaces and aliases.
+///
+/// Unresolved globs don't stand for any item in particular, so they have no public paths.
+pub fn assign_public_paths(declarations: &mut [SymbolDeclaration]) {
+    let mut public_paths_by_item: HashMap<String, Vec<String>> = HashMap::new();
+    for declaration in declarations.iter() {
+        if declaration.kind == SymbolKind::UnresolvedGlob {
+            continue;
+        }
+        public_paths_by_item
+            .entry(declaration.canonical_path.clone())
+            .or_default()
+            .extend(
+                declaration
+                    .modules
+                    .iter()
+                    .map(|module| get_item_path(&declaration.symbol.name, module)),
+            );
+    }
+    for public_paths in public_paths_by_item.values_mut() {
+        public_paths.sort();
+        public_paths.dedup();
+    }
+    for declaration in declarations {
+        declaration.public_paths = public_paths_by_item
+            .get(&declaration.canonical_path)
+            .cloned()
+            .unwrap_or_default();
+    }
+}
+
+/// Get the path to an item from the crate root (e.g. `crate::formatting::Format`), or from the
+/// root of its crate if the module is external (e.g. `::serde_json::Value`).
+fn get_item_path(item_name: &str, module_path: &str) -> String {
+    if is_external_path(module_path) {
+        format!("{}::{}", module_path, item_name)
+    } else {
+        format!(
+            "crate::{}",
+            get_symbol_path_from_module_path(item_name, module_path)
+        )
     }
 }
 
@@ -407,6 +963,24 @@ fn normalise_reference(reference: &str, current_module: &str) -> Result<String,
     }
 }
 
+/// Normalise a reference that may be a module keyword alone (e.g. `super` in `use super::*;`).
+fn normalise_module_reference(
+    reference: &str,
+    current_module: &str,
+) -> Result<String, ExtractionError> {
+    match reference {
+        "crate" => Ok(String::new()),
+        "self" => Ok(current_module.to_string()),
+        "super" if current_module.is_empty() => Err(ExtractionError::Malformed(
+            "Cannot use super from the root module".to_string(),
+        )),
+        "super" => Ok(current_module
+            .rsplit_once("::")
+            .map_or(String::new(), |(parent, _)| parent.to_string())),
+        _ => normalise_reference(reference, current_module),
+    }
+}
+
 fn get_doc_comments_by_module(public_modules: &[Module]) -> HashMap<String, String> {
     let doc_comments = public_modules
         .iter()
@@ -430,7 +1004,7 @@ mod tests {
             self.symbols
                 .iter()
                 .find(|s| s.symbol == symbol)
-                .expect(&format!("No matching symbol found in {:?}", self.symbols))
+                .unwrap_or_else(|| panic!("No matching symbol found in {:?}", self.symbols))
                 .modules
                 .clone()
         }
@@ -446,13 +1020,22 @@ mod tests {
             let modules = vec![Module {
                 name: String::new(),
                 is_public: true,
+                is_doc_hidden: false,
                 doc_comment: None,
                 symbols: vec![ModuleItem::Symbol {
                     symbol: symbol.clone(),
+                    kind: SymbolKind::Function,
+                    generics: None,
+                    is_doc_hidden: false,
+                    location: SourceLocation::default(),
                 }],
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
             }];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             assert_set_eq!(resolution.get_symbol_modules(symbol), vec![String::new()]);
@@ -464,13 +1047,22 @@ mod tests {
             let modules = vec![Module {
                 name: "outer::inner".to_string(),
                 is_public: true,
+                is_doc_hidden: false,
                 doc_comment: None,
                 symbols: vec![ModuleItem::Symbol {
                     symbol: symbol.clone(),
+                    kind: SymbolKind::Function,
+                    generics: None,
+                    is_doc_hidden: false,
+                    location: SourceLocation::default(),
                 }],
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
             }];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             assert_set_eq!(
@@ -478,6 +1070,55 @@ mod tests {
                 vec!["outer::inner".to_string()]
             );
         }
+
+        #[test]
+        fn sharing_path() {
+            let unix_symbol = Symbol {
+                name: "Handle".to_string(),
+                source_code: "#[cfg(unix)]\npub struct Handle;".to_string(),
+            };
+            let windows_symbol = Symbol {
+                name: "Handle".to_string(),
+                source_code: "#[cfg(windows)]\npub struct Handle;".to_string(),
+            };
+            let modules = vec![Module {
+                name: String::new(),
+                is_public: true,
+                is_doc_hidden: false,
+                doc_comment: None,
+                symbols: vec![
+                    ModuleItem::Symbol {
+                        symbol: unix_symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
+                    },
+                    ModuleItem::Symbol {
+                        symbol: windows_symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
+                    },
+                ],
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            }];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(resolution.symbols.len(), 1);
+            assert_eq!(
+                resolution.symbols[0].symbol.source_code,
+                format!(
+                    "{}\n\n{}",
+                    unix_symbol.source_code, windows_symbol.source_code
+                )
+            );
+        }
     }
 
     mod reexports {
@@ -491,23 +1132,38 @@ mod tests {
                 Module {
                     name: String::new(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "module::test".to_string(),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "module".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             assert_set_eq!(resolution.get_symbol_modules(symbol), vec![String::new()]);
@@ -520,23 +1176,38 @@ mod tests {
                 Module {
                     name: String::new(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "priv::test".to_string(),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "priv".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             assert_set_eq!(resolution.get_symbol_modules(symbol), vec![String::new()]);
@@ -550,28 +1221,47 @@ mod tests {
                 Module {
                     name: String::new(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: format!("inner::{}", reexported_symbol.name),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "inner".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![
                         ModuleItem::Symbol {
                             symbol: reexported_symbol.clone(),
+                            kind: SymbolKind::Function,
+                            generics: None,
+                            is_doc_hidden: false,
+                            location: SourceLocation::default(),
                         },
                         ModuleItem::Symbol {
                             symbol: non_reexported_symbol.clone(),
+                            kind: SymbolKind::Function,
+                            generics: None,
+                            is_doc_hidden: false,
+                            location: SourceLocation::default(),
                         },
                     ],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             assert_set_eq!(
@@ -591,40 +1281,69 @@ mod tests {
                 Module {
                     name: "foo".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: foo_symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "bar".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: bar_symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "reexporter1".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "foo::test".to_string(),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "reexporter2".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "bar::test".to_string(),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 2);
             assert_set_eq!(
@@ -644,23 +1363,38 @@ mod tests {
                 Module {
                     name: String::new(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "crate::inner::test".to_string(),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "inner".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             assert_set_eq!(resolution.get_symbol_modules(symbol), vec![String::new()]);
@@ -671,14 +1405,20 @@ mod tests {
             let modules = vec![Module {
                 name: String::new(),
                 is_public: true,
+                is_doc_hidden: false,
                 doc_comment: None,
                 symbols: vec![ModuleItem::SymbolReexport {
                     source_path: "super::test".to_string(),
                     import_type: ImportType::Simple,
+                    is_doc_hidden: false,
+                    location: SourceLocation::default(),
                 }],
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
             }];
 
-            let result = resolve_symbols(&modules);
+            let result = resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new());
 
             assert!(matches!(
                 result,
@@ -693,23 +1433,38 @@ mod tests {
                 Module {
                     name: "".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "child".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "super::test".to_string(),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             assert_set_eq!(resolution.get_symbol_modules(symbol), vec!["".to_string()]);
@@ -722,23 +1477,38 @@ mod tests {
                 Module {
                     name: "parent".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "parent::child".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "super::test".to_string(),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             assert_set_eq!(
@@ -754,23 +1524,38 @@ mod tests {
                 Module {
                     name: "".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "self::child::test".to_string(),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "child".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             assert_set_eq!(resolution.get_symbol_modules(symbol), vec!["".to_string()]);
@@ -783,23 +1568,38 @@ mod tests {
                 Module {
                     name: "module".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "self::inner::test".to_string(),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "module::inner".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             assert_set_eq!(
@@ -815,32 +1615,53 @@ mod tests {
                 Module {
                     name: String::new(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "child::Foo".to_string(),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "child".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "grandchild::Foo".to_string(),
                         import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "child::grandchild".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             assert_set_eq!(resolution.get_symbol_modules(symbol), vec![String::new()]);
@@ -852,14 +1673,21 @@ mod tests {
             let modules = vec![Module {
                 name: "outer".to_string(),
                 is_public: true,
+                is_doc_hidden: false,
                 doc_comment: None,
                 symbols: vec![ModuleItem::SymbolReexport {
                     source_path: reference_source_code.to_string(),
                     import_type: ImportType::Simple,
+                    is_doc_hidden: false,
+                    location: SourceLocation::default(),
                 }],
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
             }];
 
-            let result = resolve_symbols(&modules).unwrap();
+            let result =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(result.symbols.len(), 1);
             let resolved_symbol = result.symbols[0].clone();
@@ -877,23 +1705,38 @@ mod tests {
                 Module {
                     name: "reexporter".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "inner::test".to_string(),
                         import_type: ImportType::Aliased("aliased_test".to_string()),
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "inner".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: original_symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 2);
             let original = resolution
@@ -922,32 +1765,53 @@ mod tests {
                 Module {
                     name: String::new(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "child::Bar".to_string(),
                         import_type: ImportType::Aliased("Foo".to_string()),
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "child".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "grandchild::Baz".to_string(),
                         import_type: ImportType::Aliased("Bar".to_string()),
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "child::grandchild".to_string(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 3);
 
@@ -992,23 +1856,38 @@ mod tests {
                 Module {
                     name: String::new(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "child::Bar".to_string(),
                         import_type: ImportType::Aliased("Foo".to_string()),
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "child".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::Symbol {
                         symbol: original_symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 1);
             let expected_symbol = stub_symbol_with_name("Foo");
@@ -1025,14 +1904,21 @@ mod tests {
             let modules = vec![Module {
                 name: "outer".to_string(),
                 is_public: true,
+                is_doc_hidden: false,
                 doc_comment: None,
                 symbols: vec![ModuleItem::SymbolReexport {
                     source_path: reference_source_code.to_string(),
                     import_type: ImportType::Aliased(alias.to_string()),
+                    is_doc_hidden: false,
+                    location: SourceLocation::default(),
                 }],
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
             }];
 
-            let result = resolve_symbols(&modules).unwrap();
+            let result =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(result.symbols.len(), 1);
             let resolved_symbol = result.symbols[0].clone();
@@ -1051,28 +1937,47 @@ mod tests {
                 Module {
                     name: String::new(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "inner".to_string(),
                         import_type: ImportType::Wildcard,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "inner".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![
                         ModuleItem::Symbol {
                             symbol: symbol1.clone(),
+                            kind: SymbolKind::Function,
+                            generics: None,
+                            is_doc_hidden: false,
+                            location: SourceLocation::default(),
                         },
                         ModuleItem::Symbol {
                             symbol: symbol2.clone(),
+                            kind: SymbolKind::Function,
+                            generics: None,
+                            is_doc_hidden: false,
+                            location: SourceLocation::default(),
                         },
                     ],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 2);
             assert_set_eq!(resolution.get_symbol_modules(symbol1), vec![String::new()]);
@@ -1087,37 +1992,62 @@ mod tests {
                 Module {
                     name: String::new(),
                     is_public: true,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "child".to_string(),
                         import_type: ImportType::Wildcard,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "child".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![ModuleItem::SymbolReexport {
                         source_path: "grandchild".to_string(),
                         import_type: ImportType::Wildcard,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
                     }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
                 Module {
                     name: "child::grandchild".to_string(),
                     is_public: false,
+                    is_doc_hidden: false,
                     doc_comment: None,
                     symbols: vec![
                         ModuleItem::Symbol {
                             symbol: symbol1.clone(),
+                            kind: SymbolKind::Function,
+                            generics: None,
+                            is_doc_hidden: false,
+                            location: SourceLocation::default(),
                         },
                         ModuleItem::Symbol {
                             symbol: symbol2.clone(),
+                            kind: SymbolKind::Function,
+                            generics: None,
+                            is_doc_hidden: false,
+                            location: SourceLocation::default(),
                         },
                     ],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
                 },
             ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(resolution.symbols.len(), 2);
             assert_set_eq!(resolution.get_symbol_modules(symbol1), vec![String::new()]);
@@ -1130,14 +2060,21 @@ mod tests {
             let modules = vec![Module {
                 name: "outer".to_string(),
                 is_public: true,
+                is_doc_hidden: false,
                 doc_comment: None,
                 symbols: vec![ModuleItem::SymbolReexport {
                     source_path: reference_source_code.to_string(),
                     import_type: ImportType::Wildcard,
+                    is_doc_hidden: false,
+                    location: SourceLocation::default(),
                 }],
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
             }];
 
-            let result = resolve_symbols(&modules).unwrap();
+            let result =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
             assert_eq!(result.symbols.len(), 1);
             let resolved_symbol = result.symbols[0].clone();
@@ -1149,38 +2086,1150 @@ mod tests {
         }
     }
 
-    mod doc_comments {
+    mod impl_blocks {
         use super::*;
+        use crate::test_helpers::stub_symbol_with_name;
 
         #[test]
-        fn namespace_without_doc_comment() {
+        fn same_module() {
+            let symbol = stub_symbol_with_name("Foo");
             let modules = vec![Module {
-                name: "text".to_string(),
+                name: String::new(),
                 is_public: true,
+                is_doc_hidden: false,
                 doc_comment: None,
-                symbols: Vec::new(),
+                symbols: vec![
+                    ModuleItem::Symbol {
+                        symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
+                    },
+                    ModuleItem::ImplBlock {
+                        type_path: "Foo".to_string(),
+                        source_code: "impl Foo {}".to_string(),
+                    },
+                ],
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
             }];
 
-            let resolution = resolve_symbols(&modules).unwrap();
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
 
-            assert!(resolution.doc_comments.is_empty());
+            assert_eq!(resolution.symbols.len(), 1);
+            assert_eq!(
+                resolution.symbols[0].symbol.source_code,
+                format!("{}\n\nimpl Foo {{}}", symbol.source_code)
+            );
         }
 
         #[test]
-        fn namespace_with_doc_comment() {
-            let modules = vec![Module {
-                name: "text".to_string(),
-                is_public: true,
-                doc_comment: Some("Module for text processing".to_string()),
-                symbols: Vec::new(),
-            }];
+        fn different_module() {
+            let symbol = stub_symbol_with_name("Foo");
+            let modules = vec![
+                Module {
+                    name: String::new(),
+                    is_public: true,
+                    is_doc_hidden: false,
+                    doc_comment: None,
+                    symbols: vec![ModuleItem::Symbol {
+                        symbol: symbol.clone(),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
+                    }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
+                },
+                Module {
+                    name: "child".to_string(),
+                    is_public: false,
+                    is_doc_hidden: false,
+                    doc_comment: None,
+                    symbols: vec![ModuleItem::ImplBlock {
+                        type_path: "super::Foo".to_string(),
+                        source_code: "impl super::Foo {}".to_string(),
+                    }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
+                },
+            ];
 
-            let resolution = resolve_symbols(&modules).unwrap();
-            assert_eq!(resolution.doc_comments.len(), 1);
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(resolution.symbols.len(), 1);
             assert_eq!(
-                resolution.doc_comments.get("text"),
-                Some(&"Module for text processing".to_string())
+                resolution.symbols[0].symbol.source_code,
+                format!("{}\n\nimpl super::Foo {{}}", symbol.source_code)
+            );
+        }
+
+        #[test]
+        fn reexported_type() {
+            let symbol = stub_symbol_with_name("Foo");
+            let modules = vec![
+                Module {
+                    name: String::new(),
+                    is_public: true,
+                    is_doc_hidden: false,
+                    doc_comment: None,
+                    symbols: vec![ModuleItem::SymbolReexport {
+                        source_path: "inner::Foo".to_string(),
+                        import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
+                    }],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
+                },
+                Module {
+                    name: "inner".to_string(),
+                    is_public: false,
+                    is_doc_hidden: false,
+                    doc_comment: None,
+                    symbols: vec![
+                        ModuleItem::Symbol {
+                            symbol: symbol.clone(),
+                            kind: SymbolKind::Function,
+                            generics: None,
+                            is_doc_hidden: false,
+                            location: SourceLocation::default(),
+                        },
+                        ModuleItem::ImplBlock {
+                            type_path: "Foo".to_string(),
+                            source_code: "impl Foo {}".to_string(),
+                        },
+                    ],
+                    location: SourceLocation::default(),
+                    is_unresolved: false,
+                    unresolved_includes: Vec::new(),
+                },
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(resolution.symbols.len(), 1);
+            let declaration = &resolution.symbols[0];
+            assert_eq!(
+                declaration.symbol.source_code,
+                format!("{}\n\nimpl Foo {{}}", symbol.source_code)
+            );
+            assert_set_eq!(declaration.modules, vec![String::new()]);
+        }
+
+        fn make_module(name: &str, symbols: Vec<ModuleItem>) -> Module {
+            Module {
+                name: name.to_string(),
+                is_public: name.is_empty(),
+                is_doc_hidden: false,
+                doc_comment: None,
+                symbols,
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            }
+        }
+
+        fn make_symbol(name: &str) -> ModuleItem {
+            ModuleItem::Symbol {
+                symbol: stub_symbol_with_name(name),
+                kind: SymbolKind::Struct,
+                generics: None,
+                is_doc_hidden: false,
+                location: SourceLocation::default(),
+            }
+        }
+
+        fn make_impl_block(type_path: &str) -> ModuleItem {
+            ModuleItem::ImplBlock {
+                type_path: type_path.to_string(),
+                source_code: format!("impl {} {{}}", type_path),
+            }
+        }
+
+        #[test]
+        fn imported_type() {
+            let modules = vec![
+                make_module("", vec![make_symbol("Foo")]),
+                make_module(
+                    "child",
+                    vec![
+                        ModuleItem::Import {
+                            source_path: "crate::Foo".to_string(),
+                            import_type: ImportType::Simple,
+                        },
+                        make_impl_block("Foo"),
+                    ],
+                ),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(resolution.symbols.len(), 1);
+            assert_contains!(resolution.symbols[0].symbol.source_code, "impl Foo {}");
+        }
+
+        #[test]
+        fn glob_imported_type() {
+            let modules = vec![
+                make_module("", vec![make_symbol("Foo")]),
+                make_module(
+                    "child",
+                    vec![
+                        ModuleItem::Import {
+                            source_path: "super".to_string(),
+                            import_type: ImportType::Wildcard,
+                        },
+                        make_impl_block("Foo"),
+                    ],
+                ),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(resolution.symbols.len(), 1);
+            assert_contains!(resolution.symbols[0].symbol.source_code, "impl Foo {}");
+        }
+
+        #[test]
+        fn type_of_submodule() {
+            let modules = vec![
+                make_module(
+                    "",
+                    vec![
+                        ModuleItem::SymbolReexport {
+                            source_path: "inner::Foo".to_string(),
+                            import_type: ImportType::Simple,
+                            is_doc_hidden: false,
+                            location: SourceLocation::default(),
+                        },
+                        make_impl_block("inner::Foo"),
+                    ],
+                ),
+                make_module("inner", vec![make_symbol("Foo")]),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(resolution.symbols.len(), 1);
+            assert_contains!(
+                resolution.symbols[0].symbol.source_code,
+                "impl inner::Foo {}"
             );
         }
+
+        #[test]
+        fn private_type_sharing_name() {
+            let modules = vec![
+                make_module("", vec![make_symbol("Foo")]),
+                make_module("child", vec![make_impl_block("Foo")]),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(resolution.symbols.len(), 1);
+            assert_eq!(
+                resolution.symbols[0].symbol.source_code,
+                stub_symbol_with_name("Foo").source_code
+            );
+        }
+
+        #[test]
+        fn unknown_type() {
+            let modules = vec![Module {
+                name: String::new(),
+                is_public: true,
+                is_doc_hidden: false,
+                doc_comment: None,
+                symbols: vec![ModuleItem::ImplBlock {
+                    type_path: "Missing".to_string(),
+                    source_code: "impl Missing {}".to_string(),
+                }],
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            }];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert!(resolution.symbols.is_empty());
+        }
+    }
+
+    mod doc_comments {
+        use super::*;
+
+        #[test]
+        fn namespace_without_doc_comment() {
+            let modules = vec![Module {
+                name: "text".to_string(),
+                is_public: true,
+                is_doc_hidden: false,
+                doc_comment: None,
+                symbols: Vec::new(),
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            }];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert!(resolution.doc_comments.is_empty());
+        }
+
+        #[test]
+        fn namespace_with_doc_comment() {
+            let modules = vec![Module {
+                name: "text".to_string(),
+                is_public: true,
+                is_doc_hidden: false,
+                doc_comment: Some("Module for text processing".to_string()),
+                symbols: Vec::new(),
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            }];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+            assert_eq!(resolution.doc_comments.len(), 1);
+            assert_eq!(
+                resolution.doc_comments.get("text"),
+                Some(&"Module for text processing".to_string())
+            );
+        }
+    }
+
+    mod doc_hidden {
+        use super::*;
+        use crate::test_helpers::stub_symbol_with_name;
+
+        fn make_module(name: &str, is_doc_hidden: bool, symbols: Vec<ModuleItem>) -> Module {
+            Module {
+                name: name.to_string(),
+                is_public: true,
+                is_doc_hidden,
+                doc_comment: None,
+                symbols,
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            }
+        }
+
+        fn make_symbol(name: &str, is_doc_hidden: bool) -> ModuleItem {
+            ModuleItem::Symbol {
+                symbol: stub_symbol_with_name(name),
+                kind: SymbolKind::Function,
+                generics: None,
+                is_doc_hidden,
+                location: SourceLocation::default(),
+            }
+        }
+
+        fn make_reexport(source_path: &str, is_doc_hidden: bool) -> ModuleItem {
+            ModuleItem::SymbolReexport {
+                source_path: source_path.to_string(),
+                import_type: ImportType::Simple,
+                is_doc_hidden,
+                location: SourceLocation::default(),
+            }
+        }
+
+        fn get_symbol_names(resolution: &SymbolResolution) -> HashSet<String> {
+            resolution
+                .symbols
+                .iter()
+                .map(|declaration| declaration.symbol.name.clone())
+                .collect()
+        }
+
+        #[test]
+        fn hidden_symbol() {
+            let modules = vec![make_module(
+                "",
+                false,
+                vec![make_symbol("visible", false), make_symbol("hidden", true)],
+            )];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(
+                get_symbol_names(&resolution),
+                HashSet::from(["visible".to_string()])
+            );
+        }
+
+        #[test]
+        fn hidden_symbol_included() {
+            let modules = vec![make_module(
+                "",
+                false,
+                vec![make_symbol("visible", false), make_symbol("hidden", true)],
+            )];
+
+            let resolution = resolve_symbols(
+                &modules,
+                &ExtractionOptions::new().with_doc_hidden_items(),
+                &HashMap::new(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                get_symbol_names(&resolution),
+                HashSet::from(["visible".to_string(), "hidden".to_string()])
+            );
+        }
+
+        #[test]
+        fn hidden_module_and_descendants() {
+            let modules = vec![
+                make_module("", false, vec![make_symbol("visible", false)]),
+                make_module("__private", true, vec![make_symbol("helper", false)]),
+                make_module(
+                    "__private::nested",
+                    false,
+                    vec![make_symbol("inner", false)],
+                ),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(
+                get_symbol_names(&resolution),
+                HashSet::from(["visible".to_string()])
+            );
+        }
+
+        #[test]
+        fn reexport_from_hidden_module() {
+            let modules = vec![
+                make_module("", false, vec![make_reexport("__private::helper", false)]),
+                make_module("__private", true, vec![make_symbol("helper", false)]),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(resolution.symbols.len(), 1);
+            assert_eq!(resolution.symbols[0].modules, vec![String::new()]);
+        }
+
+        #[test]
+        fn hidden_reexport() {
+            let modules = vec![
+                make_module("", false, vec![make_reexport("inner::Foo", true)]),
+                make_module("inner", false, vec![make_symbol("Foo", false)]),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(resolution.symbols.len(), 1);
+            assert_eq!(resolution.symbols[0].modules, vec!["inner".to_string()]);
+        }
+
+        #[test]
+        fn reexport_of_hidden_symbol() {
+            let modules = vec![
+                make_module("", false, vec![make_reexport("inner::Foo", false)]),
+                make_module("inner", false, vec![make_symbol("Foo", true)]),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert!(resolution.symbols.is_empty());
+        }
+
+        #[test]
+        fn aliased_reexport_of_hidden_symbol() {
+            let modules = vec![make_module(
+                "",
+                false,
+                vec![
+                    make_symbol("__hidden_impl", true),
+                    ModuleItem::SymbolReexport {
+                        source_path: "__hidden_impl".to_string(),
+                        import_type: ImportType::Aliased("nice".to_string()),
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
+                    },
+                ],
+            )];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(
+                get_symbol_names(&resolution),
+                HashSet::from(["nice".to_string()])
+            );
+        }
+    }
+
+    mod external_crates {
+        use super::*;
+        use crate::test_helpers::stub_symbol_with_name;
+
+        fn make_module(name: &str, symbols: Vec<ModuleItem>) -> Module {
+            Module {
+                name: name.to_string(),
+                is_public: true,
+                is_doc_hidden: false,
+                doc_comment: None,
+                symbols,
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            }
+        }
+
+        fn make_reexport(source_path: &str) -> ModuleItem {
+            ModuleItem::SymbolReexport {
+                source_path: source_path.to_string(),
+                import_type: ImportType::Simple,
+                is_doc_hidden: false,
+                location: SourceLocation::default(),
+            }
+        }
+
+        fn get_source_codes(resolution: &SymbolResolution) -> Vec<String> {
+            resolution
+                .symbols
+                .iter()
+                .map(|declaration| declaration.symbol.source_code.clone())
+                .collect()
+        }
+
+        #[test]
+        fn dependency() {
+            let modules = vec![make_module("", vec![make_reexport("foo::Bar")])];
+            let dependencies = HashMap::from([("foo".to_string(), "foo".to_string())]);
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &dependencies).unwrap();
+
+            assert_eq!(get_source_codes(&resolution), vec!["pub use foo::Bar;"]);
+            assert_eq!(resolution.symbols[0].modules, vec!["".to_string()]);
+        }
+
+        #[test]
+        fn renamed_dependency() {
+            let modules = vec![make_module("", vec![make_reexport("bar::Baz")])];
+            let dependencies = HashMap::from([("bar".to_string(), "foo".to_string())]);
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &dependencies).unwrap();
+
+            assert_eq!(get_source_codes(&resolution), vec!["pub use foo::Baz;"]);
+        }
+
+        #[test]
+        fn absolute_path() {
+            let modules = vec![make_module("", vec![make_reexport("::foo::Bar")])];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(get_source_codes(&resolution), vec!["pub use foo::Bar;"]);
+        }
+
+        #[test]
+        fn extern_crate_alias() {
+            let modules = vec![
+                make_module(
+                    "",
+                    vec![ModuleItem::ExternCrate {
+                        name: "foo".to_string(),
+                        alias: Some("bar".to_string()),
+                    }],
+                ),
+                make_module("inner", vec![make_reexport("bar::Baz")]),
+            ];
+            let dependencies = HashMap::from([("foo".to_string(), "foo".to_string())]);
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &dependencies).unwrap();
+
+            assert_eq!(get_source_codes(&resolution), vec!["pub use foo::Baz;"]);
+            assert_eq!(resolution.symbols[0].modules, vec!["inner".to_string()]);
+        }
+
+        #[test]
+        fn extern_crate_self_alias() {
+            let modules = vec![
+                make_module(
+                    "",
+                    vec![
+                        ModuleItem::ExternCrate {
+                            name: "self".to_string(),
+                            alias: Some("my_crate".to_string()),
+                        },
+                        make_reexport("my_crate::inner::Foo"),
+                    ],
+                ),
+                make_module(
+                    "inner",
+                    vec![ModuleItem::Symbol {
+                        symbol: stub_symbol_with_name("Foo"),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
+                    }],
+                ),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            let foo = resolution
+                .symbols
+                .iter()
+                .find(|declaration| declaration.symbol.name == "Foo")
+                .unwrap();
+            assert_set_eq!(foo.modules, vec!["".to_string(), "inner".to_string()]);
+        }
+
+        #[test]
+        fn local_module_shadowing_dependency() {
+            let modules = vec![
+                make_module("", vec![make_reexport("foo::Bar")]),
+                make_module(
+                    "foo",
+                    vec![ModuleItem::Symbol {
+                        symbol: stub_symbol_with_name("Bar"),
+                        kind: SymbolKind::Function,
+                        generics: None,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
+                    }],
+                ),
+            ];
+            let dependencies = HashMap::from([("foo".to_string(), "foo".to_string())]);
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &dependencies).unwrap();
+
+            assert_eq!(resolution.symbols.len(), 1);
+            assert_set_eq!(
+                resolution.symbols[0].modules,
+                vec!["".to_string(), "foo".to_string()]
+            );
+        }
+    }
+
+    mod globs {
+        use super::*;
+
+        fn make_module(name: &str, symbols: Vec<ModuleItem>) -> Module {
+            Module {
+                name: name.to_string(),
+                is_public: true,
+                is_doc_hidden: false,
+                doc_comment: None,
+                symbols,
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            }
+        }
+
+        fn make_glob(source_path: &str) -> ModuleItem {
+            ModuleItem::SymbolReexport {
+                source_path: source_path.to_string(),
+                import_type: ImportType::Wildcard,
+                is_doc_hidden: false,
+                location: SourceLocation::default(),
+            }
+        }
+
+        fn make_enum_variants() -> ModuleItem {
+            ModuleItem::EnumVariants {
+                enum_name: "Colour".to_string(),
+                variants: vec!["Red".to_string(), "Green".to_string()],
+            }
+        }
+
+        fn get_symbols(resolution: &SymbolResolution) -> Vec<(String, String)> {
+            let mut symbols: Vec<_> = resolution
+                .symbols
+                .iter()
+                .map(|declaration| {
+                    (
+                        declaration.symbol.name.clone(),
+                        declaration.symbol.source_code.clone(),
+                    )
+                })
+                .collect();
+            symbols.sort();
+            symbols
+        }
+
+        #[test]
+        fn enum_in_same_module() {
+            let modules = vec![make_module(
+                "",
+                vec![make_enum_variants(), make_glob("Colour")],
+            )];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(
+                get_symbols(&resolution),
+                vec![
+                    ("Green".to_string(), "pub use Colour::Green;".to_string()),
+                    ("Red".to_string(), "pub use Colour::Red;".to_string()),
+                ]
+            );
+        }
+
+        #[test]
+        fn enum_in_other_module() {
+            let modules = vec![
+                make_module("", vec![make_glob("crate::colours::Colour")]),
+                make_module("colours", vec![make_enum_variants()]),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(
+                get_symbols(&resolution),
+                vec![
+                    (
+                        "Green".to_string(),
+                        "pub use colours::Colour::Green;".to_string()
+                    ),
+                    (
+                        "Red".to_string(),
+                        "pub use colours::Colour::Red;".to_string()
+                    ),
+                ]
+            );
+            assert!(resolution
+                .symbols
+                .iter()
+                .all(|declaration| declaration.modules == vec!["".to_string()]));
+        }
+
+        #[test]
+        fn external_crate() {
+            let modules = vec![make_module("", vec![make_glob("foo::prelude")])];
+            let dependencies = HashMap::from([("foo".to_string(), "foo".to_string())]);
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &dependencies).unwrap();
+
+            assert_eq!(
+                get_symbols(&resolution),
+                vec![(
+                    "foo::prelude::*".to_string(),
+                    "pub use foo::prelude::*;".to_string()
+                )]
+            );
+            let declaration = &resolution.symbols[0];
+            assert_eq!(declaration.kind, SymbolKind::UnresolvedGlob);
+            assert_eq!(declaration.canonical_path, "::foo::prelude");
+            assert!(declaration.public_paths.is_empty());
+            assert_matches!(
+                &declaration.external_reexport,
+                Some(ExternalReexport { source_path, import_type: ImportType::Wildcard })
+                    if source_path == "foo::prelude"
+            );
+        }
+
+        #[test]
+        fn several_external_crates() {
+            let modules = vec![make_module(
+                "",
+                vec![make_glob("foo::prelude"), make_glob("bar::prelude")],
+            )];
+            let dependencies = HashMap::from([
+                ("foo".to_string(), "foo".to_string()),
+                ("bar".to_string(), "bar".to_string()),
+            ]);
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &dependencies).unwrap();
+
+            assert_eq!(
+                get_symbols(&resolution),
+                vec![
+                    (
+                        "bar::prelude::*".to_string(),
+                        "pub use bar::prelude::*;".to_string()
+                    ),
+                    (
+                        "foo::prelude::*".to_string(),
+                        "pub use foo::prelude::*;".to_string()
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn single_variant() {
+            let modules = vec![make_module(
+                "",
+                vec![
+                    make_enum_variants(),
+                    ModuleItem::SymbolReexport {
+                        source_path: "Colour::Red".to_string(),
+                        import_type: ImportType::Simple,
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
+                    },
+                ],
+            )];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(
+                get_symbols(&resolution),
+                vec![("Red".to_string(), "pub use Colour::Red;".to_string())]
+            );
+            assert_eq!(resolution.symbols[0].canonical_path, "crate::Colour::Red");
+        }
+
+        #[test]
+        fn aliased_variant() {
+            let modules = vec![
+                make_module(
+                    "",
+                    vec![ModuleItem::SymbolReexport {
+                        source_path: "colours::Colour::Red".to_string(),
+                        import_type: ImportType::Aliased("Rouge".to_string()),
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
+                    }],
+                ),
+                make_module("colours", vec![make_enum_variants()]),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(
+                get_symbols(&resolution),
+                vec![(
+                    "Rouge".to_string(),
+                    "pub use colours::Colour::Red as Rouge;".to_string()
+                )]
+            );
+            let declaration = &resolution.symbols[0];
+            assert_eq!(declaration.canonical_path, "crate::colours::Colour::Red");
+            assert_eq!(declaration.public_paths, vec!["crate::Rouge"]);
+        }
+    }
+
+    mod symbol_kinds {
+        use super::*;
+        use crate::test_helpers::stub_symbol_with_name;
+
+        fn make_module(name: &str, symbols: Vec<ModuleItem>) -> Module {
+            Module {
+                name: name.to_string(),
+                is_public: true,
+                is_doc_hidden: false,
+                doc_comment: None,
+                symbols,
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            }
+        }
+
+        fn make_reexport(source_path: &str) -> ModuleItem {
+            ModuleItem::SymbolReexport {
+                source_path: source_path.to_string(),
+                import_type: ImportType::Simple,
+                is_doc_hidden: false,
+                location: SourceLocation::default(),
+            }
+        }
+
+        #[test]
+        fn reexported_symbol() {
+            let modules = vec![
+                make_module("", vec![make_reexport("inner::Foo")]),
+                make_module(
+                    "inner",
+                    vec![ModuleItem::Symbol {
+                        symbol: stub_symbol_with_name("Foo"),
+                        kind: SymbolKind::Struct,
+                        generics: Some("<T>".to_string()),
+                        is_doc_hidden: false,
+                        location: SourceLocation::default(),
+                    }],
+                ),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(resolution.symbols.len(), 1);
+            let declaration = &resolution.symbols[0];
+            assert_eq!(declaration.kind, SymbolKind::Struct);
+            assert_eq!(declaration.generics, Some("<T>".to_string()));
+            assert_eq!(declaration.module_path, "inner");
+        }
+
+        #[test]
+        fn unresolved_reexport() {
+            let modules = vec![make_module("", vec![make_reexport("missing::Foo")])];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            assert_eq!(resolution.symbols[0].kind, SymbolKind::Reexport);
+        }
+
+        #[test]
+        fn reexported_crate() {
+            let modules = vec![make_module("", vec![make_reexport("foo")])];
+            let dependencies = HashMap::from([("foo".to_string(), "foo".to_string())]);
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &dependencies).unwrap();
+
+            assert_eq!(resolution.symbols[0].kind, SymbolKind::Module);
+        }
+    }
+
+    mod item_paths {
+        use super::*;
+        use crate::test_helpers::stub_symbol_with_name;
+
+        fn make_module(name: &str, is_public: bool, symbols: Vec<ModuleItem>) -> Module {
+            Module {
+                name: name.to_string(),
+                is_public,
+                is_doc_hidden: false,
+                doc_comment: None,
+                symbols,
+                location: SourceLocation::default(),
+                is_unresolved: false,
+                unresolved_includes: Vec::new(),
+            }
+        }
+
+        fn make_symbol(name: &str) -> ModuleItem {
+            ModuleItem::Symbol {
+                symbol: stub_symbol_with_name(name),
+                kind: SymbolKind::Struct,
+                generics: None,
+                is_doc_hidden: false,
+                location: SourceLocation::default(),
+            }
+        }
+
+        fn make_reexport(source_path: &str, import_type: ImportType) -> ModuleItem {
+            ModuleItem::SymbolReexport {
+                source_path: source_path.to_string(),
+                import_type,
+                is_doc_hidden: false,
+                location: SourceLocation::default(),
+            }
+        }
+
+        fn get_declaration<'a>(
+            resolution: &'a SymbolResolution,
+            name: &str,
+        ) -> &'a SymbolDeclaration {
+            resolution
+                .symbols
+                .iter()
+                .find(|declaration| declaration.symbol.name == name)
+                .unwrap()
+        }
+
+        #[test]
+        fn declared_symbol() {
+            let modules = vec![
+                make_module("", true, vec![]),
+                make_module("codec", true, vec![make_symbol("Format")]),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            let declaration = get_declaration(&resolution, "Format");
+            assert_eq!(declaration.canonical_path, "crate::codec::Format");
+            assert_eq!(declaration.public_paths, vec!["crate::codec::Format"]);
+        }
+
+        #[test]
+        fn symbol_reexported_from_private_module() {
+            let modules = vec![
+                make_module(
+                    "",
+                    true,
+                    vec![make_reexport("internal::codec::Format", ImportType::Simple)],
+                ),
+                make_module("internal", false, vec![]),
+                make_module("internal::codec", false, vec![make_symbol("Format")]),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            let declaration = get_declaration(&resolution, "Format");
+            assert_eq!(declaration.canonical_path, "crate::internal::codec::Format");
+            assert_eq!(declaration.public_paths, vec!["crate::Format"]);
+        }
+
+        #[test]
+        fn aliased_symbol() {
+            let modules = vec![
+                make_module(
+                    "",
+                    true,
+                    vec![make_reexport(
+                        "codec::Format",
+                        ImportType::Aliased("Encoding".to_string()),
+                    )],
+                ),
+                make_module("codec", true, vec![make_symbol("Format")]),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            let expected_paths = vec!["crate::Encoding", "crate::codec::Format"];
+            let original = get_declaration(&resolution, "Format");
+            assert_eq!(original.canonical_path, "crate::codec::Format");
+            assert_eq!(original.public_paths, expected_paths);
+            let alias = get_declaration(&resolution, "Encoding");
+            assert_eq!(alias.canonical_path, "crate::codec::Format");
+            assert_eq!(alias.public_paths, expected_paths);
+        }
+
+        #[test]
+        fn enum_variant() {
+            let modules = vec![
+                make_module(
+                    "",
+                    true,
+                    vec![make_reexport("colours::Colour", ImportType::Wildcard)],
+                ),
+                make_module(
+                    "colours",
+                    false,
+                    vec![ModuleItem::EnumVariants {
+                        enum_name: "Colour".to_string(),
+                        variants: vec!["Red".to_string()],
+                    }],
+                ),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            let declaration = get_declaration(&resolution, "Red");
+            assert_eq!(declaration.canonical_path, "crate::colours::Colour::Red");
+            assert_eq!(declaration.public_paths, vec!["crate::Red"]);
+        }
+
+        #[test]
+        fn external_reexport() {
+            let modules = vec![make_module(
+                "",
+                true,
+                vec![make_reexport("serde_json::Value", ImportType::Simple)],
+            )];
+            let dependencies =
+                HashMap::from([("serde_json".to_string(), "serde_json".to_string())]);
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &dependencies).unwrap();
+
+            let declaration = get_declaration(&resolution, "Value");
+            assert_eq!(declaration.canonical_path, "::serde_json::Value");
+            assert_eq!(declaration.public_paths, vec!["crate::Value"]);
+        }
+
+        #[test]
+        fn standard_library_reexport() {
+            let modules = vec![make_module(
+                "",
+                true,
+                vec![make_reexport("std::io::Error", ImportType::Simple)],
+            )];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            let declaration = get_declaration(&resolution, "Error");
+            assert_eq!(declaration.canonical_path, "::std::io::Error");
+        }
+
+        #[test]
+        fn unresolved_reexport() {
+            let modules = vec![make_module(
+                "",
+                true,
+                vec![make_reexport("self::missing::Foo", ImportType::Simple)],
+            )];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            let declaration = get_declaration(&resolution, "Foo");
+            assert_eq!(declaration.canonical_path, "crate::missing::Foo");
+            assert_eq!(declaration.public_paths, vec!["crate::Foo"]);
+        }
+
+        #[test]
+        fn unresolved_reexport_from_submodule() {
+            let modules = vec![
+                make_module("", true, vec![]),
+                make_module(
+                    "outer",
+                    true,
+                    vec![make_reexport("super::missing::Foo", ImportType::Simple)],
+                ),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            let declaration = get_declaration(&resolution, "Foo");
+            assert_eq!(declaration.canonical_path, "crate::missing::Foo");
+        }
+
+        #[test]
+        fn reexported_local_module() {
+            let modules = vec![
+                make_module("", true, vec![make_reexport("inner", ImportType::Simple)]),
+                make_module("inner", false, vec![]),
+            ];
+
+            let resolution =
+                resolve_symbols(&modules, &ExtractionOptions::default(), &HashMap::new()).unwrap();
+
+            let declaration = get_declaration(&resolution, "inner");
+            assert_eq!(declaration.canonical_path, "crate::inner");
+        }
     }
 }
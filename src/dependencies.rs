@@ -1,7 +1,13 @@
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
 use cargo_metadata::MetadataCommand;
 use daipendency_extractor::DependencyResolutionError;
+use tree_sitter::Parser;
+
+use crate::api::{self, ExtractionOptions, RustNamespace};
+use crate::metadata::{self, get_crate_name};
 
 pub fn resolve_dependency_path(
     dependency_name: &str,
@@ -16,13 +22,71 @@ pub fn resolve_dependency_path(
     let dependency_manifest_path: std::path::PathBuf = metadata
         .packages
         .iter()
-        .find(|package| package.name == dependency_name)
+        .find(|package| get_crate_name(&package.name) == get_crate_name(dependency_name))
         .map(|package| package.manifest_path.clone().into())
         .ok_or_else(|| DependencyResolutionError::MissingDependency(dependency_name.to_string()))?;
 
     Ok(dependency_manifest_path.parent().unwrap().to_path_buf())
 }
 
+/// The public APIs of the dependencies of a crate, extracted on demand with their default features.
+///
+/// Each API is extracted once, and crates whose APIs are being extracted are skipped, so that
+/// cyclic reexports between crates terminate.
+pub struct DependencyApis {
+    crate_directory: PathBuf,
+    apis: HashMap<String, Option<Rc<Vec<RustNamespace>>>>,
+    pending_crates: HashSet<String>,
+}
+
+impl DependencyApis {
+    pub fn new(crate_directory: &Path) -> Self {
+        Self {
+            crate_directory: crate_directory.to_path_buf(),
+            apis: HashMap::new(),
+            pending_crates: HashSet::new(),
+        }
+    }
+
+    /// Get the public API of the dependency `crate_name`, or `None` if it can't be extracted.
+    pub fn get_public_api(&mut self, crate_name: &str) -> Option<Rc<Vec<RustNamespace>>> {
+        if let Some(api) = self.apis.get(crate_name) {
+            return api.clone();
+        }
+        if !self.pending_crates.insert(crate_name.to_string()) {
+            return None;
+        }
+        let api = self.extract_public_api(crate_name).map(Rc::new);
+        self.pending_crates.remove(crate_name);
+        self.apis.insert(crate_name.to_string(), api.clone());
+        api
+    }
+
+    fn extract_public_api(&mut self, crate_name: &str) -> Option<Vec<RustNamespace>> {
+        let dependency_directory =
+            resolve_dependency_path(crate_name, &self.crate_directory).ok()?;
+        let metadata = metadata::extract_metadata(&dependency_directory, |_| None).ok()?;
+        let options = ExtractionOptions::new()
+            .with_features(metadata::resolve_features(&dependency_directory, None).ok()?)
+            .with_inlined_reexports();
+        let dependencies = metadata::resolve_dependencies(&dependency_directory).ok()?;
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_rust::LANGUAGE.into())
+            .ok()?;
+        api::build_rust_api(
+            &metadata.entry_point,
+            &metadata.name,
+            &options,
+            &dependencies,
+            Some(&mut |name: &str| self.get_public_api(name)),
+            &mut parser,
+        )
+        .ok()
+        .map(|api| api.namespaces)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -34,7 +98,7 @@ mod tests {
         let cargo_toml = Path::new(env!("CARGO_MANIFEST_DIR"));
         let dependency_name = "tree-sitter";
 
-        let result = resolve_dependency_path(dependency_name, &cargo_toml);
+        let result = resolve_dependency_path(dependency_name, cargo_toml);
 
         assert_ok!(&result);
         let dependency_path = result.unwrap();
@@ -46,7 +110,7 @@ mod tests {
     fn missing_dependency() {
         let cargo_toml = Path::new(env!("CARGO_MANIFEST_DIR"));
 
-        let result = resolve_dependency_path("non-existent-dependency", &cargo_toml);
+        let result = resolve_dependency_path("non-existent-dependency", cargo_toml);
 
         assert!(matches!(
             result,
@@ -66,4 +130,58 @@ mod tests {
             Err(DependencyResolutionError::RetrievalFailure(_))
         ));
     }
+
+    mod dependency_apis {
+        use super::*;
+
+        fn create_crates(temp_dir: &TempDir) -> PathBuf {
+            temp_dir
+                .create_file(
+                    "dependency/Cargo.toml",
+                    "[package]\nname = \"my-dependency\"\nversion = \"0.1.0\"\n",
+                )
+                .unwrap();
+            temp_dir
+                .create_file("dependency/src/lib.rs", "pub struct Foo;")
+                .unwrap();
+            let cargo_toml = temp_dir
+                .create_file(
+                    "dependant/Cargo.toml",
+                    r#"[package]
+name = "dependant"
+version = "0.1.0"
+
+[dependencies]
+my-dependency = { path = "../dependency" }
+"#,
+                )
+                .unwrap();
+            temp_dir.create_file("dependant/src/lib.rs", "").unwrap();
+            cargo_toml.parent().unwrap().to_path_buf()
+        }
+
+        #[test]
+        fn dependency_api() {
+            let temp_dir = TempDir::new();
+            let crate_directory = create_crates(&temp_dir);
+            let mut dependency_apis = DependencyApis::new(&crate_directory);
+
+            let api = dependency_apis.get_public_api("my_dependency").unwrap();
+
+            assert_eq!(api.len(), 1);
+            assert_eq!(api[0].name, "my_dependency");
+            assert!(api[0].get_symbol("Foo").is_some());
+        }
+
+        #[test]
+        fn missing_dependency() {
+            let temp_dir = TempDir::new();
+            let crate_directory = create_crates(&temp_dir);
+            let mut dependency_apis = DependencyApis::new(&crate_directory);
+
+            let api = dependency_apis.get_public_api("non_existent_dependency");
+
+            assert!(api.is_none());
+        }
+    }
 }
@@ -1,22 +1,164 @@
+use super::api::{DependencyApiProvider, ExtractionOptions, RustApi};
 use super::{api, dependencies, metadata};
 use daipendency_extractor::{
     DependencyResolutionError, ExtractionError, Extractor, LibraryMetadata, LibraryMetadataError,
     Namespace,
 };
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tree_sitter::{Language, Parser};
 
-pub struct RustExtractor;
-
-impl Default for RustExtractor {
-    fn default() -> Self {
-        Self::new()
-    }
+/// Extractor of the public API of Rust crates.
+///
+/// Items disabled by `#[cfg(...)]` attributes are pruned, using the crate's default features and
+/// the host's target configuration unless configured otherwise. Alternatively, all items can be
+/// retained and annotated with the features or cfg predicates they require.
+#[derive(Debug, Clone, Default)]
+pub struct RustExtractor {
+    options: ExtractionOptions,
 }
 
 impl RustExtractor {
+    /// Create an extractor with the default options.
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Enable the given Cargo features instead of the crate's default features.
+    pub fn with_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options = self.options.with_features(features);
+        self
+    }
+
+    /// Use the given target configuration options instead of the host's (e.g. `("unix", None)`,
+    /// `("target_os", Some("linux"))`).
+    pub fn with_target_options<I>(mut self, target_options: I) -> Self
+    where
+        I: IntoIterator<Item = (String, Option<String>)>,
+    {
+        self.options = self.options.with_target_options(target_options);
+        self
+    }
+
+    /// Retain the items disabled by `#[cfg(...)]` attributes, and annotate all conditionally
+    /// compiled items with the features or cfg predicates they require (e.g. "Available on crate
+    /// feature `serde` only."), instead of pruning them.
+    pub fn with_cfg_annotations(mut self) -> Self {
+        self.options = self.options.with_cfg_annotations();
+        self
+    }
+
+    /// Include the items hidden from the documentation with `#[doc(hidden)]`, which are excluded
+    /// by default.
+    pub fn with_doc_hidden_items(mut self) -> Self {
+        self.options = self.options.with_doc_hidden_items();
+        self
+    }
+
+    /// Inline the items reexported from dependencies (e.g. `pub use serde_json::Value;`), as
+    /// rustdoc does, instead of preserving their `pub use` declarations.
+    ///
+    /// The public APIs of the dependencies are extracted with their default features, and the
+    /// declarations of items that can't be found are preserved.
+    pub fn with_inlined_reexports(mut self) -> Self {
+        self.options = self.options.with_inlined_reexports();
+        self
+    }
+
+    /// Fail with [`ExtractionError::Malformed`] if any source file has syntax errors, instead of
+    /// extracting whatever the parser could recover from them.
+    ///
+    /// This also applies to the other diagnostics, such as the missing modules tolerated by
+    /// [`RustExtractor::with_missing_modules_tolerated`].
+    pub fn with_strict_parsing(mut self) -> Self {
+        self.options = self.options.with_strict_parsing();
+        self
+    }
+
+    /// Carry on when the file of a module can't be found (e.g. a module generated by a build
+    /// script, or a platform module missing from a vendored copy), reporting it as a diagnostic
+    /// and marking its namespace as unresolved, instead of failing.
+    pub fn with_missing_modules_tolerated(mut self) -> Self {
+        self.options = self.options.with_missing_modules_tolerated();
+        self
+    }
+
+    /// Look up the files included from the output directory of the build script (e.g.
+    /// `include!(concat!(env!("OUT_DIR"), "/bindings.rs"));`) in `out_dir`, instead of the
+    /// crate's `target` directory.
+    pub fn with_out_dir(mut self, out_dir: impl Into<PathBuf>) -> Self {
+        self.options = self.options.with_out_dir(out_dir);
+        self
+    }
+
+    /// Render up to `max_rules` rules of each `macro_rules!` macro (e.g. `($x:expr) => { ... };`),
+    /// instead of [`api::DEFAULT_MAX_MACRO_RULES`].
+    pub fn with_max_macro_rules(mut self, max_rules: usize) -> Self {
+        self.options = self.options.with_max_macro_rules(max_rules);
+        self
+    }
+
+    /// Extract the public API of the crate like [`Extractor::extract_public_api`], retaining the
+    /// details of each symbol (e.g. its kind, generics and defining module) and the diagnostics of
+    /// the source files (e.g. syntax errors).
+    pub fn extract_rust_api(
+        &self,
+        metadata: &LibraryMetadata,
+        parser: &mut Parser,
+    ) -> Result<RustApi, ExtractionError> {
+        let mut options = self.options.clone();
+        let crate_directory = metadata::find_crate_directory(&metadata.entry_point);
+        if let Some(crate_directory) = &crate_directory {
+            if !options.annotate_cfg {
+                let features =
+                    metadata::resolve_features(crate_directory, options.features.as_deref())
+                        .map_err(convert_metadata_error)?;
+                options.features = Some(features.into_iter().collect());
+            }
+            if options.out_dirs.is_empty() {
+                options.out_dirs = metadata::find_out_directories(crate_directory, &metadata.name);
+            }
+        }
+        let dependencies = get_dependencies(&metadata.entry_point)?;
+        let mut dependency_apis = match &crate_directory {
+            Some(crate_directory) if options.inline_reexports => {
+                Some(dependencies::DependencyApis::new(crate_directory))
+            }
+            _ => None,
+        };
+        let mut get_dependency_api = |name: &str| {
+            dependency_apis
+                .as_mut()
+                .and_then(|apis| apis.get_public_api(name))
+        };
+        api::build_rust_api(
+            &metadata.entry_point,
+            &metadata.name,
+            &options,
+            &dependencies,
+            Some(&mut get_dependency_api as &mut DependencyApiProvider),
+            parser,
+        )
+    }
+}
+
+fn get_dependencies(entry_point: &Path) -> Result<HashMap<String, String>, ExtractionError> {
+    match metadata::find_crate_directory(entry_point) {
+        Some(crate_directory) => {
+            metadata::resolve_dependencies(&crate_directory).map_err(convert_metadata_error)
+        }
+        None => Ok(HashMap::new()),
+    }
+}
+
+fn convert_metadata_error(error: LibraryMetadataError) -> ExtractionError {
+    match error {
+        LibraryMetadataError::MissingManifest(e) => ExtractionError::Io(e),
+        LibraryMetadataError::MalformedManifest(e) => ExtractionError::Malformed(e),
     }
 }
 
@@ -26,7 +168,13 @@ impl Extractor for RustExtractor {
     }
 
     fn get_library_metadata(&self, path: &Path) -> Result<LibraryMetadata, LibraryMetadataError> {
-        metadata::extract_metadata(path)
+        metadata::extract_metadata(path, |entry_point| {
+            let mut parser = Parser::new();
+            parser.set_language(&self.get_parser_language()).ok()?;
+            api::extract_crate_documentation(entry_point, &mut parser)
+                .ok()
+                .flatten()
+        })
     }
 
     fn extract_public_api(
@@ -34,7 +182,8 @@ impl Extractor for RustExtractor {
         metadata: &LibraryMetadata,
         parser: &mut Parser,
     ) -> Result<Vec<Namespace>, ExtractionError> {
-        api::build_public_api(&metadata.entry_point, &metadata.name, parser)
+        let api = self.extract_rust_api(metadata, parser)?;
+        Ok(api.namespaces.into_iter().map(Namespace::from).collect())
     }
 
     fn resolve_dependency_path(
@@ -49,6 +198,7 @@ impl Extractor for RustExtractor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::{IncludePath, SymbolKind, VisibilityOrigin};
     use crate::test_helpers::setup_parser;
     use assertables::{assert_contains, assert_ok};
     use daipendency_testing::tempdir::TempDir;
@@ -74,6 +224,24 @@ version = "0.1.0"
         assert_eq!(metadata.name, "test_crate");
     }
 
+    #[test]
+    fn crate_documentation_fallback() {
+        let temp_dir = TempDir::new();
+        let cargo_toml = temp_dir
+            .create_file("Cargo.toml", "[package]\nname = \"test_crate\"\n")
+            .unwrap();
+        temp_dir
+            .create_file("src/lib.rs", "//! Crate documentation\n\npub fn foo() {}\n")
+            .unwrap();
+        let analyser = RustExtractor::new();
+
+        let metadata = analyser
+            .get_library_metadata(cargo_toml.parent().unwrap())
+            .unwrap();
+
+        assert_eq!(metadata.documentation, "Crate documentation");
+    }
+
     #[test]
     fn extract_public_api() {
         let temp_dir = TempDir::new();
@@ -111,9 +279,587 @@ pub fn test_function() -> i32 {
         let analyser = RustExtractor::new();
         let dependency_name = "tree-sitter";
 
-        let result = analyser.resolve_dependency_path(dependency_name, &cargo_toml);
+        let result = analyser.resolve_dependency_path(dependency_name, cargo_toml);
 
         assert_ok!(&result);
         assert_contains!(result.unwrap().to_str().unwrap(), dependency_name);
     }
+
+    mod cfg {
+        use super::*;
+
+        fn create_crate(temp_dir: &TempDir) -> LibraryMetadata {
+            temp_dir
+                .create_file(
+                    "Cargo.toml",
+                    r#"[package]
+name = "test_crate"
+version = "0.1.0"
+
+[features]
+default = ["std"]
+std = []
+serde = []
+"#,
+                )
+                .unwrap();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[cfg(feature = "std")]
+pub fn std_function() {}
+
+#[cfg(feature = "serde")]
+pub fn serde_function() {}
+
+#[cfg(target_os = "plan9")]
+pub fn plan9_function() {}
+"#,
+                )
+                .unwrap();
+            LibraryMetadata {
+                name: "test_crate".to_string(),
+                version: Some("0.1.0".to_string()),
+                documentation: String::new(),
+                entry_point: lib_rs,
+            }
+        }
+
+        fn get_symbol_names(namespaces: &[Namespace]) -> Vec<String> {
+            let root = namespaces.iter().find(|n| n.name == "test_crate").unwrap();
+            root.symbols.iter().map(|s| s.name.clone()).collect()
+        }
+
+        #[test]
+        fn default_features() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let analyser = RustExtractor::new();
+            let mut parser = setup_parser();
+
+            let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+            assert_eq!(get_symbol_names(&namespaces), vec!["std_function"]);
+        }
+
+        #[test]
+        fn custom_features() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let analyser = RustExtractor::new().with_features(["serde"]);
+            let mut parser = setup_parser();
+
+            let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+            assert_eq!(get_symbol_names(&namespaces), vec!["serde_function"]);
+        }
+
+        #[test]
+        fn custom_target_options() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let analyser = RustExtractor::new()
+                .with_features(Vec::<String>::new())
+                .with_target_options([("target_os".to_string(), Some("plan9".to_string()))]);
+            let mut parser = setup_parser();
+
+            let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+            assert_eq!(get_symbol_names(&namespaces), vec!["plan9_function"]);
+        }
+
+        #[test]
+        fn annotations() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let analyser = RustExtractor::new().with_cfg_annotations();
+            let mut parser = setup_parser();
+
+            let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+            let root = namespaces.iter().find(|n| n.name == "test_crate").unwrap();
+            assert_eq!(root.symbols.len(), 3);
+            let serde_function = root
+                .symbols
+                .iter()
+                .find(|s| s.name == "serde_function")
+                .unwrap();
+            assert_eq!(
+                serde_function.source_code,
+                "// Available on crate feature `serde` only.\n#[cfg(feature = \"serde\")]\npub fn serde_function();"
+            );
+        }
+    }
+
+    mod doc_hidden {
+        use super::*;
+
+        fn create_crate(temp_dir: &TempDir) -> LibraryMetadata {
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+pub fn visible_function() {}
+
+#[doc(hidden)]
+pub fn hidden_function() {}
+"#,
+                )
+                .unwrap();
+            LibraryMetadata {
+                name: "test_crate".to_string(),
+                version: Some("0.1.0".to_string()),
+                documentation: String::new(),
+                entry_point: lib_rs,
+            }
+        }
+
+        #[test]
+        fn excluded_by_default() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let analyser = RustExtractor::new();
+            let mut parser = setup_parser();
+
+            let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+            let root = namespaces.iter().find(|n| n.name == "test_crate").unwrap();
+            assert_eq!(root.symbols.len(), 1);
+            assert!(root.get_symbol("visible_function").is_some());
+        }
+
+        #[test]
+        fn included_on_request() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let analyser = RustExtractor::new().with_doc_hidden_items();
+            let mut parser = setup_parser();
+
+            let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+            let root = namespaces.iter().find(|n| n.name == "test_crate").unwrap();
+            assert_eq!(root.symbols.len(), 2);
+            assert!(root.get_symbol("hidden_function").is_some());
+        }
+    }
+
+    mod macro_rules {
+        use super::*;
+
+        fn create_crate(temp_dir: &TempDir) -> LibraryMetadata {
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+#[macro_export]
+macro_rules! greet {
+    () => { "Hello" };
+    ($name:expr) => { format!("Hello, {}", $name) };
+    ($greeting:literal, $name:expr) => { format!("{}, {}", $greeting, $name) };
+}
+"#,
+                )
+                .unwrap();
+            LibraryMetadata {
+                name: "test_crate".to_string(),
+                version: Some("0.1.0".to_string()),
+                documentation: String::new(),
+                entry_point: lib_rs,
+            }
+        }
+
+        #[test]
+        fn all_rules_by_default() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let analyser = RustExtractor::new();
+            let mut parser = setup_parser();
+
+            let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+            let root = namespaces.iter().find(|n| n.name == "test_crate").unwrap();
+            let greet = root.get_symbol("greet").unwrap();
+            assert_eq!(
+                greet.source_code,
+                "#[macro_export]\nmacro_rules! greet {\n    () => { ... };\n    ($name:expr) => { ... };\n    ($greeting:literal, $name:expr) => { ... };\n}"
+            );
+        }
+
+        #[test]
+        fn custom_maximum() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let analyser = RustExtractor::new().with_max_macro_rules(1);
+            let mut parser = setup_parser();
+
+            let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+            let root = namespaces.iter().find(|n| n.name == "test_crate").unwrap();
+            let greet = root.get_symbol("greet").unwrap();
+            assert_eq!(
+                greet.source_code,
+                "#[macro_export]\nmacro_rules! greet {\n    () => { ... };\n    // 2 more rules omitted\n}"
+            );
+        }
+    }
+
+    mod syntax_errors {
+        use super::*;
+
+        fn create_crate(temp_dir: &TempDir) -> LibraryMetadata {
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+pub fn valid_function() {}
+
+pub struct Incomplete
+"#,
+                )
+                .unwrap();
+            LibraryMetadata {
+                name: "test_crate".to_string(),
+                version: Some("0.1.0".to_string()),
+                documentation: String::new(),
+                entry_point: lib_rs,
+            }
+        }
+
+        #[test]
+        fn reported_by_default() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let analyser = RustExtractor::new();
+            let mut parser = setup_parser();
+
+            let api = analyser.extract_rust_api(&metadata, &mut parser).unwrap();
+
+            assert_eq!(api.diagnostics.len(), 1);
+            assert_eq!(api.diagnostics[0].location.path, metadata.entry_point);
+            let root = api
+                .namespaces
+                .iter()
+                .find(|n| n.name == "test_crate")
+                .unwrap();
+            assert!(root.get_symbol("valid_function").is_some());
+        }
+
+        #[test]
+        fn rejected_in_strict_mode() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let analyser = RustExtractor::new().with_strict_parsing();
+            let mut parser = setup_parser();
+
+            let result = analyser.extract_public_api(&metadata, &mut parser);
+
+            let Err(ExtractionError::Malformed(message)) = result else {
+                panic!("Expected a malformed error, got {result:?}");
+            };
+            assert_contains!(message, "Missing `;`");
+        }
+
+        #[test]
+        fn decl_macro_in_strict_mode() {
+            let temp_dir = TempDir::new();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"
+pub fn before() {}
+
+/// Doubles the value.
+pub macro double($x:expr) {
+    $x * 2
+}
+
+pub macro pair {
+    ($a:expr) => { ($a, $a) },
+    ($a:expr, $b:expr) => { ($a, $b) },
+}
+"#,
+                )
+                .unwrap();
+            let metadata = LibraryMetadata {
+                name: "test_crate".to_string(),
+                version: Some("0.1.0".to_string()),
+                documentation: String::new(),
+                entry_point: lib_rs,
+            };
+            let analyser = RustExtractor::new().with_strict_parsing();
+            let mut parser = setup_parser();
+
+            let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+            let root = namespaces.iter().find(|n| n.name == "test_crate").unwrap();
+            let double = root.get_symbol("double").unwrap();
+            assert_eq!(
+                double.source_code,
+                "/// Doubles the value.\npub macro double($x:expr);"
+            );
+            assert!(root.get_symbol("pair").is_some());
+        }
+    }
+
+    #[test]
+    fn missing_modules_tolerated() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"
+pub mod generated;
+
+pub fn present_function() {}
+"#,
+            )
+            .unwrap();
+        let metadata = LibraryMetadata {
+            name: "test_crate".to_string(),
+            version: Some("0.1.0".to_string()),
+            documentation: String::new(),
+            entry_point: lib_rs,
+        };
+        let analyser = RustExtractor::new().with_missing_modules_tolerated();
+        let mut parser = setup_parser();
+
+        let api = analyser.extract_rust_api(&metadata, &mut parser).unwrap();
+
+        assert_eq!(api.diagnostics.len(), 1);
+        assert_contains!(api.diagnostics[0].message, "generated");
+        let generated = api
+            .namespaces
+            .iter()
+            .find(|n| n.name == "test_crate::generated")
+            .unwrap();
+        assert!(generated.is_unresolved);
+        let root = api
+            .namespaces
+            .iter()
+            .find(|n| n.name == "test_crate")
+            .unwrap();
+        assert!(!root.is_unresolved);
+        assert!(root.get_symbol("present_function").is_some());
+    }
+
+    mod source_includes {
+        use super::*;
+
+        fn create_crate(temp_dir: &TempDir) -> LibraryMetadata {
+            temp_dir
+                .create_file(
+                    "Cargo.toml",
+                    r#"[package]
+name = "test-crate"
+version = "0.1.0"
+"#,
+                )
+                .unwrap();
+            let lib_rs = temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    r#"include!(concat!(env!("OUT_DIR"), "/bindings.rs"));"#,
+                )
+                .unwrap();
+            LibraryMetadata {
+                name: "test-crate".to_string(),
+                version: Some("0.1.0".to_string()),
+                documentation: String::new(),
+                entry_point: lib_rs,
+            }
+        }
+
+        #[test]
+        fn discovered_out_dir() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            temp_dir
+                .create_file(
+                    "target/debug/build/test-crate-0123abcd/out/bindings.rs",
+                    "pub struct Bindings;",
+                )
+                .unwrap();
+            let analyser = RustExtractor::new();
+            let mut parser = setup_parser();
+
+            let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+            let root = namespaces.iter().find(|n| n.name == "test_crate").unwrap();
+            assert!(root.get_symbol("Bindings").is_some());
+        }
+
+        #[test]
+        fn custom_out_dir() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let bindings_rs = temp_dir
+                .create_file("generated/bindings.rs", "pub struct Bindings;")
+                .unwrap();
+            let analyser = RustExtractor::new().with_out_dir(bindings_rs.parent().unwrap());
+            let mut parser = setup_parser();
+
+            let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+            let root = namespaces.iter().find(|n| n.name == "test_crate").unwrap();
+            assert!(root.get_symbol("Bindings").is_some());
+        }
+
+        #[test]
+        fn unresolved_include() {
+            let temp_dir = TempDir::new();
+            let metadata = create_crate(&temp_dir);
+            let analyser = RustExtractor::new();
+            let mut parser = setup_parser();
+
+            let api = analyser.extract_rust_api(&metadata, &mut parser).unwrap();
+
+            let root = api
+                .namespaces
+                .iter()
+                .find(|n| n.name == "test_crate")
+                .unwrap();
+            assert!(root.symbols.is_empty());
+            assert_eq!(root.unresolved_includes.len(), 1);
+            let unresolved_include = &root.unresolved_includes[0];
+            assert_eq!(
+                unresolved_include.path,
+                Some(IncludePath::OutDir("/bindings.rs".to_string()))
+            );
+            assert_eq!(
+                unresolved_include.source_code,
+                "include!(concat!(env!(\"OUT_DIR\"), \"/bindings.rs\"));"
+            );
+            assert_eq!(unresolved_include.location.path, metadata.entry_point);
+        }
+    }
+
+    #[test]
+    fn renamed_dependency_reexport() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                "Cargo.toml",
+                r#"[package]
+name = "test_crate"
+version = "0.1.0"
+
+[dependencies]
+json = { package = "serde_json", version = "1.0" }
+"#,
+            )
+            .unwrap();
+        let lib_rs = temp_dir
+            .create_file("src/lib.rs", "pub use json::Value;")
+            .unwrap();
+        let metadata = LibraryMetadata {
+            name: "test_crate".to_string(),
+            version: Some("0.1.0".to_string()),
+            documentation: String::new(),
+            entry_point: lib_rs,
+        };
+        let analyser = RustExtractor::new();
+        let mut parser = setup_parser();
+
+        let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+        let root = namespaces.iter().find(|n| n.name == "test_crate").unwrap();
+        let symbol = root.get_symbol("Value").unwrap();
+        assert_eq!(symbol.source_code, "pub use serde_json::Value;");
+    }
+
+    #[test]
+    fn inlined_reexports() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                "dependency/Cargo.toml",
+                "[package]\nname = \"dependency\"\nversion = \"0.1.0\"\n",
+            )
+            .unwrap();
+        temp_dir
+            .create_file("dependency/src/lib.rs", "pub struct Foo;")
+            .unwrap();
+        temp_dir
+            .create_file(
+                "test_crate/Cargo.toml",
+                r#"[package]
+name = "test_crate"
+version = "0.1.0"
+
+[dependencies]
+dependency = { path = "../dependency" }
+"#,
+            )
+            .unwrap();
+        let lib_rs = temp_dir
+            .create_file(
+                "test_crate/src/lib.rs",
+                "pub use dependency::Foo;\npub use dependency::Missing;",
+            )
+            .unwrap();
+        let metadata = LibraryMetadata {
+            name: "test_crate".to_string(),
+            version: Some("0.1.0".to_string()),
+            documentation: String::new(),
+            entry_point: lib_rs,
+        };
+        let analyser = RustExtractor::new().with_inlined_reexports();
+        let mut parser = setup_parser();
+
+        let namespaces = analyser.extract_public_api(&metadata, &mut parser).unwrap();
+
+        let root = namespaces.iter().find(|n| n.name == "test_crate").unwrap();
+        assert_eq!(
+            root.get_symbol("Foo").unwrap().source_code,
+            "pub struct Foo;"
+        );
+        assert_eq!(
+            root.get_symbol("Missing").unwrap().source_code,
+            "pub use dependency::Missing;"
+        );
+    }
+
+    #[test]
+    fn extract_rust_api() {
+        let temp_dir = TempDir::new();
+        let lib_rs = temp_dir
+            .create_file(
+                "src/lib.rs",
+                r#"
+pub mod inner {
+    pub struct Wrapper<T>(pub T);
+}
+
+pub use inner::Wrapper;
+"#,
+            )
+            .unwrap();
+        let metadata = LibraryMetadata {
+            name: "test_crate".to_string(),
+            version: Some("0.1.0".to_string()),
+            documentation: String::new(),
+            entry_point: lib_rs,
+        };
+        let analyser = RustExtractor::new();
+        let mut parser = setup_parser();
+
+        let api = analyser.extract_rust_api(&metadata, &mut parser).unwrap();
+
+        let root = api
+            .namespaces
+            .iter()
+            .find(|n| n.name == "test_crate")
+            .unwrap();
+        let details = root.get_symbol("Wrapper").unwrap();
+        assert_eq!(details.kind, SymbolKind::Struct);
+        assert_eq!(details.generics, Some("<T>".to_string()));
+        assert_eq!(details.visibility_origin, VisibilityOrigin::Reexport);
+        assert_eq!(details.module_path, "test_crate::inner");
+        assert_eq!(details.canonical_path, "test_crate::inner::Wrapper");
+        assert_eq!(
+            details.public_paths,
+            vec!["test_crate::Wrapper", "test_crate::inner::Wrapper"]
+        );
+    }
 }
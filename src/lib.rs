@@ -1,4 +1,7 @@
-mod api;
+//! Daipendency extractor for Rust library crates.
+#![warn(missing_docs)]
+
+pub mod api;
 mod dependencies;
 mod extractor;
 mod metadata;
@@ -7,4 +10,8 @@ mod test_helpers;
 #[cfg(test)]
 mod treesitter_test_helpers;
 
+pub use api::{
+    Diagnostic, ExtractionOptions, RustApi, RustNamespace, SourceLocation, SourceSpan,
+    SymbolDetails, SymbolKind, VisibilityOrigin,
+};
 pub use extractor::RustExtractor;
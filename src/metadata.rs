@@ -1,37 +1,74 @@
 use daipendency_extractor::{LibraryMetadata, LibraryMetadataError};
-use serde::{de::Error, Deserialize, Serialize};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 const DEFAULT_LIB_PATH: &str = "src/lib.rs";
-const README_PATH: &str = "README.md";
+/// The readme files used when `package.readme` is unset or `true`, in order of preference
+const DEFAULT_README_PATHS: [&str; 4] = ["README.md", "README.txt", "README.rst", "README"];
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 struct PackageConfig {
     name: String,
-    #[serde(default, deserialize_with = "deserialize_version")]
-    version: Option<String>,
+    version: Option<InheritableField<String>>,
+    description: Option<InheritableField<String>>,
+    readme: Option<InheritableField<ReadmeConfig>>,
+    license: Option<InheritableField<String>>,
+    edition: Option<InheritableField<String>>,
+    /// The path to the workspace root, if it isn't an ancestor of the package
+    workspace: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+/// A package field that may be inherited from the workspace (e.g. `version.workspace = true`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(untagged)]
-enum VersionField {
-    Direct(Option<String>),
-    #[serde(rename = "workspace")]
-    Workspace(serde::de::IgnoredAny),
+enum InheritableField<T> {
+    Value(T),
+    Inherited { workspace: bool },
 }
 
-fn deserialize_version<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    match VersionField::deserialize(deserializer) {
-        Ok(VersionField::Direct(version)) => Ok(version),
-        Ok(VersionField::Workspace(_)) => Ok(None),
-        Err(e) => Err(D::Error::custom(format!("Malformed version field: {}", e))),
+impl<T> InheritableField<T> {
+    fn is_inherited(&self) -> bool {
+        matches!(self, InheritableField::Inherited { workspace: true })
+    }
+
+    fn into_value(self) -> Option<T> {
+        match self {
+            InheritableField::Value(value) => Some(value),
+            InheritableField::Inherited { .. } => None,
+        }
     }
 }
 
+/// The `readme` field, which is either a path or whether to use the default readme file.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+enum ReadmeConfig {
+    Path(String),
+    Enabled(bool),
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct WorkspacePackageConfig {
+    version: Option<String>,
+    description: Option<String>,
+    readme: Option<ReadmeConfig>,
+    license: Option<String>,
+    edition: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkspaceConfig {
+    #[serde(default)]
+    package: WorkspacePackageConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkspaceManifest {
+    workspace: Option<WorkspaceConfig>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct LibConfig {
     path: Option<String>,
@@ -43,16 +80,49 @@ struct CargoConfig {
     lib: Option<LibConfig>,
 }
 
-pub fn extract_metadata(path: &Path) -> Result<LibraryMetadata, LibraryMetadataError> {
+#[derive(Debug, Deserialize)]
+struct FeaturesConfig {
+    #[serde(default)]
+    features: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    dependencies: HashMap<String, toml::Value>,
+    #[serde(default, rename = "build-dependencies")]
+    build_dependencies: HashMap<String, toml::Value>,
+    #[serde(default)]
+    target: HashMap<String, TargetDependenciesConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DependenciesConfig {
+    #[serde(default)]
+    dependencies: HashMap<String, toml::Value>,
+    #[serde(default)]
+    target: HashMap<String, TargetDependenciesConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TargetDependenciesConfig {
+    #[serde(default)]
+    dependencies: HashMap<String, toml::Value>,
+    #[serde(default, rename = "build-dependencies")]
+    build_dependencies: HashMap<String, toml::Value>,
+}
+
+/// Extract the metadata of the package at `path`.
+///
+/// Its documentation is its readme, or the crate-level documentation returned by
+/// `get_crate_documentation` for its entry point if it has none.
+pub fn extract_metadata(
+    path: &Path,
+    get_crate_documentation: impl FnOnce(&Path) -> Option<String>,
+) -> Result<LibraryMetadata, LibraryMetadataError> {
     let cargo_toml_path = path.join("Cargo.toml");
     let cargo_toml_content =
         fs::read_to_string(&cargo_toml_path).map_err(LibraryMetadataError::MissingManifest)?;
 
-    let cargo_config: CargoConfig = toml::from_str(&cargo_toml_content)
+    let mut cargo_config: CargoConfig = toml::from_str(&cargo_toml_content)
         .map_err(|e| LibraryMetadataError::MalformedManifest(format!("{}", e)))?;
-
-    let readme_path = path.join(README_PATH);
-    let documentation = fs::read_to_string(&readme_path).unwrap_or_default();
+    inherit_workspace_fields(&mut cargo_config.package, path)?;
 
     let entry_point = cargo_config
         .lib
@@ -60,14 +130,297 @@ pub fn extract_metadata(path: &Path) -> Result<LibraryMetadata, LibraryMetadataE
         .map(|path_str| path.join(Path::new(&path_str)))
         .unwrap_or_else(|| path.join(DEFAULT_LIB_PATH));
 
+    let readme = cargo_config
+        .package
+        .readme
+        .and_then(InheritableField::into_value);
+    let documentation = read_readme(path, readme)
+        .or_else(|| get_crate_documentation(&entry_point))
+        .unwrap_or_default();
+
     Ok(LibraryMetadata {
         name: cargo_config.package.name,
-        version: cargo_config.package.version,
+        version: cargo_config
+            .package
+            .version
+            .and_then(InheritableField::into_value),
         documentation,
         entry_point,
     })
 }
 
+/// Replace the fields inherited from the workspace (e.g. `version.workspace = true`) with the
+/// values in the `[workspace.package]` table of the workspace root.
+fn inherit_workspace_fields(
+    package: &mut PackageConfig,
+    path: &Path,
+) -> Result<(), LibraryMetadataError> {
+    let is_inheriting = [
+        package.version.as_ref().is_some_and(|f| f.is_inherited()),
+        package
+            .description
+            .as_ref()
+            .is_some_and(|f| f.is_inherited()),
+        package.readme.as_ref().is_some_and(|f| f.is_inherited()),
+        package.license.as_ref().is_some_and(|f| f.is_inherited()),
+        package.edition.as_ref().is_some_and(|f| f.is_inherited()),
+    ]
+    .into_iter()
+    .any(|is_inherited| is_inherited);
+    if !is_inheriting {
+        return Ok(());
+    }
+
+    let (workspace_directory, mut workspace) =
+        find_workspace_package(path, package.workspace.as_deref())?;
+    // Like Cargo, make the readme path relative to the package rather than the workspace root
+    if let Some(ReadmeConfig::Path(readme_path)) = &workspace.readme {
+        let readme_path = workspace_directory.join(readme_path);
+        workspace.readme = Some(ReadmeConfig::Path(
+            readme_path.to_string_lossy().to_string(),
+        ));
+    }
+    inherit_field("version", &mut package.version, &workspace.version)?;
+    inherit_field(
+        "description",
+        &mut package.description,
+        &workspace.description,
+    )?;
+    inherit_field("readme", &mut package.readme, &workspace.readme)?;
+    inherit_field("license", &mut package.license, &workspace.license)?;
+    inherit_field("edition", &mut package.edition, &workspace.edition)?;
+    Ok(())
+}
+
+fn inherit_field<T: Clone>(
+    field_name: &str,
+    field: &mut Option<InheritableField<T>>,
+    workspace_value: &Option<T>,
+) -> Result<(), LibraryMetadataError> {
+    if !field.as_ref().is_some_and(|f| f.is_inherited()) {
+        return Ok(());
+    }
+    let value = workspace_value.clone().ok_or_else(|| {
+        LibraryMetadataError::MalformedManifest(format!(
+            "Field `package.{}` is inherited from the workspace, but `workspace.package.{}` is not defined",
+            field_name, field_name
+        ))
+    })?;
+    *field = Some(InheritableField::Value(value));
+    Ok(())
+}
+
+/// Find the directory and `[workspace.package]` table of the workspace root, which is either at
+/// `workspace_path` (relative to the package) or in the closest ancestor declaring a workspace.
+fn find_workspace_package(
+    path: &Path,
+    workspace_path: Option<&str>,
+) -> Result<(PathBuf, WorkspacePackageConfig), LibraryMetadataError> {
+    let candidate_directories: Vec<PathBuf> = match workspace_path {
+        Some(workspace_path) => vec![path.join(workspace_path)],
+        None => path.ancestors().map(Path::to_path_buf).collect(),
+    };
+    for directory in candidate_directories {
+        let Ok(cargo_toml_content) = fs::read_to_string(directory.join("Cargo.toml")) else {
+            continue;
+        };
+        let manifest: WorkspaceManifest = toml::from_str(&cargo_toml_content)
+            .map_err(|e| LibraryMetadataError::MalformedManifest(format!("{}", e)))?;
+        if let Some(workspace) = manifest.workspace {
+            return Ok((directory, workspace.package));
+        }
+    }
+    Err(LibraryMetadataError::MalformedManifest(format!(
+        "Package at {} inherits fields from a workspace, but no workspace root was found",
+        path.display()
+    )))
+}
+
+/// Read the readme of the package, as specified by `package.readme` or found in the default locations.
+fn read_readme(path: &Path, readme: Option<ReadmeConfig>) -> Option<String> {
+    let candidate_paths: Vec<PathBuf> = match readme {
+        Some(ReadmeConfig::Path(readme_path)) => vec![path.join(readme_path)],
+        Some(ReadmeConfig::Enabled(false)) => vec![],
+        Some(ReadmeConfig::Enabled(true)) | None => DEFAULT_README_PATHS
+            .iter()
+            .map(|readme_path| path.join(readme_path))
+            .collect(),
+    };
+    candidate_paths
+        .iter()
+        .find_map(|readme_path| fs::read_to_string(readme_path).ok())
+}
+
+/// Find the directory of the crate containing `path`, by looking for its `Cargo.toml`.
+pub fn find_crate_directory(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|directory| directory.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Resolve the features enabled by `requested_features`, or by the `default` feature if `None`.
+///
+/// Features enabled by other features are included, but optional dependencies (`dep:foo`) and
+/// dependency features (`foo/bar`) are not. Like Cargo, each optional dependency that no feature
+/// refers to with `dep:` has an implicit feature of the same name, which `foo/bar` also enables.
+pub fn resolve_features(
+    path: &Path,
+    requested_features: Option<&[String]>,
+) -> Result<HashSet<String>, LibraryMetadataError> {
+    let cargo_toml_content = fs::read_to_string(path.join("Cargo.toml"))
+        .map_err(LibraryMetadataError::MissingManifest)?;
+    let config: FeaturesConfig = toml::from_str(&cargo_toml_content)
+        .map_err(|e| LibraryMetadataError::MalformedManifest(format!("{}", e)))?;
+
+    let mut pending_features: Vec<String> = match requested_features {
+        Some(features) => features.to_vec(),
+        None => config.features.get("default").cloned().unwrap_or_default(),
+    };
+    let implicit_features = get_implicit_features(&config);
+    let mut enabled_features = HashSet::new();
+    while let Some(feature) = pending_features.pop() {
+        if feature.starts_with("dep:") {
+            continue;
+        }
+        if let Some((dependency, _)) = feature.split_once('/') {
+            // `foo?/bar` only enables `bar` if `foo` is enabled by other means
+            if implicit_features.contains(dependency) {
+                pending_features.push(dependency.to_string());
+            }
+            continue;
+        }
+        if let Some(implied_features) = config.features.get(&feature) {
+            if !enabled_features.contains(&feature) {
+                pending_features.extend(implied_features.iter().cloned());
+            }
+        }
+        enabled_features.insert(feature);
+    }
+    Ok(enabled_features)
+}
+
+/// Get the features Cargo creates implicitly for the optional dependencies that no feature refers
+/// to with `dep:`.
+fn get_implicit_features(config: &FeaturesConfig) -> HashSet<String> {
+    let explicit_dependencies: HashSet<&str> = config
+        .features
+        .values()
+        .flatten()
+        .filter_map(|feature| feature.strip_prefix("dep:"))
+        .collect();
+    let dependencies = config
+        .dependencies
+        .iter()
+        .chain(&config.build_dependencies)
+        .chain(
+            config
+                .target
+                .values()
+                .flat_map(|target| target.dependencies.iter().chain(&target.build_dependencies)),
+        );
+    dependencies
+        .filter(|(_, dependency)| {
+            dependency
+                .get("optional")
+                .and_then(|optional| optional.as_bool())
+                .unwrap_or(false)
+        })
+        .map(|(name, _)| name)
+        .filter(|name| {
+            !explicit_dependencies.contains(name.as_str()) && !config.features.contains_key(*name)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Resolve the crates the package depends on, including target-specific dependencies.
+///
+/// They're keyed by the name they're referred to in the code, with the names of the crates they
+/// refer to as values (e.g. `bar` => `foo` for `bar = { package = "foo" }`).
+pub fn resolve_dependencies(path: &Path) -> Result<HashMap<String, String>, LibraryMetadataError> {
+    let cargo_toml_content = fs::read_to_string(path.join("Cargo.toml"))
+        .map_err(LibraryMetadataError::MissingManifest)?;
+    let config: DependenciesConfig = toml::from_str(&cargo_toml_content)
+        .map_err(|e| LibraryMetadataError::MalformedManifest(format!("{}", e)))?;
+
+    let dependencies = config.dependencies.into_iter().chain(
+        config
+            .target
+            .into_values()
+            .flat_map(|target| target.dependencies),
+    );
+    Ok(dependencies
+        .map(|(name, dependency)| {
+            let package = dependency
+                .get("package")
+                .and_then(|package| package.as_str())
+                .unwrap_or(&name);
+            (get_crate_name(&name), get_crate_name(package))
+        })
+        .collect())
+}
+
+/// Find the output directories of the build script of the package `package_name` (i.e. its
+/// `OUT_DIR`s), under the `target` directory of the crate or of one of its ancestors (e.g. its
+/// workspace).
+///
+/// They're sorted from the most recently modified, as each profile and target has its own.
+pub fn find_out_directories(crate_directory: &Path, package_name: &str) -> Vec<PathBuf> {
+    let Some(target_directory) = crate_directory
+        .ancestors()
+        .map(|directory| directory.join("target"))
+        .find(|directory| directory.is_dir())
+    else {
+        return Vec::new();
+    };
+    // Profiles are in `target/<profile>`, or `target/<triple>/<profile>` when cross-compiling
+    let profile_directories =
+        list_directories(&target_directory)
+            .into_iter()
+            .flat_map(|directory| {
+                let mut directories = list_directories(&directory);
+                directories.push(directory);
+                directories
+            });
+    let crate_name = get_crate_name(package_name);
+    let mut out_directories: Vec<PathBuf> = profile_directories
+        .flat_map(|directory| list_directories(&directory.join("build")))
+        .filter(|directory| {
+            let directory_name = directory.file_name().unwrap().to_string_lossy();
+            directory_name.rsplit_once('-').is_some_and(|(name, hash)| {
+                get_crate_name(name) == crate_name
+                    && hash.chars().all(|character| character.is_ascii_hexdigit())
+            })
+        })
+        .map(|directory| directory.join("out"))
+        .filter(|directory| directory.is_dir())
+        .collect();
+    out_directories.sort_by_key(|directory| {
+        std::cmp::Reverse(
+            fs::metadata(directory)
+                .and_then(|metadata| metadata.modified())
+                .ok(),
+        )
+    });
+    out_directories
+}
+
+fn list_directories(path: &Path) -> Vec<PathBuf> {
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Get the name a package is referred to in the code (e.g. `serde_json` for `serde-json`).
+pub fn get_crate_name(package_name: &str) -> String {
+    package_name.replace('-', "_")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,14 +433,15 @@ mod tests {
         let config = CargoConfig {
             package: PackageConfig {
                 name: "test-crate".to_string(),
-                version: Some("0.1.0".to_string()),
+                version: Some(InheritableField::Value("0.1.0".to_string())),
+                ..Default::default()
             },
             lib: custom_lib.map(|path| LibConfig { path: Some(path) }),
         };
 
         let cargo_toml = toml::to_string(&config).unwrap();
         temp_dir.create_file("Cargo.toml", &cargo_toml)?;
-        temp_dir.create_file(README_PATH, "Test crate")?;
+        temp_dir.create_file("README.md", "Test crate")?;
         Ok(())
     }
 
@@ -97,7 +451,7 @@ mod tests {
         create_test_crate(&temp_dir, None).unwrap();
         let dummy = temp_dir.create_file("dummy", "").unwrap();
 
-        let result = extract_metadata(dummy.parent().unwrap());
+        let result = extract_metadata(dummy.parent().unwrap(), |_| None);
 
         let metadata = result.unwrap();
         assert_eq!(metadata.name, "test-crate");
@@ -110,7 +464,7 @@ mod tests {
         let temp_dir = TempDir::new();
         let dummy = temp_dir.create_file("dummy", "").unwrap();
 
-        let result = extract_metadata(dummy.parent().unwrap());
+        let result = extract_metadata(dummy.parent().unwrap(), |_| None);
 
         assert!(matches!(
             result,
@@ -124,17 +478,17 @@ mod tests {
         let config = CargoConfig {
             package: PackageConfig {
                 name: "test-crate".to_string(),
-                version: None,
+                ..Default::default()
             },
             lib: None,
         };
         temp_dir
             .create_file("Cargo.toml", &toml::to_string(&config).unwrap())
             .unwrap();
-        temp_dir.create_file(README_PATH, "Test crate").unwrap();
+        temp_dir.create_file("README.md", "Test crate").unwrap();
         let dummy = temp_dir.create_file("dummy", "").unwrap();
 
-        let result = extract_metadata(dummy.parent().unwrap()).unwrap();
+        let result = extract_metadata(dummy.parent().unwrap(), |_| None).unwrap();
 
         assert_eq!(result.version, None);
     }
@@ -147,7 +501,7 @@ mod tests {
             .unwrap();
         let dummy = temp_dir.create_file("dummy", "").unwrap();
 
-        let result = extract_metadata(dummy.parent().unwrap());
+        let result = extract_metadata(dummy.parent().unwrap(), |_| None);
 
         assert!(matches!(
             result,
@@ -163,7 +517,7 @@ mod tests {
             .unwrap();
         let dummy = temp_dir.create_file("dummy", "").unwrap();
 
-        let result = extract_metadata(dummy.parent().unwrap());
+        let result = extract_metadata(dummy.parent().unwrap(), |_| None);
 
         assert!(matches!(
             result,
@@ -177,27 +531,286 @@ mod tests {
         create_test_crate(&temp_dir, None).unwrap();
         let dummy = temp_dir.create_file("dummy", "").unwrap();
 
-        let result = extract_metadata(dummy.parent().unwrap());
+        let result = extract_metadata(dummy.parent().unwrap(), |_| None);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap().documentation, "Test crate");
     }
 
-    #[test]
-    fn workspace_version() {
-        let temp_dir = TempDir::new();
-        let cargo_toml = r#"
+    mod workspace_inheritance {
+        use super::*;
+
+        const MEMBER_CARGO_TOML: &str = r#"
 [package]
 name = "test-crate"
 version.workspace = true
+description.workspace = true
+readme.workspace = true
+license.workspace = true
+edition.workspace = true
 "#;
-        temp_dir.create_file("Cargo.toml", cargo_toml).unwrap();
-        temp_dir.create_file(README_PATH, "Test crate").unwrap();
-        let dummy = temp_dir.create_file("dummy", "").unwrap();
 
-        let metadata = extract_metadata(dummy.parent().unwrap()).unwrap();
+        #[test]
+        fn inherited_fields() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "Cargo.toml",
+                    r#"
+[workspace]
+members = ["member"]
+
+[workspace.package]
+version = "1.2.3"
+description = "A test crate"
+readme = "README.md"
+license = "MIT"
+edition = "2021"
+"#,
+                )
+                .unwrap();
+            let cargo_toml = temp_dir
+                .create_file("member/Cargo.toml", MEMBER_CARGO_TOML)
+                .unwrap();
+            let crate_path = cargo_toml.parent().unwrap();
+            let cargo_config: CargoConfig = toml::from_str(MEMBER_CARGO_TOML).unwrap();
+            let mut package = cargo_config.package;
+
+            inherit_workspace_fields(&mut package, crate_path).unwrap();
+
+            assert_eq!(
+                package.version.and_then(InheritableField::into_value),
+                Some("1.2.3".to_string())
+            );
+            assert_eq!(
+                package.description.and_then(InheritableField::into_value),
+                Some("A test crate".to_string())
+            );
+            assert!(matches!(
+                package.readme.and_then(InheritableField::into_value),
+                Some(ReadmeConfig::Path(path))
+                if Path::new(&path) == crate_path.parent().unwrap().join("README.md")
+            ));
+            assert_eq!(
+                package.license.and_then(InheritableField::into_value),
+                Some("MIT".to_string())
+            );
+            assert_eq!(
+                package.edition.and_then(InheritableField::into_value),
+                Some("2021".to_string())
+            );
+        }
+
+        #[test]
+        fn inherited_version() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "Cargo.toml",
+                    "[workspace]\n\n[workspace.package]\nversion = \"1.2.3\"\n",
+                )
+                .unwrap();
+            let cargo_toml = temp_dir
+                .create_file(
+                    "crates/member/Cargo.toml",
+                    "[package]\nname = \"test-crate\"\nversion.workspace = true\n",
+                )
+                .unwrap();
+
+            let metadata = extract_metadata(cargo_toml.parent().unwrap(), |_| None).unwrap();
+
+            assert_eq!(metadata.version, Some("1.2.3".to_string()));
+        }
+
+        #[test]
+        fn explicit_workspace_path() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "workspace/Cargo.toml",
+                    "[workspace]\n\n[workspace.package]\nversion = \"1.2.3\"\n",
+                )
+                .unwrap();
+            let cargo_toml = temp_dir
+                .create_file(
+                    "member/Cargo.toml",
+                    "[package]\nname = \"test-crate\"\nversion.workspace = true\nworkspace = \"../workspace\"\n",
+                )
+                .unwrap();
+
+            let metadata = extract_metadata(cargo_toml.parent().unwrap(), |_| None).unwrap();
+
+            assert_eq!(metadata.version, Some("1.2.3".to_string()));
+        }
+
+        #[test]
+        fn package_as_workspace_root() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir
+                .create_file(
+                    "Cargo.toml",
+                    r#"
+[package]
+name = "test-crate"
+version.workspace = true
+
+[workspace.package]
+version = "1.2.3"
+"#,
+                )
+                .unwrap();
+
+            let metadata = extract_metadata(cargo_toml.parent().unwrap(), |_| None).unwrap();
+
+            assert_eq!(metadata.version, Some("1.2.3".to_string()));
+        }
+
+        #[test]
+        fn undefined_workspace_field() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "Cargo.toml",
+                    "[workspace]\n\n[workspace.package]\nversion = \"1.2.3\"\n",
+                )
+                .unwrap();
+            let cargo_toml = temp_dir
+                .create_file("member/Cargo.toml", MEMBER_CARGO_TOML)
+                .unwrap();
+
+            let result = extract_metadata(cargo_toml.parent().unwrap(), |_| None);
+
+            assert!(matches!(
+                result,
+                Err(LibraryMetadataError::MalformedManifest(message))
+                if message.contains("workspace.package.description")
+            ));
+        }
+
+        #[test]
+        fn missing_workspace_root() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir
+                .create_file(
+                    "Cargo.toml",
+                    "[package]\nname = \"test-crate\"\nversion.workspace = true\n",
+                )
+                .unwrap();
+
+            let result = extract_metadata(cargo_toml.parent().unwrap(), |_| None);
+
+            assert!(matches!(
+                result,
+                Err(LibraryMetadataError::MalformedManifest(message))
+                if message.contains("no workspace root was found")
+            ));
+        }
+    }
+
+    mod documentation {
+        use super::*;
+
+        fn get_stub_documentation(entry_point: &Path) -> Option<String> {
+            Some(format!("Documentation of {}", entry_point.display()))
+        }
+
+        fn create_cargo_toml(temp_dir: &TempDir, readme: Option<&str>) -> PathBuf {
+            let readme_field = readme
+                .map(|readme| format!("readme = {}\n", readme))
+                .unwrap_or_default();
+            let cargo_toml = temp_dir
+                .create_file(
+                    "Cargo.toml",
+                    &format!("[package]\nname = \"test-crate\"\n{}", readme_field),
+                )
+                .unwrap();
+            cargo_toml.parent().unwrap().to_path_buf()
+        }
+
+        #[test]
+        fn custom_readme_path() {
+            let temp_dir = TempDir::new();
+            let crate_path = create_cargo_toml(&temp_dir, Some("\"docs/intro.md\""));
+            temp_dir.create_file("docs/intro.md", "Intro").unwrap();
+            temp_dir.create_file("README.md", "Readme").unwrap();
+
+            let metadata = extract_metadata(&crate_path, |_| None).unwrap();
+
+            assert_eq!(metadata.documentation, "Intro");
+        }
+
+        #[test]
+        fn alternative_readme_file() {
+            let temp_dir = TempDir::new();
+            let crate_path = create_cargo_toml(&temp_dir, None);
+            temp_dir.create_file("README.rst", "Readme").unwrap();
+
+            let metadata = extract_metadata(&crate_path, |_| None).unwrap();
+
+            assert_eq!(metadata.documentation, "Readme");
+        }
+
+        #[test]
+        fn default_readme_enabled() {
+            let temp_dir = TempDir::new();
+            let crate_path = create_cargo_toml(&temp_dir, Some("true"));
+            temp_dir.create_file("README", "Readme").unwrap();
+
+            let metadata = extract_metadata(&crate_path, |_| None).unwrap();
+
+            assert_eq!(metadata.documentation, "Readme");
+        }
+
+        #[test]
+        fn readme_disabled() {
+            let temp_dir = TempDir::new();
+            let crate_path = create_cargo_toml(&temp_dir, Some("false"));
+            temp_dir.create_file("README.md", "Readme").unwrap();
+
+            let metadata = extract_metadata(&crate_path, get_stub_documentation).unwrap();
+
+            assert_eq!(
+                metadata.documentation,
+                format!("Documentation of {}", metadata.entry_point.display())
+            );
+        }
+
+        #[test]
+        fn crate_documentation_fallback() {
+            let temp_dir = TempDir::new();
+            let crate_path = create_cargo_toml(&temp_dir, None);
+
+            let metadata = extract_metadata(&crate_path, get_stub_documentation).unwrap();
+
+            assert_eq!(
+                metadata.documentation,
+                format!(
+                    "Documentation of {}",
+                    crate_path.join(DEFAULT_LIB_PATH).display()
+                )
+            );
+        }
+
+        #[test]
+        fn readme_preferred() {
+            let temp_dir = TempDir::new();
+            let crate_path = create_cargo_toml(&temp_dir, None);
+            temp_dir.create_file("README.md", "Readme").unwrap();
+
+            let metadata = extract_metadata(&crate_path, get_stub_documentation).unwrap();
 
-        assert_eq!(metadata.version, None);
+            assert_eq!(metadata.documentation, "Readme");
+        }
+
+        #[test]
+        fn no_documentation() {
+            let temp_dir = TempDir::new();
+            let crate_path = create_cargo_toml(&temp_dir, None);
+
+            let metadata = extract_metadata(&crate_path, |_| None).unwrap();
+
+            assert_eq!(metadata.documentation, "");
+        }
     }
 
     mod entrypoint {
@@ -210,7 +823,7 @@ version.workspace = true
             let dummy = temp_dir.create_file("dummy", "").unwrap();
             let root_dir = dummy.parent().unwrap();
 
-            let metadata = extract_metadata(root_dir).unwrap();
+            let metadata = extract_metadata(root_dir, |_| None).unwrap();
 
             assert_eq!(metadata.entry_point, root_dir.join(DEFAULT_LIB_PATH));
         }
@@ -223,9 +836,311 @@ version.workspace = true
             let dummy = temp_dir.create_file("dummy", "").unwrap();
             let root_dir = dummy.parent().unwrap();
 
-            let metadata = extract_metadata(root_dir).unwrap();
+            let metadata = extract_metadata(root_dir, |_| None).unwrap();
 
             assert_eq!(metadata.entry_point, root_dir.join(custom_lib_path));
         }
     }
+
+    mod features {
+        use super::*;
+        use assertables::*;
+
+        const CARGO_TOML: &str = r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+
+[features]
+default = ["std"]
+std = ["alloc", "dep:libc", "serde?/std"]
+alloc = []
+full = ["std", "extra"]
+extra = []
+"#;
+
+        #[test]
+        fn default_features() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir.create_file("Cargo.toml", CARGO_TOML).unwrap();
+
+            let features = resolve_features(cargo_toml.parent().unwrap(), None).unwrap();
+
+            assert_set_eq!(features, ["std".to_string(), "alloc".to_string()]);
+        }
+
+        #[test]
+        fn requested_features() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir.create_file("Cargo.toml", CARGO_TOML).unwrap();
+
+            let features =
+                resolve_features(cargo_toml.parent().unwrap(), Some(&["full".to_string()]))
+                    .unwrap();
+
+            assert_set_eq!(
+                features,
+                [
+                    "full".to_string(),
+                    "std".to_string(),
+                    "alloc".to_string(),
+                    "extra".to_string()
+                ]
+            );
+        }
+
+        #[test]
+        fn undeclared_features() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir
+                .create_file("Cargo.toml", "[package]\nname = \"test-crate\"\n")
+                .unwrap();
+
+            let features =
+                resolve_features(cargo_toml.parent().unwrap(), Some(&["serde".to_string()]))
+                    .unwrap();
+
+            assert_set_eq!(features, ["serde".to_string()]);
+        }
+
+        mod optional_dependencies {
+            use super::*;
+
+            const CARGO_TOML: &str = r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+
+[dependencies]
+serde = { version = "1", optional = true }
+libc = { version = "0.2", optional = true }
+log = "0.4"
+
+[target.'cfg(unix)'.dependencies]
+nix = { version = "0.29", optional = true }
+
+[features]
+std = ["dep:libc"]
+logging = ["nix/signal"]
+"#;
+
+            fn resolve(requested_features: &[&str]) -> HashSet<String> {
+                let temp_dir = TempDir::new();
+                let cargo_toml = temp_dir.create_file("Cargo.toml", CARGO_TOML).unwrap();
+                let requested_features: Vec<String> =
+                    requested_features.iter().map(|f| f.to_string()).collect();
+                resolve_features(cargo_toml.parent().unwrap(), Some(&requested_features)).unwrap()
+            }
+
+            #[test]
+            fn implicit_feature() {
+                let features = resolve(&["serde"]);
+
+                assert_set_eq!(features, ["serde".to_string()]);
+            }
+
+            #[test]
+            fn dependency_feature() {
+                let features = resolve(&["logging"]);
+
+                assert_set_eq!(features, ["logging".to_string(), "nix".to_string()]);
+            }
+
+            #[test]
+            fn weak_dependency_feature() {
+                let features = resolve(&["serde?/derive"]);
+
+                assert!(features.is_empty());
+            }
+
+            #[test]
+            fn dependency_enabled_with_dep_prefix() {
+                let features = resolve(&["std", "libc/extra_traits"]);
+
+                assert_set_eq!(features, ["std".to_string()]);
+            }
+        }
+
+        #[test]
+        fn no_default_feature() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir
+                .create_file("Cargo.toml", "[package]\nname = \"test-crate\"\n")
+                .unwrap();
+
+            let features = resolve_features(cargo_toml.parent().unwrap(), None).unwrap();
+
+            assert!(features.is_empty());
+        }
+
+        #[test]
+        fn missing_manifest() {
+            let temp_dir = TempDir::new();
+            let dummy = temp_dir.create_file("dummy", "").unwrap();
+
+            let result = resolve_features(dummy.parent().unwrap(), None);
+
+            assert!(matches!(
+                result,
+                Err(LibraryMetadataError::MissingManifest(_))
+            ));
+        }
+
+        #[test]
+        fn crate_directory() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir.create_file("Cargo.toml", CARGO_TOML).unwrap();
+            let lib_rs = temp_dir.create_file("src/lib.rs", "").unwrap();
+
+            let directory = find_crate_directory(&lib_rs);
+
+            assert_eq!(directory, Some(cargo_toml.parent().unwrap().to_path_buf()));
+        }
+    }
+
+    mod dependencies {
+        use super::*;
+
+        const CARGO_TOML: &str = r#"
+[package]
+name = "test-crate"
+version = "0.1.0"
+
+[dependencies]
+serde = "1.0"
+tree-sitter = { version = "0.24" }
+json = { package = "serde_json", version = "1.0" }
+
+[target.'cfg(unix)'.dependencies]
+libc = "0.2"
+
+[dev-dependencies]
+tempfile = "3"
+"#;
+
+        #[test]
+        fn plain_dependency() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir.create_file("Cargo.toml", CARGO_TOML).unwrap();
+
+            let dependencies = resolve_dependencies(cargo_toml.parent().unwrap()).unwrap();
+
+            assert_eq!(dependencies.get("serde"), Some(&"serde".to_string()));
+        }
+
+        #[test]
+        fn hyphenated_dependency() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir.create_file("Cargo.toml", CARGO_TOML).unwrap();
+
+            let dependencies = resolve_dependencies(cargo_toml.parent().unwrap()).unwrap();
+
+            assert_eq!(
+                dependencies.get("tree_sitter"),
+                Some(&"tree_sitter".to_string())
+            );
+        }
+
+        #[test]
+        fn renamed_dependency() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir.create_file("Cargo.toml", CARGO_TOML).unwrap();
+
+            let dependencies = resolve_dependencies(cargo_toml.parent().unwrap()).unwrap();
+
+            assert_eq!(dependencies.get("json"), Some(&"serde_json".to_string()));
+        }
+
+        #[test]
+        fn target_dependency() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir.create_file("Cargo.toml", CARGO_TOML).unwrap();
+
+            let dependencies = resolve_dependencies(cargo_toml.parent().unwrap()).unwrap();
+
+            assert_eq!(dependencies.get("libc"), Some(&"libc".to_string()));
+        }
+
+        #[test]
+        fn dev_dependency() {
+            let temp_dir = TempDir::new();
+            let cargo_toml = temp_dir.create_file("Cargo.toml", CARGO_TOML).unwrap();
+
+            let dependencies = resolve_dependencies(cargo_toml.parent().unwrap()).unwrap();
+
+            assert!(!dependencies.contains_key("tempfile"));
+        }
+    }
+
+    mod out_directories {
+        use super::*;
+
+        fn create_crate_directory(temp_dir: &TempDir, path: &str) -> PathBuf {
+            let cargo_toml = temp_dir
+                .create_file(&format!("{path}/Cargo.toml"), "")
+                .unwrap();
+            cargo_toml.parent().unwrap().to_path_buf()
+        }
+
+        #[test]
+        fn profile_directory() {
+            let temp_dir = TempDir::new();
+            let bindings_rs = temp_dir
+                .create_file("target/debug/build/test-crate-0123abcd/out/bindings.rs", "")
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "target/debug/build/test-crate-sys-0123abcd/out/bindings.rs",
+                    "",
+                )
+                .unwrap();
+            let crate_directory = create_crate_directory(&temp_dir, ".");
+
+            let directories = find_out_directories(&crate_directory, "test-crate");
+
+            assert_eq!(
+                directories,
+                vec![bindings_rs.parent().unwrap().to_path_buf()]
+            );
+        }
+
+        #[test]
+        fn cross_compilation_directory() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "target/x86_64-unknown-linux-gnu/release/build/test-crate-0123abcd/out/a.rs",
+                    "",
+                )
+                .unwrap();
+            let crate_directory = create_crate_directory(&temp_dir, ".");
+
+            let directories = find_out_directories(&crate_directory, "test_crate");
+
+            assert_eq!(directories.len(), 1);
+        }
+
+        #[test]
+        fn workspace_target_directory() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file("target/debug/build/test-crate-0123abcd/out/bindings.rs", "")
+                .unwrap();
+            let crate_directory = create_crate_directory(&temp_dir, "crates/test-crate");
+
+            let directories = find_out_directories(&crate_directory, "test-crate");
+
+            assert_eq!(directories.len(), 1);
+        }
+
+        #[test]
+        fn no_target_directory() {
+            let temp_dir = TempDir::new();
+            let crate_directory = create_crate_directory(&temp_dir, ".");
+
+            let directories = find_out_directories(&crate_directory, "test-crate");
+
+            assert!(directories.is_empty());
+        }
+    }
 }
@@ -6,7 +6,7 @@ use tree_sitter::Parser;
 
 pub fn setup_parser() -> Parser {
     let mut parser = Parser::new();
-    let analyser = RustExtractor;
+    let analyser = RustExtractor::new();
     parser
         .set_language(&analyser.get_parser_language())
         .unwrap();